@@ -1,4 +1,5 @@
 use rust_higher_kined_types::const_generic::{Array, Matrix, compile_time_size_check, demonstrate_different_sizes};
+use rust_higher_kined_types::gat::Stream;
 
 fn test_const_generics_type_level_programming() {
     println!("5. === Const Generics and Type-Level Programming ===");
@@ -38,6 +39,17 @@ fn test_const_generics_type_level_programming() {
         print!("{}", item);
     }
     println!();
+
+    // iter_mut로 제자리 수정, contains로 포함 여부 확인
+    for item in arr1.iter_mut() {
+        *item += 1;
+    }
+    println!("      After incrementing via iter_mut:");
+    print!("      ");
+    arr1.display();
+    println!("      arr1.contains(&100) = {}, arr1.contains(&5) = {}", arr1.contains(&100), arr1.contains(&5));
+    // Display 구현 덕분에 문자열로 캡처하거나 다른 출력에 섞어 쓸 수 있다
+    println!("      Inline via Display: arr1 = {}, {} chars", arr1, arr1.to_string().len());
     println!();
 
     // 2. 타입 레벨 배열 연결 (원래 구현된 방식 사용)
@@ -55,6 +67,29 @@ fn test_const_generics_type_level_programming() {
     let combined = small_arr.concat_with_3(&medium_arr);
     println!("    Combined array (2+3=5): ");
     combined.display();
+
+    // 같은 크기의 배열끼리만 원소별 연산 가능 (크기가 타입에 포함되므로)
+    let lhs: Array<i32, 3> = Array::from_array([1, 2, 3]);
+    let rhs: Array<i32, 3> = Array::from_array([10, 20, 30]);
+    println!("    {} + {} = {}", lhs, rhs, lhs + rhs);
+    println!("    {} - {} = {}", rhs, lhs, rhs - lhs);
+    println!("    {} * {} = {}", lhs, rhs, lhs * rhs);
+    println!("    ❌ Array<i32, 3> + Array<i32, 2>                  // Different types - does not compile");
+
+    // 술어 기반 질의: 모두/하나라도/개수
+    let readings: Array<i32, 4> = Array::from_array([12, 7, 3, 25]);
+    println!("    {} all positive? {}", readings, readings.all(|&x| x > 0));
+    println!("    {} any over 20? {}, count over 5: {}", readings, readings.any(|&x| x > 20), readings.count_where(|&x| x > 5));
+
+    // 정렬된 복사본: 길이 N은 타입에 그대로 유지
+    let unsorted: Array<i32, 3> = Array::from_array([3, 1, 2]);
+    println!("    {} sorted: {}, descending: {}", unsorted, unsorted.sorted(), unsorted.sorted_by(|a, b| b.cmp(a)));
+
+    // 고정 크기 청크로 분할: CHUNK * COUNT == N 이 컴파일 타임에 검사됨
+    let six: Array<i32, 6> = Array::from_array([1, 2, 3, 4, 5, 6]);
+    let halves: Array<Array<i32, 3>, 2> = six.chunks();
+    println!("    {} chunked by 3 = {}", six, halves);
+    println!("    ❌ six.chunks::<4, 1>()                             // 4 * 1 != 6 - does not compile");
     println!();
 
     // 3. 행렬 연산 (원래 구현된 방식 사용)
@@ -85,6 +120,74 @@ fn test_const_generics_type_level_programming() {
     let result = matrix_2x3.multiply_with_3x2(&matrix_3x2);
     println!("    Result A × B ({}x{}):", result.rows(), result.cols());
     result.display();
+
+    // 3-1. 행/열 추출: 길이가 타입에 포함된 Array로 복사
+    println!("[3-1] 🔍 Extracting Rows and Columns:");
+    if let Some(row) = matrix_2x3.row(1) {
+        println!("    A.row(1): Array<i32, {}> = {:?}", row.len(), row.iter().collect::<Vec<_>>());
+    }
+    if let Some(col) = matrix_2x3.col(2) {
+        println!("    A.col(2): Array<i32, {}> = {:?}", col.len(), col.iter().collect::<Vec<_>>());
+    }
+    println!("    A.row(5): {:?}", matrix_2x3.row(5).map(|row| row.len()));
+
+    // 3-2. 평탄한(row-major) 슬라이스로부터 행렬 생성
+    println!("[3-2] 🧱 Building from Flat Data:");
+    let flat = [1, 2, 3, 4, 5, 6];
+    if let Ok(from_flat) = Matrix::<i32, 2, 3>::from_flat(&flat) {
+        from_flat.display();
+    }
+    println!("    from_flat(5 elements): {:?}", Matrix::<i32, 2, 3>::from_flat(&flat[..5]).err());
+
+    // 3-3. GAT Stream으로 행 순회
+    println!("[3-3] 🔁 Streaming Rows:");
+    let mut rows = matrix_2x3.rows_stream();
+    while let Some((row, index)) = rows.next_with_position() {
+        println!("    Row {}: {:?}", index, row);
+    }
+
+    // 3-4. 차원을 유지한 채 모든 원소 변환
+    println!("[3-4] 🔄 Mapping Elements (dimensions preserved):");
+    let squared = matrix_2x3.map(|x| x * x);
+    println!("    A squared ({}x{}):", squared.rows(), squared.cols());
+    squared.display();
+    let as_f64: Matrix<f64, 2, 3> = matrix_2x3.map(|&x| x as f64 / 2.0);
+    println!("    A / 2 as Matrix<f64, 2, 3>:");
+    as_f64.display();
+    println!();
+
+    // 3-5. 행 연산으로 가우스 소거: 3x3 행렬을 행 사다리꼴로 변환
+    println!("[3-5] 🧮 Row Operations (Gaussian elimination):");
+    let mut system = Matrix::<f64, 3, 3>::from_data([[2.0, 1.0, -1.0], [-3.0, -1.0, 2.0], [-2.0, 1.0, 2.0]]);
+    for pivot in 0..system.rows() {
+        // 부분 피벗팅: 피벗 열에서 절댓값이 가장 큰 행을 위로
+        let magnitude = |r: usize| system.get(r, pivot).unwrap().abs();
+        let best = (pivot..system.rows()).max_by(|&a, &b| magnitude(a).total_cmp(&magnitude(b))).unwrap();
+        system.swap_rows(pivot, best).unwrap();
+        let lead = *system.get(pivot, pivot).unwrap();
+        if lead == 0.0 {
+            continue;
+        }
+        system.scale_row(pivot, 1.0 / lead).unwrap();
+        for r in pivot + 1..system.rows() {
+            let factor = *system.get(r, pivot).unwrap();
+            system.add_scaled_row(r, pivot, -factor).unwrap();
+        }
+    }
+    println!("    Row-echelon form (rounded to 2 decimals):");
+    system.map(|x| (x * 100.0).round() / 100.0 + 0.0).display();
+    println!();
+
+    // 3-6. 부동소수점 행렬은 오차 범위 안에서 비교
+    println!("[3-6] 🎯 Approximate Equality (epsilon = 1e-9):");
+    let left = Matrix::<f64, 2, 3>::from_data([[0.1, 0.2, 0.3], [1.0, 2.0, 3.0]]);
+    let right = Matrix::<f64, 3, 2>::from_data([[1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+    let product = left.multiply_with_3x2(&right);
+    let expected = Matrix::<f64, 2, 2>::from_data([[0.3, 0.5], [3.0, 5.0]]);
+    println!("    Computed product:");
+    product.display();
+    println!("    Exactly 0.3? {}", *product.get(0, 0).unwrap() == 0.3);
+    println!("    approx_eq(expected, 1e-9): {}", product.approx_eq(&expected, 1e-9));
     println!();
 
     // 4. 컴파일 타임 크기 비교
@@ -138,6 +241,21 @@ fn test_const_generics_type_level_programming() {
     
     println!("    🚀 All sizes known at compile time - zero runtime overhead!");
     println!("    🔒 Type system prevents mixing incompatible buffer sizes!");
+    println!();
+
+    // 7. 정사각 행렬의 행렬식
+    println!("[7] 🧮 Square Matrix Determinants:");
+
+    let square_2x2: Matrix<i32, 2, 2> = Matrix::from_data([[3, 8], [4, 6]]);
+    square_2x2.display();
+    println!("    det = {}", square_2x2.determinant());
+
+    let square_3x3: Matrix<i32, 3, 3> = Matrix::from_data([[6, 1, 1], [4, -2, 5], [2, 8, 7]]);
+    square_3x3.display();
+    println!("    det = {}", square_3x3.determinant());
+
+    println!("    💡 determinant() only exists for Matrix<T, N, N> with N <= 4");
+    println!("    ❌ Matrix<2x3>.determinant()                      // Not square - does not compile");
 }
 
 fn main() {
@@ -1,16 +1,13 @@
 //
 // Generic Associated Types (GATs)
 // 
-use rust_higher_kined_types::gat::{StringStream, Stream, IntStream};
+use rust_higher_kined_types::gat::{StringStream, Stream, StreamExt, PeekableStream, IntStream, SliceStream, LineStream, ReaderStream, CsvStream, MutStream, SliceMutStream, SeekableStream, KeyValueStream, JsonTokenStream, CharStream, DynStream, BidiStream, SplittableStream, StrStream, HistoryStream, ParseStream, ParseError, ByteChunkStream, RangeStream, SentenceStream, ExactSizeStream, RoundRobin, TryStream, FallibleLineStream, CheckpointStream};
 
 fn test_generic_associated_types() {
     println!("4. === Generic Associated Types (GATs) ===");
 
     println!("--- String Stream ---");
-    let mut stream = StringStream {
-        data: "Hello world from the stream".to_string(),
-        position: 0,
-    };
+    let mut stream = StringStream::new("Hello world from the stream");
     
     while let Some(word) = stream.next() {
         println!("    Word: {}", word);
@@ -24,6 +21,45 @@ fn test_generic_associated_types() {
     }
     println!("    Final position: {}", stream.position);
 
+    println!("--- String Stream with multi-byte text and whitespace runs ---");
+    let mut korean_stream = StringStream::new("  안녕   world\t하세요 ");
+    while let Some((word, pos)) = korean_stream.next_with_position() {
+        println!("    Word: {}, byte position: {}", word, pos);
+    }
+
+    println!("--- String Stream with custom delimiters ---");
+    let mut csv_stream = StringStream::with_delimiter("name,,age,city", ',');
+    while let Some((field, pos)) = csv_stream.next_with_position() {
+        println!("    Field: {}, position: {}", field, pos);
+    }
+    let mut keep_empty_stream = StringStream::with_delimiter("name,,age,city", ',').keep_empty(true);
+    while let Some(field) = keep_empty_stream.next() {
+        println!("    Field (keep empty): {:?}", field);
+    }
+    let mut path_stream = StringStream::with_split_fn("usr/local;bin", |c| c.is_ascii_punctuation());
+    while let Some(part) = path_stream.next() {
+        println!("    Part: {}", part);
+    }
+
+    println!("--- Char Stream (one char per step, byte positions) ---");
+    let mut char_stream = CharStream::new("aé🦀");
+    while let Some((c, pos)) = char_stream.next_with_position() {
+        println!("    Char: {}, byte position: {}, len: {}", c, pos, c.len_utf8());
+    }
+
+    println!("--- Sentence Stream (abbreviation-aware) ---");
+    let paragraph = "Streams lend items. Do they outlive next()? No! Use e.g. owned() then";
+    let mut sentences = SentenceStream::new(paragraph);
+    while let Some((sentence, pos)) = sentences.next_with_position() {
+        println!("    Sentence at byte {}: {:?}", pos, sentence);
+    }
+
+    println!("--- Token metadata (enumerate / with_metadata) ---");
+    let mut infos = StringStream::new("index  vs   byte offset").with_metadata();
+    while let Some(info) = infos.next() {
+        println!("    #{} {:?} at byte {} ({} bytes)", info.index, info.text, info.byte_offset, info.len);
+    }
+
     println!("--- Int Stream ---");
     let mut int_stream = IntStream {
         data: vec![1, 2, 3, 4, 5],
@@ -40,6 +76,338 @@ fn test_generic_associated_types() {
         println!("    Number: {}, position: {}", num, pos);
     }
     println!("    Final position: {}", int_stream.position);
+    int_stream.reset_position();
+    int_stream.next();
+    println!("    size_hint after one item: {:?}, len: {}", int_stream.size_hint(), int_stream.len());
+    println!("    StringStream size_hint (unknown): {:?}", StringStream::new("a b").size_hint());
+
+    println!("--- Range Stream (lazy, owned items) ---");
+    if let Ok(mut evens) = RangeStream::new(0, 10, 2) {
+        while let Some((num, pos)) = evens.next_with_position() {
+            println!("    Number: {}, position: {}", num, pos);
+        }
+    }
+
+    println!("--- Mutable items (MutStream) ---");
+    int_stream.reset_position();
+    while let Some(num) = int_stream.next_mut() {
+        *num *= 2;
+    }
+    println!("    Doubled in place: {:?}", int_stream.data);
+    let mut names = vec!["ada".to_string(), "grace".to_string()];
+    let mut name_stream = SliceMutStream::from_slice(&mut names);
+    while let Some(name) = name_stream.next_mut() {
+        name.make_ascii_uppercase();
+    }
+    println!("    Uppercased in place: {:?}", names);
+
+    println!("--- Look-behind buffer (HistoryStream<S, O, K>) ---");
+    let mut recent: HistoryStream<_, _, 3> = HistoryStream::new(StringStream::new("remember only the last three words"));
+    while let Some(word) = recent.next() {
+        println!("    Word: {}", word);
+    }
+    println!("    History (K = 3): {:?}, last: {:?}", recent.history(), recent.last());
+
+    println!("--- Searching (find / position) ---");
+    let mut search = IntStream { data: vec![3, 7, 10, 12], position: 0 };
+    println!("    First even: {:?}", search.find(|x: &&i32| **x % 2 == 0));
+    search.reset_position();
+    println!("    Index of first even: {:?}", StreamExt::position(&mut search, |x: &&i32| **x % 2 == 0));
+
+    println!("--- Stepping backwards (prev / rev) ---");
+    let mut cursor = IntStream { data: vec![10, 20, 30], position: 0 };
+    let forward = (cursor.next().copied(), cursor.next().copied());
+    println!("    Forward: {:?}, back: {:?}, forward again: {:?}", forward, cursor.prev().copied(), cursor.next().copied());
+    let mut countdown = IntStream { data: (1..=5).collect(), position: 0 }.rev();
+    while let Some((num, pos)) = countdown.next_with_position() {
+        println!("    Reversed: {}, position: {}", num, pos);
+    }
+
+    println!("--- Slice Stream (borrowed, generic over T) ---");
+    let names = ["Ferris", "Corro", "Rusty"];
+    let mut slice_stream = SliceStream::from_slice(&names);
+    while let Some((name, pos)) = slice_stream.next_with_position() {
+        // name은 stream을 빌리고 있으므로, remaining()은 name을 다 쓴 뒤에만 호출 가능
+        println!("    Name: {}, position: {}", name, pos);
+    }
+    println!("    Remaining: {}", slice_stream.remaining());
+
+    println!("--- Two cursors over shared data (split_at / tee) ---");
+    let scores = [90, 75, 60, 85, 70];
+    let (mut top, mut others) = SliceStream::from_slice(&scores).split_at(2);
+    while let Some((score, pos)) = others.next_with_position() {
+        println!("    Others: {}, position: {}", score, pos);
+    }
+    while let Some((score, pos)) = top.next_with_position() {
+        println!("    Top two: {}, position: {}", score, pos);
+    }
+    // StringStream은 String을 소유하므로, 빌린 StrStream으로 바꾼 뒤 커서를 복제한다
+    let sentence = StringStream::new("tee gives two cursors");
+    let (mut ahead, mut behind) = sentence.as_str_stream().tee();
+    ahead.next();
+    ahead.next();
+    println!("    Ahead: {:?}, behind: {:?}", ahead.next(), behind.next());
+    let (mut head, mut tail) = StrStream::new("alpha beta gamma delta").split_at(1);
+    println!("    Head: {:?}, tail starts with: {:?}", head.next(), tail.next());
+
+    println!("--- Byte chunks with a const-generic size (ByteChunkStream<N>) ---");
+    let packet: Vec<u8> = (1..=10).collect();
+    let mut words32 = ByteChunkStream::<4>::new(&packet);
+    while let Some((chunk, offset)) = words32.next_with_position() {
+        println!("    Chunk at byte {}: {:?} -> u32 {:#010x}", offset, chunk, u32::from_le_bytes(*chunk));
+    }
+    println!("    Remainder: {:?}", words32.remainder());
+
+    println!("--- Line Stream (CRLF aware, empties skipped) ---");
+    let text = "[server]\r\nhost=localhost\r\n\r\nport=8080\n";
+    let mut line_stream = LineStream::new(text).with_skip_empty(true);
+    while let Some((line, offset)) = line_stream.next_with_position() {
+        println!("    Line at byte {}: {}", offset, line);
+    }
+
+    println!("--- CSV Stream (borrowed records and fields) ---");
+    let mut csv_stream = CsvStream::new("name,motto\nAda,\"Hello, \"\"world\"\"\"\nBob,\n");
+    while let Some((record, pos)) = csv_stream.next_with_position() {
+        let fields: Vec<String> = (0..record.len())
+            .map(|i| record.unescaped(i).unwrap_or_default().into_owned())
+            .collect();
+        println!("    Record at {}: {:?}", pos, fields);
+    }
+
+    println!("--- Key/Value Stream (config lines) ---");
+    let config = format!("# server\nhost = localhost\nport = {}\n\nhost = 0.0.0.0", 8080);
+    let mut kv_stream = KeyValueStream::new(&config);
+    while let Some(((key, value), pos)) = kv_stream.next_with_position() {
+        println!("    {} = {} (line at {})", key, value, pos);
+    }
+    let settings = KeyValueStream::new(&config).collect_map();
+    println!("    Effective host (last wins): {:?}", settings.get("host"));
+
+    println!("--- JSON Token Stream (allocation-free lexer) ---");
+    let mut json_stream = JsonTokenStream::new(r#"{"name": "crab", "legs": 1e1, "ok": tru}"#);
+    while let Some((token, pos)) = json_stream.next_with_position() {
+        println!("    {:>2}: {:?}", pos, token);
+    }
+
+    println!("--- Parsing with error positions (ParseStream) ---");
+    // set NAME = VALUE 문법: 실패하면 문제가 된 단어의 byte 위치를 알려준다
+    fn parse_set(input: &str) -> Result<(&str, &str), ParseError> {
+        let mut parser = ParseStream::new(input);
+        parser.expect(|t| t == "set", "keyword `set`")?;
+        let name = parser.expect(|t| t.chars().all(|c| c.is_ascii_alphabetic()), "a name")?;
+        parser.expect(|t| t == "=", "`=`")?;
+        let value = parser.expect(|_| true, "a value")?;
+        parser.expect_end()?;
+        Ok((name, value))
+    }
+    for input in ["set WIDTH = 80", "let WIDTH = 80", "set WIDTH := 80", "set WIDTH ="] {
+        match parse_set(input) {
+            Ok((name, value)) => println!("    {:?} -> {} = {}", input, name, value),
+            Err(e) => println!("    {:?} -> {}", input, e),
+        }
+    }
+    let mut flags = ParseStream::new("-v -q input.txt");
+    while let Some(flag) = flags.try_next_matching(|t| t.starts_with('-')) {
+        println!("    Flag: {} ({} tokens left)", flag, flags.remaining_hint());
+    }
+
+    println!("--- Reader Stream (lending from an internal buffer) ---");
+    let mut reader_stream = ReaderStream::new(std::io::Cursor::new("one\ntwo\nthree\n"));
+    while let Some(line) = reader_stream.next() {
+        // line은 reader_stream 내부 버퍼를 빌린 것 - 다음 next() 전에 사용을 마쳐야 한다
+        println!("    Read: {}", line);
+    }
+    // Seek가 없는 리더(stdin, 소켓, 파이프 등)도 그대로 사용할 수 있다
+    let mut piped = ReaderStream::new(&b"from a pipe
+"[..]);
+    while let Some((line, offset)) = piped.next_with_position() {
+        println!("    Piped @{}: {}", offset, line);
+    }
+
+    println!("--- Fallible reads (TryStream) ---");
+    // 읽기 실패를 Result로 돌려주므로 ? 연산자와 while let을 함께 쓸 수 있다
+    fn print_lines<T: TryStream>(stream: &mut T) -> Result<usize, T::Error>
+    where
+        for<'a> T::Ok<'a>: std::fmt::Display,
+    {
+        let mut count = 0;
+        while let Some(line) = stream.try_next()? {
+            println!("    Line: {}", line);
+            count += 1;
+        }
+        Ok(count)
+    }
+    let mut fallible = FallibleLineStream::new(std::io::Cursor::new("alpha\r\nbeta\n"));
+    println!("    Read {:?} lines", print_lines(&mut fallible).map_err(|e| e.kind()));
+    let mut never_fails = StringStream::new("infallible words").into_try_stream();
+    println!("    Read {:?} words", print_lines(&mut never_fails));
+
+    println!("--- Seeking back to a saved position ---");
+    let mut seek_stream = StringStream::new("seek back to replay words");
+    seek_stream.next();
+    let saved = seek_stream.next_with_position().map(|(_, pos)| pos).unwrap_or(0);
+    seek_stream.next();
+    if seek_stream.seek(saved).is_ok() {
+        println!("    Replayed from {}: {:?}", saved, seek_stream.next());
+    }
+    if let Err(e) = StringStream::new("안녕").seek(1) {
+        println!("    seek(1) on \"안녕\": {}", e);
+    }
+
+    println!("--- Backtracking with checkpoints ---");
+    let mut input = StringStream::new("fn main ( )");
+    let before = input.checkpoint();
+    let is_let = input.next() == Some("let");
+    println!("    Tried `let` statement: {}", if is_let { "matched" } else { "no match, backtracking" });
+    input.restore(before).unwrap();
+    println!("    Re-read after restore: {:?}", input.next());
+    if let Err(e) = StringStream::new("fn main ( )").restore(input.checkpoint()) {
+        println!("    Restoring into another stream: {}", e);
+    }
+
+    println!("--- Peeking without consuming ---");
+    let mut tokens = StringStream::new("let answer = 42");
+    while let Some(token) = tokens.peek() {
+        let kind = if token.chars().all(|c| c.is_ascii_digit()) { "number" } else { "word" };
+        println!("    Next is a {}: {}", kind, tokens.next().unwrap());
+    }
+
+    println!("--- Stream adapters (skip / take / map / filter) ---");
+    let mut lengths = StringStream::new("skip these words and measure the rest")
+    .skip(2)
+    .filter(|word: &&str| word.len() > 3)
+    .take(3)
+    .map(str::len);
+
+    while let Some(len) = lengths.next() {
+        println!("    Length: {}", len);
+    }
+
+    println!("--- Sampling every n-th item (step_by) ---");
+    let nine = IntStream { data: (1..=9).collect(), position: 0 };
+    if let Ok(mut every_second) = nine.step_by(2) {
+        while let Some((num, pos)) = every_second.next_with_position() {
+            println!("    Sampled: {}, position: {}", num, pos);
+        }
+    }
+    println!("    step_by(0) -> {:?}", StringStream::new("a b").step_by(0).err());
+
+    println!("--- Owned vs borrowed mapping (map_item / map_borrow) ---");
+    let mut word_lengths = StringStream::new("owned outputs stop borrowing").map_item(|word: &str| word.len()).take(2);
+    while let Some(len) = word_lengths.next() {
+        println!("    Owned length: {}", len);
+    }
+    // 출력이 입력과 같은 lifetime으로 빌려지는 경우: for<'a> FnMut(&'a str) -> &'a str
+    let mut trimmed = StringStream::with_delimiter(" padded , fields ,here", ',').map_borrow(|field| field.trim()).take(2);
+    while let Some((field, pos)) = trimmed.next_with_position() {
+        println!("    Borrowed sub-slice: {:?}, position: {}", field, pos);
+    }
+
+    println!("--- Grouping items (chunks / windows) ---");
+    let seven = || IntStream { data: (1..=7).collect(), position: 0 };
+    if let Ok(mut chunks) = seven().chunks(3, |x: &i32| *x) {
+        while let Some(chunk) = chunks.next() {
+            println!("    Chunk: {:?}", chunk);
+        }
+    }
+    if let Ok(mut windows) = StringStream::new("a rolling window of words").windows(2, str::to_string) {
+        while let Some((window, pos)) = windows.next_with_position() {
+            println!("    Window at {}: {:?}", pos, window);
+        }
+    }
+    println!("    chunks(0) -> {:?}", seven().chunks(0, |x: &i32| *x).err());
+
+    println!("--- Terminal operations (collect_owned / collect_map / count / fold / inspect) ---");
+    let owned: Vec<String> = StringStream::new("borrowed words become owned").collect_owned(str::to_uppercase);
+    println!("    Collected: {:?}", owned);
+    let by_offset = StringStream::new("index words by offset")
+        .with_metadata()
+        .collect_map(|token| (token.byte_offset, token.text.to_string()));
+    let mut offsets: Vec<_> = by_offset.keys().copied().collect();
+    offsets.sort();
+    for offset in offsets {
+        println!("    collect_map[{}] = {}", offset, by_offset[&offset]);
+    }
+    println!("    Count: {}", StringStream::new("one two three").count());
+    StringStream::new("for each prints every word").for_each(|word: &str| println!("    for_each: {}", word));
+    let sum = IntStream { data: vec![1, 2, 3, 4], position: 0 }.fold(0, |acc, x: &i32| acc + x);
+    println!("    Sum: {}", sum);
+    let even_sum = IntStream { data: vec![1, 2, 3, 4, 5, 6], position: 0 }
+        .filter(|x: &&i32| **x % 2 == 0)
+        .inspect(|x: &&i32| println!("    inspect: {} passed the filter", x))
+        .fold(0, |acc, x: &i32| acc + x);
+    println!("    Sum of evens: {}", even_sum);
+
+    println!("--- Predicate-driven adapters (take_while / skip_while / scan) ---");
+    let mut short_words = StringStream::new("tiny small enormous wee").take_while(|word: &&str| word.len() < 6);
+    while let Some(word) = short_words.next() {
+        println!("    Short word: {}", word);
+    }
+    let mut positives = IntStream { data: vec![-2, -1, 3, -4], position: 0 }.skip_while(|x: &&i32| **x < 0);
+    while let Some(x) = positives.next() {
+        println!("    After negatives: {}", x);
+    }
+    let mut running = IntStream { data: vec![1, 2, 3, 4], position: 0 }.scan(0, |total: &mut i32, x: &i32| {
+        *total += x;
+        Some(*total)
+    });
+    while let Some(sum) = running.next() {
+        println!("    Running sum: {}", sum);
+    }
+
+    println!("--- Run-length encoding (rle) ---");
+    let mut runs = IntStream { data: vec![1, 1, 2, 3, 3, 3], position: 0 }.rle();
+    while let Some(((value, count), position)) = runs.next_with_position() {
+        println!("    {} x{} (run starts at {})", value, count, position);
+    }
+
+    println!("--- Fused streams ---");
+    let mut fused = IntStream { data: vec![1, 2], position: 0 }.fuse();
+    while fused.next().is_some() {}
+    fused.get_mut().data.push(3);
+    let after_growth = fused.next().copied();
+    println!("    After growing the source: {:?} (exhausted: {})", after_growth, fused.is_exhausted());
+
+    println!("--- Heterogeneous streams (Box<dyn DynStream<String>>) ---");
+    let mut mixed: Vec<Box<dyn DynStream<String>>> = vec![
+        Box::new(StringStream::new("words and").owned(str::to_string)),
+        Box::new(IntStream { data: vec![1, 2], position: 0 }.owned(|x: &i32| x.to_string())),
+    ];
+    for stream in mixed.iter_mut() {
+        while let Some(item) = stream.next_owned() {
+            println!("    Owned item: {}", item);
+        }
+    }
+
+    println!("--- Iterator bridges (iter_owned / copied) ---");
+    let longest = StringStream::new("bridge into plain iterators").iter_owned(str::to_string).max_by_key(|w| w.len());
+    println!("    Longest word: {:?}", longest);
+    let total: i32 = IntStream { data: vec![1, 2, 3], position: 0 }.copied().sum();
+    println!("    Sum via Iterator::sum: {}", total);
+
+    println!("--- Combining streams (zip / chain / interleave / round robin) ---");
+    let ranks = IntStream { data: vec![1, 2, 3], position: 0 };
+    let mut ranked = StringStream::new("gold silver bronze copper").zip(ranks);
+    while let Some((medal, rank)) = ranked.next() {
+        println!("    #{} {}", rank, medal);
+    }
+    let mut both = StringStream::new("first half").chain(StringStream::new("second half"));
+    while let Some((word, pos)) = both.next_with_position() {
+        println!("    Word: {}, position (restarts per stream): {}", word, pos);
+    }
+    let odds = IntStream { data: vec![1, 3, 5, 7], position: 0 };
+    let mut alternating = odds.interleave(IntStream { data: vec![2, 4], position: 0 });
+    while let Some(num) = alternating.next() {
+        println!("    Interleaved: {}", num);
+    }
+    let mut merged = RoundRobin::new([
+        StringStream::new("north1 north2"),
+        StringStream::new("east1"),
+        StringStream::new("south1 south2 south3"),
+    ]);
+    while let Some(word) = merged.next() {
+        println!("    Round robin: {}", word);
+    }
 }
 
 fn main() {
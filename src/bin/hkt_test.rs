@@ -1,11 +1,23 @@
 // 
 // Higher-Kinded Types (HKT)
 // 
-use rust_higher_kined_types::container::Container;
+use rust_higher_kined_types::container::{
+    lazy, saturating_scale, scale_container, widen_container, Container, ContainerBoth, ContainerCow, ContainerInPlace,
+    LazyContainer, MonadicContainer,
+};
+use rust_higher_kined_types::sparse_vec::SparseVec;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::time::Instant;
 
-// Now we can write generic code that works with any Container
-fn double_container<C: Container<Item = i32>>(container: C) -> C::Mapped<i64> {
-    container.map(|&x| x as i64 * 2)
+// Bind with `f`, then label every surviving item -- works for any MonadicContainer
+fn chain_and_label<C, U, F>(container: C, f: F) -> <C::Mapped<U> as Container>::Mapped<String>
+where
+    C: MonadicContainer,
+    F: FnMut(C::Item) -> C::Mapped<U>,
+    U: Display,
+{
+    container.and_then(f).map(|x| format!("<{}>", x))
 }
 
 fn test_container_higher_kinded_types() {
@@ -14,13 +26,85 @@ fn test_container_higher_kinded_types() {
     let option: Option<i32> = Some(1);
     let result: Result<i32, &str> = Ok(1);
 
-    let doubled_option = double_container(option);
-    let doubled_result = double_container(result);
+    // Now we can write generic code that works with any Container
+    let doubled_option: Option<i64> = scale_container(option, 2);
+    let doubled_result: Result<i64, &str> = scale_container(result, 2);
 
     println!("    Doubled Option: {:?}", doubled_option);
     println!("    Doubled Result: {:?}", doubled_result);
+
+    // Plain Rust arrays keep their length N through the mapping
+    let doubled_array: [i64; 3] = scale_container([1, 2, 3], 2);
+    println!("    Doubled [i32; 3]: {:?}", doubled_array);
+
+    let widened_vec: Vec<i16> = widen_container(vec![i8::MIN, 0, i8::MAX]);
+    println!("    Widened Vec (i8 -> i16): {:?}", widened_vec);
+
+    let saturated = saturating_scale(vec![i32::MAX, -1, i32::MIN], 2);
+    println!("    Saturating scale (x2): {:?}", saturated);
+
+    // Two-channel mapping rewrites Ok and Err in a single call
+    let failed: Result<i32, &str> = Err("not a number");
+    let mapped = failed.map_both(|x| x.to_string(), |e| e.len());
+    println!("    map_both on Err: {:?}", mapped);
+
+    let pair = (21, "pair");
+    println!("    map_both on tuple: {:?}", pair.map_both(|x| x * 2, |s| s.to_uppercase()));
+
+    // and_then flattens whatever container the closure returns, short-circuiting on empty
+    let parsed = chain_and_label(Some("12"), |s| s.parse::<i32>().ok());
+    let unparsed = chain_and_label(Some("twelve"), |s| s.parse::<i32>().ok());
+    println!("    and_then on Option: {:?}, {:?}", parsed, unparsed);
+    let checked: Result<u8, &str> = Ok(200);
+    let overflowed = chain_and_label(checked, |x| x.checked_add(100).ok_or("overflow"));
+    println!("    and_then on Result: {:?}", overflowed);
+    let repeated = chain_and_label(vec![1, 2, 3], |x| vec![x; x]);
+    println!("    and_then on Vec: {:?}", repeated);
+
+    // Cow-aware mapping only allocates when the closure really changes something
+    let words = "borrow until needed";
+    let untouched = Cow::Borrowed(words).map_cow(|&c| if c == '!' { '.' } else { c });
+    let touched = Cow::Borrowed(words).map_cow(|&c| if c == ' ' { '_' } else { c });
+    println!("    map_cow identity: {:?} (borrowed: {})", untouched, matches!(untouched, Cow::Borrowed(_)));
+    println!("    map_cow change:   {:?} (borrowed: {})", touched, matches!(touched, Cow::Borrowed(_)));
+
+    // Lazy containers defer every map until collection, then run them in one pass
+    let pending = lazy(vec![1, 2, 3])
+        .lazy_map(|x| x + 1)
+        .lazy_map(|x| x * 10)
+        .lazy_map(|x| format!("<{}>", x));
+    let collected: Vec<String> = pending.collect_container();
+    println!("    Lazy pipeline collected: {:?}", collected);
+
+    // Sparse containers keep their index set through the mapping
+    let mut sparse = SparseVec::new(1000);
+    sparse.insert(2, 1).unwrap();
+    sparse.insert(7, 2).unwrap();
+    sparse.insert(100, 3).unwrap();
+    let doubled_sparse: SparseVec<i64> = scale_container(sparse, 2);
+    println!(
+        "    Doubled SparseVec: {:?} (density {})",
+        doubled_sparse.iter().collect::<Vec<_>>(),
+        doubled_sparse.density()
+    );
+
+    // In-place mapping reuses the Vec buffer when the element type does not change
+    let large: Vec<i64> = (0..2_000_000).collect();
+    let copy = large.clone();
+    let started = Instant::now();
+    let allocated = Container::map(copy, |&x| x * 3 + 1);
+    let allocating_time = started.elapsed();
+    let started = Instant::now();
+    let in_place = large.map_in_place(|&x| x * 3 + 1);
+    let in_place_time = started.elapsed();
+    println!(
+        "    map (allocating): {:?}, map_in_place: {:?}, identical: {}",
+        allocating_time,
+        in_place_time,
+        allocated == in_place
+    );
 }
 
 fn main() {
     test_container_higher_kinded_types();
-} 
\ No newline at end of file
+} 
@@ -1,12 +1,14 @@
 // 
 // Higher-Ranked Types (HRT)
 // 
-use rust_higher_kined_types::with_lifetime::{process_any_lifetime, WordCounter, WithLifetime};
+use rust_higher_kined_types::with_lifetime::{
+    compose, process_any_lifetime, process_borrowed_outputs, process_inputs, process_pairs, Concat, FirstWord,
+    KeyValueParser, ProcessorRegistry, Stringify, UppercaseConverter, WordCounter, WithLifetime, WithTwoLifetimes,
+};
 use std::fmt::Debug;
 
 // 추가 예시를 위한 구현들
 struct LengthCounter;
-struct UppercaseConverter;
 struct CharCounter;
 
 impl<'a> WithLifetime<'a> for LengthCounter {
@@ -17,14 +19,6 @@ impl<'a> WithLifetime<'a> for LengthCounter {
     }
 }
 
-impl<'a> WithLifetime<'a> for UppercaseConverter {
-    type Output = String;
-
-    fn process(&self, input: &'a str) -> Self::Output {
-        input.to_uppercase()
-    }
-}
-
 impl<'a> WithLifetime<'a> for CharCounter {
     type Output = usize;
 
@@ -105,33 +99,28 @@ where
     }
 }
 
-// 고급 HRT 패턴: 중첩된 라이프타임 처리
-trait NestedProcessor<'a, 'b> {
-    type Output;
-    fn process_nested(&self, outer: &'a str, inner: &'b str) -> Self::Output;
-}
-
+// 고급 HRT 패턴: 중첩된 라이프타임 처리 (라이브러리의 WithTwoLifetimes 사용)
 struct CombiningProcessor;
 
-impl<'a, 'b> NestedProcessor<'a, 'b> for CombiningProcessor {
+impl<'a, 'b> WithTwoLifetimes<'a, 'b> for CombiningProcessor {
     type Output = String;
     
-    fn process_nested(&self, outer: &'a str, inner: &'b str) -> Self::Output {
+    fn process(&self, outer: &'a str, inner: &'b str) -> Self::Output {
         format!("Combined: '{}' + '{}'", outer, inner)
     }
 }
 
 fn use_nested_processor<P>(processor: P)
 where
-    for<'a, 'b> P: NestedProcessor<'a, 'b>,
-    for<'a, 'b> <P as NestedProcessor<'a, 'b>>::Output: Debug,
+    for<'a, 'b> P: WithTwoLifetimes<'a, 'b>,
+    for<'a, 'b> <P as WithTwoLifetimes<'a, 'b>>::Output: Debug,
 {
     println!("    🔄 Testing nested lifetime processor:");
     
     let outer_data = String::from("outer scope data");
     let inner_data = "inner static data";
     
-    let result = processor.process_nested(&outer_data, inner_data);
+    let result = processor.process(&outer_data, inner_data);
     println!("      Nested result: {:?}", result);
 }
 
@@ -175,6 +164,25 @@ fn test_with_lifetime_higher_ranked_types() {
     }
     println!();
 
+    // 설정에서 읽어 온 입력 목록 처리
+    println!("  Word Counter over inputs read from a config:");
+    let config = String::from("greeting = hello there\nfarewell = see you later\nempty =");
+    let config_inputs: Vec<&str> = config.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(_, value)| value.trim())
+        .collect();
+    for result in process_inputs(WordCounter, &config_inputs) {
+        println!("    {}", result);
+    }
+    println!();
+
+    // 2-1. 입력을 빌려 반환하는 프로세서 (Output = &'a str)
+    println!("[2-1] 🔖 Borrowed Output Processor:");
+    let sentence = String::from("borrowed output example");
+    println!("    FirstWord on '{}': {:?}", sentence, FirstWord.process(&sentence));
+    println!("    Collected as owned Strings: {:?}", process_borrowed_outputs(FirstWord));
+    println!();
+
     // 3. 클로저와 HRT
     println!("[3] 🎭 HRT with Closures:");
     apply_to_various_lifetimes(|s: &str| s.len());
@@ -197,9 +205,42 @@ fn test_with_lifetime_higher_ranked_types() {
     }
     println!();
 
+    // 5-1. 프로세서 합성: UppercaseConverter의 출력을 WordCounter의 입력으로 사용
+    println!("[5-1] 🧬 Processor Composition:");
+    let composed = compose(UppercaseConverter, WordCounter);
+    for input in ["hello composed world", "Rust", "a b c d"] {
+        println!("      '{}' -> uppercase -> word count = {}", input, composed.process(input));
+    }
+    println!();
+
+    // 5-2. 이름으로 프로세서 선택 (동적 디스패치, Output = String으로 통일)
+    println!("[5-2] 🗂️ Processor Registry:");
+    let mut registry = ProcessorRegistry::new();
+    registry
+        .register("upper", UppercaseConverter)
+        .register("words", Stringify(WordCounter));
+    let request = String::from("select processors at runtime");
+    for name in ["upper", "words", "missing"] {
+        match registry.run(name, &request) {
+            Some(output) => println!("      {} -> {}", name, output),
+            None => println!("      {} -> (no such processor)", name),
+        }
+    }
+    println!();
+
     // 6. 중첩 라이프타임 처리
     println!("[6] 🔄 Nested Lifetime Processing:");
     use_nested_processor(CombiningProcessor);
+
+    // Concat: 바깥(outer) 문자열과 안쪽(inner) 문자열을 결합
+    let outer = String::from("outer/");
+    {
+        let inner = String::from("inner");
+        println!("      Concat result: {:?}", Concat.process(&outer, &inner));
+    }
+    for result in process_pairs(Concat, &[("left-", "right"), ("static ", "pair")]) {
+        println!("      {}", result);
+    }
     println!();
 
     // 7. 함수 포인터와 HRT
@@ -262,6 +303,10 @@ fn test_with_lifetime_higher_ranked_types() {
     }
     
     parse_various_configs(SimpleConfigParser);
+
+    // 라이브러리의 KeyValueParser: 키와 값 모두 입력에서 빌려온다
+    let server_config = format!("server = localhost\nport = {}\n# comment line\nssl = true", 8080);
+    println!("      Key/value pairs: {:?}", KeyValueParser.process(&server_config));
     println!();
 
     // 9. 타입 안전성 검증
@@ -112,6 +112,19 @@ fn test_functor_mapping() {
     // 타입 변환
     let string_mapped = Some(42).fmap(|x| format!("숫자: {}", x));
     println!("Some(42).fmap(to_string) = {:?}", string_mapped);
+
+    // 내용은 버리고 형태(Some/None, Ok/Err)만 유지
+    println!("Some(5).void() = {:?}", Some(5).void());
+    let ok_val: Result<i32, &str> = Ok(1);
+    println!("Ok(1).map_const(\"x\") = {:?}", ok_val.map_const("x"));
+    let err_val: Result<i32, &str> = Err("실패");
+    println!("Err(\"실패\").map_const(\"x\") = {:?}", err_val.map_const("x"));
+
+    // NonEmpty: 원소가 최소 하나임을 API가 보장 (fmap/bind는 FnMut를 받는 고유 메서드)
+    let scores = NonEmpty::new(70, vec![85, 92]);
+    println!("NonEmpty.fmap(|x| x + 5) = {:?}", scores.clone().fmap(|x| x + 5).into_vec());
+    let expanded = scores.bind(|x| NonEmpty::new(x, vec![x / 10]));
+    println!("NonEmpty.bind(|x| [x, x / 10]) = {:?} (len {})", expanded.clone().into_vec(), expanded.len());
     
     println!("====================================\n");
 }
@@ -154,6 +167,10 @@ fn test_applicative() {
     let func6: Result<fn(i32) -> i32, &str> = Ok(|x| x * 3);
     let result6 = value6.apply(func6);
     println!("Err('값 에러').apply(Ok(|x| x * 3)) = {:?}", result6);
+
+    // map2: 두 Applicative 컨텍스트 안의 값에 이항 함수 적용
+    println!("map2(Some(2), Some(3), +) = {:?}", map2(Some(2), Some(3), |a, b| a + b));
+    println!("map2(Some(2), None, +) = {:?}", map2(Some(2), None::<i32>, |a, b| a + b));
     
     // 복합 Applicative 연산 예제
     println!("\n--- 복합 Applicative 연산 ---");
@@ -191,6 +208,14 @@ fn test_option_chaining() {
         |x| if x < 20 { Some(x + 10) } else { None }
     );
     println!("chain_option_operations(Some(15), double_if_positive, add_10_if_small) = {:?}", result2);
+
+    // Monad 트레이트만으로 표현된 제네릭 버전
+    let generic = chain_operations(
+        Some(5),
+        |x: i32| if x > 0 { Some(x * 2) } else { None },
+        |x: i32| if x < 20 { Some(x + 10) } else { None }
+    );
+    println!("chain_operations(Some(5), double_if_positive, add_10_if_small) = {:?}", generic);
     
     println!("=====================================\n");
 }
@@ -211,6 +236,14 @@ fn test_result_chaining() {
         |x| if x < 50 { Ok(x + 10) } else { Err("값이 너무 큼") }
     );
     println!("chain_result_operations(Ok(30), positive_double, bounded_add) = {:?}", result2);
+
+    // 같은 제네릭 함수가 Result에도 그대로 동작
+    let generic: Result<i32, &str> = chain_operations(
+        Ok(30),
+        |x: i32| if x > 0 { Ok(x * 2) } else { Err("음수는 허용되지 않음") },
+        |x: i32| if x < 50 { Ok(x + 10) } else { Err("값이 너무 큼") }
+    );
+    println!("chain_operations(Ok(30), positive_double, bounded_add) = {:?}", generic);
     
     println!("=====================================\n");
 }
@@ -278,6 +311,7 @@ fn test_practical_examples() {
     
     // 사용자 데이터 처리 예제
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct User {
         name: String,
         age: i32,
@@ -285,7 +319,7 @@ fn test_practical_examples() {
     }
     
     fn validate_age(age: i32) -> Result<i32, &'static str> {
-        if age >= 0 && age <= 150 {
+        if (0..=150).contains(&age) {
             Ok(age)
         } else {
             Err("유효하지 않은 나이입니다")
@@ -322,6 +356,28 @@ fn test_practical_examples() {
     // 잘못된 이메일로 사용자 생성
     let invalid_email_user = create_user("박민수".to_string(), 30, "invalid-email".to_string());
     println!("잘못된 이메일: {:?}", invalid_email_user);
+
+    // when/unless: 조건이 참일 때만 검증 단계를 실행 (거짓이면 Ok(())로 통과)
+    let check_adult = |age: i32| if age >= 18 { Ok(()) } else { Err("성인만 가입할 수 있습니다") };
+    let requires_adult = true;
+    println!("when(성인 인증 필요, 15세 검증) = {:?}", when(requires_adult, check_adult(15)));
+    println!("when(성인 인증 불필요, 15세 검증) = {:?}", when(!requires_adult, check_adult(15)));
+    println!("unless(관리자, 15세 검증) = {:?}", unless(true, check_adult(15)));
+
+    // then: 앞선 검증의 결과값은 버리고, 성공했을 때만 다음 값을 사용 (Haskell의 >>)
+    println!("check_adult(20).then(Ok(\"가입 완료\")) = {:?}", check_adult(20).then(Ok::<_, &str>("가입 완료")));
+    println!("check_adult(15).then(Ok(\"가입 완료\")) = {:?}", check_adult(15).then(Ok::<_, &str>("가입 완료")));
+
+    // Validated: 독립적인 필드를 모두 검사하고, 실패한 항목을 한 번에 보고
+    fn check_name(name: &str) -> Validated<String, &'static str> {
+        if name.is_empty() { Validated::invalid("이름이 비어 있습니다") } else { Validated::pure(name.to_string()) }
+    }
+    let validate_user = |name: &str, age: i32, email: &str| {
+        let name_and_age = map2(check_name(name), Validated::from(validate_age(age)), |n, a| (n, a));
+        map2(name_and_age, Validated::from(validate_email(email)), |(name, age), email| User { name, age, email })
+    };
+    println!("Validated(모두 유효) = {:?}", validate_user("최지우", 28, "choi@example.com"));
+    println!("Validated(모두 실패) = {:?}", validate_user("", -1, "no-at-sign"));
     
     println!("=====================================\n");
 }
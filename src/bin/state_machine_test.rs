@@ -1,6 +1,10 @@
 // 
 // Type-Level State Machines with Phantom Data
 // 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rust_higher_kined_types::gat::Stream;
 use rust_higher_kined_types::state_machine::{Scheduler, Task, demonstrate_state_machine_safety};
 
 fn test_scheduler_type_level_state_machines() {
@@ -11,36 +15,71 @@ fn test_scheduler_type_level_state_machines() {
     println!("[1] 🎯 Basic State Transitions:");
     
     // 체이닝으로 상태 전환과 태스크 추가를 한 번에 처리
-    let scheduler = Scheduler::new()
+    let storage = Scheduler::new()
         .initialize()
         .add_task(Task::new(1, "Initialize Database", 5))
-        .add_task(Task::new(2, "Load Configuration", 8))
+        .add_task(Task::new(2, "Load Configuration", 8));
+    let services = Scheduler::new()
+        .initialize()
         .add_task(Task::new(3, "Start Web Server", 10))
-        .add_task(Task::new(4, "Run Health Check", 3));
+        .add_task(Task::new(4, "Run Health Check", 3))
+        .add_task(Task::default().with_id(5).with_name("Warm Cache").with_priority(1));
+
+    // 서로 다른 출처의 작업 목록을 하나의 스케줄러로 병합 (2 + 3 = 5)
+    let scheduler = storage.merge(services);
     
     println!("    📊 Added {} tasks", scheduler.task_count());
+    let stats = scheduler.stats();
+    println!(
+        "    📈 Priorities - min: {:?}, max: {:?}, avg: {:.2}",
+        stats.min_priority,
+        stats.max_priority,
+        stats.avg_priority.unwrap_or(0.0)
+    );
     println!();
 
     // 스케줄러 시작 및 작업 실행
     println!("[2] 🏃 Execution Phase:");
-    let mut scheduler = scheduler.start();
+    // on_execute 훅으로 실행된 태스크 이름을 공유 Vec에 수집
+    let executed_names = Rc::new(RefCell::new(Vec::new()));
+    let hook_log = Rc::clone(&executed_names);
+    let mut scheduler = scheduler
+        .start()
+        .on_execute(move |task| hook_log.borrow_mut().push(task.name.clone()));
     
-    // 모든 태스크 실행
-    while scheduler.has_tasks() {
-        scheduler = scheduler.execute_next();
-        if let Some(current) = scheduler.current_task() {
-            println!("    🔍 Current task: {} (Priority: {})", current.name, current.priority);
-        }
+    // 실행하지 않고 다음 태스크 미리 보기
+    if let Some(task) = scheduler.peek_next() {
+        println!("    👀 Next up: {} (Priority: {})", task.name, task.priority);
+    }
+
+    // 첫 태스크는 결과(ExecutedTask)를 받아서 확인
+    let (next, first) = scheduler.execute_next_result();
+    scheduler = next;
+    if let Some(executed) = first {
+        println!("    🧾 Result #{}: {} -> {:?}", executed.sequence, executed.task.name, executed.outcome);
+    }
+
+    // 나머지 태스크 실행 (run_to_completion이 has_tasks() 루프를 대신함)
+    scheduler = scheduler.run_to_completion();
+    for task in scheduler.completed_tasks() {
+        println!("    🔍 Completed task: {} (Priority: {})", task.name, task.priority);
     }
     
-    // 마지막 태스크 실행 시도
-    scheduler = scheduler.execute_next();
+    // 마지막 태스크 실행 시도: 큐가 비어 있으면 결과는 None
+    let (next, last) = scheduler.execute_next_result();
+    scheduler = next;
+    println!("    🧾 Result on empty queue: {:?}", last);
+    println!("    🪝 Hook saw: {:?}", executed_names.borrow());
     println!();
 
     // 스케줄러 정지 및 요약
     println!("[3] ⏹️ Shutdown Phase:");
     let scheduler = scheduler.stop();
     println!("    {}", scheduler.get_summary());
+    // 실행 기록: restart는 유지, reset은 비움
+    for executed in scheduler.history() {
+        println!("    📜 #{} {} ({:?})", executed.sequence, executed.task.name, executed.outcome);
+    }
     println!();
 
     // 2. 고급 상태 전환 데모
@@ -62,9 +101,105 @@ fn test_scheduler_type_level_state_machines() {
     // 재구성 후 재시작
     let scheduler = scheduler
         .add_task(Task::new(7, "Emergency Task", 9))
-        .start();
+        .add_task(Task::new(8, "Obsolete Task", 2));
+    // 일시정지 상태에서만 가능한 작업 관리: 제거, 조회, 우선순위 변경
+    let (scheduler, removed) = scheduler.remove_task(8);
+    println!("    🗑️ Removed: {:?}", removed.map(|t| t.name));
+    let scheduler = scheduler.set_priority(6, 6).unwrap();
+    if let Some(task) = scheduler.find_task(6) {
+        println!("    🎚️ {} now has priority {}", task.name, task.priority);
+    }
+    let scheduler = scheduler.start();
     
     println!("    📈 Restarted with {} remaining tasks", scheduler.remaining_tasks());
+
+    // GAT Stream으로 대기 중인 태스크를 미리 보고, 하나씩 지연 실행
+    let mut preview = scheduler.task_stream();
+    while let Some((task, rank)) = preview.next_with_position() {
+        println!("    🗂️ Queue #{}: {} (Priority: {})", rank, task.name, task.priority);
+    }
+    let mut executions = scheduler.into_execution_stream();
+    while let Some((task, step)) = executions.next_with_position() {
+        println!("    ✔️ Step {} finished: {}", step, task.name);
+    }
+    println!("    📈 Remaining after stream: {}", executions.get_ref().remaining_tasks());
+    println!();
+
+    // 실패 가능한 태스크: seed 14에서 두 번 실패한 뒤 세 번째 시도에 성공
+    println!("[4-1] 🔁 Retry Policy:");
+    let mut retrying = Scheduler::new()
+        .initialize()
+        .add_task(Task::new(1, "Flaky Upload", 7).with_retries(3))
+        .start();
+    while retrying.has_tasks() {
+        let (next, attempt) = retrying.execute_next_with_retry_result(14);
+        retrying = next;
+        if let Some(attempt) = attempt {
+            println!("    🧾 Attempt #{}: {:?}", attempt.sequence, attempt.outcome);
+        }
+    }
+    println!(
+        "    📈 Completed: {}, Failed: {}",
+        retrying.completed_tasks().len(),
+        retrying.failed_tasks().len()
+    );
+    println!();
+
+    // 의존성 그래프: 우선순위가 높아도 선행 태스크가 끝나야 실행됨
+    println!("[4-2] 🕸️ Task Dependencies:");
+    let graph = Scheduler::new()
+        .initialize()
+        .add_task_with_deps(Task::new(4, "Deploy", 9), &[2, 3])
+        .add_task_with_deps(Task::new(2, "Build", 5), &[1])
+        .add_task_with_deps(Task::new(3, "Test", 7), &[1])
+        .add_task(Task::new(1, "Checkout", 1));
+    match graph.try_start() {
+        Ok(running) => {
+            let done = running.run_to_completion();
+            let order: Vec<&str> = done.completed_tasks().iter().map(|t| t.name.as_str()).collect();
+            println!("    📋 Execution order: {:?}", order);
+        }
+        Err(e) => println!("    ❌ {}", e),
+    }
+    let cyclic = Scheduler::new()
+        .initialize()
+        .add_task_with_deps(Task::new(1, "A", 1), &[2])
+        .add_task_with_deps(Task::new(2, "B", 1), &[1]);
+    if let Err(e) = cyclic.try_start() {
+        println!("    ❌ {}", e);
+    }
+    println!();
+
+    // 페이로드를 가진 태스크: 명령(enum)을 함께 스케줄링
+    println!("[4-3] 📦 Task Payloads:");
+    let commands = Scheduler::with_payloads()
+        .initialize()
+        .add_task(Task::with_payload(1, "Backup", 3, Command::Backup { target: "db".to_string() }))
+        .add_task(Task::with_payload(2, "Notify", 8, Command::Notify("deploy finished".to_string())))
+        .start()
+        .run_to_completion();
+    for executed in commands.history() {
+        let action = match &executed.task.payload {
+            Command::Backup { target } => format!("backing up {}", target),
+            Command::Notify(message) => format!("sending \"{}\"", message),
+        };
+        println!("    📨 #{} {} -> {}", executed.sequence, executed.task.name, action);
+    }
+    println!();
+
+    // 클로저 태스크: 우선순위 순서로 실행하고 결과 메시지를 수집
+    println!("[4-4] 🧩 Closure Tasks:");
+    let (stopped, outcomes) = Scheduler::new()
+        .initialize()
+        .add_task(Task::with_action(1, "Compile", 9, Box::new(|| Ok("3 crates built".to_string()))))
+        .add_task(Task::with_action(2, "Lint", 5, Box::new(|| Err("2 warnings denied".to_string()))))
+        .add_task(Task::new(3, "Announce", 1))
+        .start()
+        .run_all();
+    for outcome in &outcomes {
+        println!("    🧾 {:?}", outcome);
+    }
+    println!("    {}", stopped.get_summary());
     println!();
 
     // 3. 타입 안전성 데모
@@ -78,9 +213,17 @@ fn test_scheduler_type_level_state_machines() {
     println!("    ❌ Scheduler::new().start()           // Can't start uninitialized");
     println!("    ❌ scheduler.initialize().execute()   // Can't execute non-running");
     println!("    ❌ running_scheduler.add_task(task)   // Can't modify running scheduler");
+    println!("    ❌ running_scheduler.remove_task(id)  // Task management is Initialized-only");
     println!("    ✅ All state violations caught at compile time!");
 }
 
+// Domain data carried by the payload demo's tasks
+#[derive(Debug, Clone)]
+enum Command {
+    Backup { target: String },
+    Notify(String),
+}
+
 fn main() {
     test_scheduler_type_level_state_machines();
 } 
\ No newline at end of file
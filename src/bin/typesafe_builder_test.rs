@@ -2,7 +2,10 @@
 // Type-Safe Builder Pattern
 // -- Let's create a type-safe builder pattern with compile-time validation:
 // 
-use rust_higher_kined_types::custom_types::{PersonBuilder, Person};
+use rust_higher_kined_types::custom_types::{
+    build_all, try_build_all, PersonBuilder, Person, Protocol, ServerConfigBuilder,
+};
+use rust_higher_kined_types::typesafe_builder;
 
 fn test_typesafe_builder_pattern() {
     println!("6. === Type-Safe Builder Pattern ===");
@@ -40,6 +43,75 @@ fn test_typesafe_builder_pattern() {
     println!("      Email: {}", person2.email());
     println!();
 
+    // 2-1. 선택적 필드 (phone)
+    println!("[2-1] 📞 Optional Field Support:");
+    
+    let with_phone = PersonBuilder::new()
+        .name("Grace Hopper".to_string())
+        .optional_phone("555-0199".to_string())  // 어느 단계에서든 호출 가능
+        .age(45)
+        .email("grace@navy.mil".to_string())
+        .build();
+    
+    let without_phone = PersonBuilder::new()
+        .name("Alan Turing".to_string())
+        .age(41)
+        .email("alan@bletchley.uk".to_string())
+        .build();
+    
+    println!("    ✅ With phone: {} - {:?}", with_phone.name(), with_phone.phone());
+    println!("    ✅ Without phone: {} - {:?}", without_phone.name(), without_phone.phone());
+    println!();
+
+    // 2-2. 값 검증 (try_build)
+    println!("[2-2] 🧪 Value Validation with try_build:");
+    
+    let bad_email = PersonBuilder::new()
+        .name("Mallory".to_string())
+        .age(33)
+        .email("mallory-at-example.com".to_string())
+        .try_build();
+    
+    match bad_email {
+        Ok(person) => println!("    ✅ Built: {:?}", person),
+        Err(error) => println!("    ❌ Validation failed: {}", error),
+    }
+    println!();
+
+    // 2-3. 잘못 입력한 필드 되돌리기 (reset)
+    println!("[2-3] ↩️ Resetting a Field:");
+    
+    let mistaken = PersonBuilder::new()
+        .name("Jhon Doe".to_string());
+    println!("    Mistaken name set, has_name: {}", mistaken.has_name());
+    
+    let reset = mistaken.reset_name();   // 타입이 PersonBuilder<(), ...>로 돌아감
+    println!("    After reset_name, has_name: {}", reset.has_name());
+    
+    let corrected = reset
+        .name("John Doe".to_string())    // name()을 다시 호출할 수 있고, 호출해야만 build 가능
+        .age(50)
+        .email("john@example.com".to_string())
+        .build();
+    println!("    ✅ Corrected person: {}", corrected.name());
+    println!();
+
+    // 2-4. 부분 빌더 병합 (merge)
+    println!("[2-4] 🔗 Merging Partial Builders:");
+    
+    let from_profile = PersonBuilder::new()
+        .name("Hana Kim".to_string());
+    let from_signup_form = PersonBuilder::new()
+        .age(27)
+        .email("hana@example.com".to_string());
+    
+    // 타입 레벨 OR: 두 빌더 중 하나라도 설정한 필드는 병합 결과에서도 설정된 상태
+    let merged = from_profile.merge(from_signup_form);
+    println!("    Merged: has_name={}, has_age={}, has_email={}",
+             merged.has_name(), merged.has_age(), merged.has_email());
+    println!("    ✅ Built from merged builder: {:?}", merged.build());
+    println!();
+
     // 3. 빌더 상태 확인
     println!("[3] 🔍 Builder State Inspection:");
     
@@ -73,7 +145,7 @@ fn test_typesafe_builder_pattern() {
     println!("[5] 🛠️ Practical Usage Examples:");
     
     // 여러 사람 생성
-    let people = vec![
+    let people = [
         PersonBuilder::new()
             .name("David Wilson".to_string())
             .age(42)
@@ -98,6 +170,30 @@ fn test_typesafe_builder_pattern() {
     }
     println!();
 
+    // 일괄 생성: build_all / try_build_all
+    let batch = build_all(vec![
+        PersonBuilder::new().name("Gina".to_string()).age(31).email("gina@team.io".to_string()),
+        PersonBuilder::new().name("Hugo".to_string()).age(47).email("hugo@team.io".to_string()),
+    ]);
+    println!("    📦 build_all created {} people", batch.len());
+    
+    let mixed = try_build_all(vec![
+        PersonBuilder::new().name("Ivy".to_string()).age(22).email("ivy@team.io".to_string()),
+        PersonBuilder::new().name("Jack".to_string()).age(200).email("jack@team.io".to_string()),
+        PersonBuilder::new().name("Kim".to_string()).age(35).email("kim-at-team.io".to_string()),
+        PersonBuilder::new().name("Lee".to_string()).age(180).email("lee.team.io".to_string()),
+    ]);
+    match mixed {
+        Ok(people) => println!("    ✅ All {} records valid", people.len()),
+        Err(errors) => {
+            println!("    ❌ try_build_all found {} problems:", errors.len());
+            for (index, error) in &errors {
+                println!("      - record #{}: {}", index, error);
+            }
+        }
+    }
+    println!();
+
     // 6. 컴파일 타임 안전성 예시
     println!("[6] 💡 Compile-Time Safety Examples:");
     println!("    ❌ These operations would NOT compile:");
@@ -133,6 +229,51 @@ fn test_typesafe_builder_pattern() {
     
     println!("    🏭 Template-based person: {}", final_person.name());
     println!("    ✅ Builder pattern enables flexible object construction!");
+    println!();
+
+    // 9. 같은 패턴을 다른 타입에 적용
+    println!("[9] 🌐 Reusing the Pattern - ServerConfigBuilder:");
+    
+    let config = ServerConfigBuilder::new()
+        .host("api.example.com".to_string())
+        .port(443)
+        .protocol(Protocol::Https)
+        .build();
+    
+    println!("    ✅ Built server config: {:?}://{}:{}", config.protocol(), config.host(), config.port());
+    println!("    ❌ ServerConfigBuilder::new().host(...).build()          // Missing port and protocol");
+    println!("    ❌ ServerConfigBuilder::new().host(...).port(...).build() // Missing protocol");
+    println!();
+
+    // 10. 매크로로 빌더 생성
+    println!("[10] 🪄 Macro-Generated Builder:");
+    
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct GeneratedPerson {
+        name: String,
+        age: u32,
+        email: String,
+    }
+    
+    typesafe_builder! {
+        GeneratedPersonBuilder => GeneratedPerson {
+            name: String [has_name],
+            age: u32 [has_age],
+            email: String [has_email],
+        }
+    }
+    
+    let partial = GeneratedPersonBuilder::new().name("Macro Mike".to_string());
+    println!("    Partial: has_name={}, has_age={}, has_email={}",
+             partial.has_name(), partial.has_age(), partial.has_email());
+    
+    let generated = partial
+        .email("mike@macro.rs".to_string())
+        .age(29)
+        .build();
+    println!("    ✅ Built with macro-generated builder: {:?}", generated);
+    println!("    ❌ GeneratedPersonBuilder::new().name(...).build()     // Still rejected at compile time");
 }
 
 fn main() {
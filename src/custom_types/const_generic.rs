@@ -2,10 +2,16 @@
 // Const Generics and Type-Level Programming
 //
 // -- Using const generics for compile-time array and matrix operations
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Sub};
+
+use crate::custom_types::gat::{ArrayStream, RowStream};
+
 // Type-level array operations using const generics
-#[derive(Debug, Clone)]
+// -- Copy (for Copy elements) lets arrays nest, e.g. the result of chunks
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Array<T, const N: usize> {
-    data: [T; N],
+    pub(crate) data: [T; N],
 }
 
 impl<T: Default + Copy, const N: usize> Array<T, N> {
@@ -36,9 +42,124 @@ impl<T: Default + Copy, const N: usize> Array<T, N> {
         N
     }
 
-    pub fn iter(&self) -> std::slice::Iter<T> {
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
         self.data.iter()
     }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.data.contains(value)
+    }
+
+    /// Whether every element satisfies `f` (true for an empty array)
+    pub fn all<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.data.iter().all(f)
+    }
+
+    /// Whether at least one element satisfies `f` (false for an empty array)
+    pub fn any<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.data.iter().any(f)
+    }
+
+    /// Number of elements satisfying `f`
+    pub fn count_where<F: FnMut(&T) -> bool>(&self, mut f: F) -> usize {
+        self.data.iter().filter(|x| f(x)).count()
+    }
+
+    /// A copy with the elements in ascending order; the length N stays in the type
+    pub fn sorted(&self) -> Array<T, N>
+    where
+        T: Ord,
+    {
+        self.sorted_by(T::cmp)
+    }
+
+    /// A copy sorted with the comparator `compare` (stable, so equal elements keep their order)
+    pub fn sorted_by<F: FnMut(&T, &T) -> Ordering>(&self, compare: F) -> Array<T, N> {
+        let mut data = self.data;
+        data.sort_by(compare);
+        Array { data }
+    }
+
+    /// Split into COUNT consecutive arrays of CHUNK elements each.
+    ///
+    /// Stable Rust cannot compute `N / CHUNK` in a type, so the caller names COUNT
+    /// (usually through the result type) and `CHUNK * COUNT == N` is checked when the
+    /// call is compiled. A chunk size that does not divide N is rejected at build time:
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::const_generic::Array;
+    ///
+    /// let arr: Array<i32, 6> = Array::from_array([1, 2, 3, 4, 5, 6]);
+    /// let _: Array<Array<i32, 4>, 1> = arr.chunks(); // ❌ 4 * 1 != 6
+    /// ```
+    pub fn chunks<const CHUNK: usize, const COUNT: usize>(&self) -> Array<Array<T, CHUNK>, COUNT> {
+        const { assert!(CHUNK * COUNT == N, "CHUNK * COUNT must equal the array length N") };
+        Array {
+            data: std::array::from_fn(|i| Array {
+                data: std::array::from_fn(|j| self.data[i * CHUNK + j]),
+            }),
+        }
+    }
+}
+
+// Lending streams over the elements (see gat::ArrayStream)
+impl<T, const N: usize> Array<T, N> {
+    pub fn stream(&self) -> ArrayStream<'_, T, N> {
+        ArrayStream::new(&self.data)
+    }
+}
+
+impl<T: Default + Copy, const N: usize> Default for Array<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Element-wise arithmetic
+// -- Both operands share N, so equal lengths are guaranteed by the type rather than
+//    checked at runtime.
+impl<T: Copy + Default + Add<Output = T>, const N: usize> Add for Array<T, N> {
+    type Output = Self;
+
+    /// Add element by element. Arrays of different sizes are different types:
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::const_generic::Array;
+    ///
+    /// let a: Array<i32, 3> = Array::from_array([1, 2, 3]);
+    /// let b: Array<i32, 2> = Array::from_array([10, 20]);
+    /// let _ = a + b; // ❌ expected Array<i32, 3>, found Array<i32, 2>
+    /// ```
+    fn add(self, rhs: Self) -> Self {
+        Self { data: std::array::from_fn(|i| self.data[i] + rhs.data[i]) }
+    }
+}
+
+impl<T: Copy + Default + Sub<Output = T>, const N: usize> Sub for Array<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self { data: std::array::from_fn(|i| self.data[i] - rhs.data[i]) }
+    }
+}
+
+impl<T: Copy + Default + Mul<Output = T>, const N: usize> Mul for Array<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self { data: std::array::from_fn(|i| self.data[i] * rhs.data[i]) }
+    }
 }
 
 // Simple concat operation for specific sizes (due to const generic limitations)
@@ -71,6 +192,16 @@ impl<T: Default + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
         Self { data }
     }
 
+    /// Build from row-major flat data, which must hold exactly R * C elements
+    pub fn from_flat(data: &[T]) -> Result<Self, &'static str> {
+        if data.len() != R * C {
+            return Err("Flat data length must equal ROWS * COLS");
+        }
+        Ok(Self {
+            data: std::array::from_fn(|r| std::array::from_fn(|c| data[r * C + c])),
+        })
+    }
+
     pub fn get(&self, row: usize, col: usize) -> Option<&T> {
         self.data.get(row)?.get(col)
     }
@@ -84,6 +215,27 @@ impl<T: Default + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
         }
     }
 
+    /// Copy row `r` into an Array whose length is the column count
+    pub fn row(&self, r: usize) -> Option<Array<T, C>> {
+        self.data.get(r).map(|row| Array::from_array(*row))
+    }
+
+    /// Copy column `c` into an Array whose length is the row count
+    pub fn col(&self, c: usize) -> Option<Array<T, R>> {
+        if c >= C {
+            return None;
+        }
+        Some(Array::from_array(std::array::from_fn(|r| self.data[r][c])))
+    }
+
+    /// Transform every element, keeping the compile-time dimensions.
+    /// `f` is called in row-major order.
+    pub fn map<U: Default + Copy, F: FnMut(&T) -> U>(&self, mut f: F) -> Matrix<U, R, C> {
+        Matrix {
+            data: std::array::from_fn(|r| std::array::from_fn(|c| f(&self.data[r][c]))),
+        }
+    }
+
     pub fn rows(&self) -> usize {
         R
     }
@@ -93,6 +245,99 @@ impl<T: Default + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
     }
 }
 
+// Elementary row operations, the building blocks of Gaussian elimination
+// -- Each one touches only the named rows and fails on an out-of-range index, leaving
+//    the matrix unchanged. Meant for float types; integer division is left to the caller.
+impl<T: Default + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// Exchange rows `a` and `b`
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<(), &'static str> {
+        if a >= R || b >= R {
+            return Err("Row index out of bounds");
+        }
+        self.data.swap(a, b);
+        Ok(())
+    }
+
+    /// Multiply every element of row `r` by `k`
+    pub fn scale_row(&mut self, r: usize, k: T) -> Result<(), &'static str>
+    where
+        T: Mul<Output = T>,
+    {
+        let row = self.data.get_mut(r).ok_or("Row index out of bounds")?;
+        for value in row.iter_mut() {
+            *value = *value * k;
+        }
+        Ok(())
+    }
+
+    /// Add `k` times row `src` to row `dst` (dst += k * src)
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, k: T) -> Result<(), &'static str>
+    where
+        T: Add<Output = T> + Mul<Output = T>,
+    {
+        if dst >= R || src >= R {
+            return Err("Row index out of bounds");
+        }
+        let source = self.data[src];
+        for (value, s) in self.data[dst].iter_mut().zip(source) {
+            *value = *value + s * k;
+        }
+        Ok(())
+    }
+}
+
+// Floating-point element types that can be compared within a tolerance
+pub trait FloatLike: Copy + PartialOrd + Sub<Output = Self> {
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_float_like {
+    ($($t:ty),*) => {
+        $(
+            impl FloatLike for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_like!(f32, f64);
+
+impl<T: FloatLike, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// Whether every element differs from the one at the same position in `other` by
+    /// at most `epsilon`. A NaN on either side never counts as close.
+    pub fn approx_eq(&self, other: &Matrix<T, R, C>, epsilon: T) -> bool {
+        self.data
+            .iter()
+            .flatten()
+            .zip(other.data.iter().flatten())
+            .all(|(&a, &b)| (a - b).abs() <= epsilon)
+    }
+}
+
+// Lending streams over the rows (see gat::RowStream)
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn rows_stream(&self) -> RowStream<'_, T, R, C> {
+        RowStream::new(&self.data)
+    }
+}
+
+impl<T: Default + Copy, const R: usize, const C: usize> TryFrom<&[T]> for Matrix<T, R, C> {
+    type Error = &'static str;
+
+    fn try_from(data: &[T]) -> Result<Self, Self::Error> {
+        Self::from_flat(data)
+    }
+}
+
+impl<T: Default + Copy, const R: usize, const C: usize> Default for Matrix<T, R, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Specific matrix multiplication implementations (due to const generic limitations)
 impl<T> Matrix<T, 2, 3> 
 where
@@ -112,29 +357,97 @@ where
     }
 }
 
+// Square-matrix determinants via cofactor expansion
+// -- Recursing over `N` would need `N - 1` in a type position (generic_const_exprs),
+//    so each size up to 4x4 gets its own explicit implementation. Larger sizes are not supported.
+impl<T> Matrix<T, 1, 1>
+where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        self.data[0][0]
+    }
+}
+
+impl<T> Matrix<T, 2, 2>
+where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        let [[a, b], [c, d]] = self.data;
+        a * d - b * c
+    }
+}
+
+impl<T> Matrix<T, 3, 3>
+where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+
+impl<T> Matrix<T, 4, 4>
+where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        // Expand along the first row, alternating signs on each 3x3 minor
+        let minor = |skip: usize| {
+            let cols = match skip {
+                0 => [1, 2, 3],
+                1 => [0, 2, 3],
+                2 => [0, 1, 3],
+                _ => [0, 1, 2],
+            };
+            let row = |r: usize| [self.data[r][cols[0]], self.data[r][cols[1]], self.data[r][cols[2]]];
+            Matrix { data: [row(1), row(2), row(3)] }.determinant()
+        };
+
+        self.data[0][0] * minor(0) - self.data[0][1] * minor(1)
+            + self.data[0][2] * minor(2) - self.data[0][3] * minor(3)
+    }
+}
+
 // Additional helpful implementations
-impl<T: std::fmt::Display, const N: usize> Array<T, N> {
-    pub fn display(&self) {
-        print!("[");
+impl<T: std::fmt::Display, const N: usize> std::fmt::Display for Array<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
         for (i, item) in self.data.iter().enumerate() {
-            if i > 0 { print!(", "); }
-            print!("{}", item);
+            if i > 0 { write!(f, ", ")?; }
+            write!(f, "{}", item)?;
         }
-        println!("]");
+        write!(f, "]")
     }
 }
 
-impl<T: std::fmt::Display, const R: usize, const C: usize> Matrix<T, R, C> {
+impl<T: std::fmt::Display, const N: usize> Array<T, N> {
     pub fn display(&self) {
-        println!("Matrix {}x{}:", R, C);
+        println!("{}", self);
+    }
+}
+
+// One line per row, right-aligned to width 3; no trailing newline
+impl<T: std::fmt::Display, const R: usize, const C: usize> std::fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Matrix {}x{}:", R, C)?;
         for row in &self.data {
-            print!("  [");
+            write!(f, "\n  [")?;
             for (j, item) in row.iter().enumerate() {
-                if j > 0 { print!(", "); }
-                print!("{:>3}", item);
+                if j > 0 { write!(f, ", ")?; }
+                write!(f, "{:>3}", item)?;
             }
-            println!("]");
+            write!(f, "]")?;
         }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn display(&self) {
+        println!("{}", self);
     }
 }
 
@@ -163,3 +476,314 @@ pub fn compile_time_size_check() {
     println!("    Array size: {}", ARRAY_SIZE);
     println!("    Matrix dimensions: {}x{}", MATRIX_ROWS, MATRIX_COLS);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_determinant_small_matrices() {
+        let m1: Matrix<i32, 1, 1> = Matrix::from_data([[7]]);
+        assert_eq!(m1.determinant(), 7);
+
+        let m2: Matrix<i32, 2, 2> = Matrix::from_data([[3, 8], [4, 6]]);
+        assert_eq!(m2.determinant(), -14);
+
+        let m3: Matrix<i32, 3, 3> = Matrix::from_data([[6, 1, 1], [4, -2, 5], [2, 8, 7]]);
+        assert_eq!(m3.determinant(), -306);
+    }
+
+    #[test]
+    fn test_determinant_4x4() {
+        let m4: Matrix<i32, 4, 4> = Matrix::from_data([
+            [1, 0, 2, -1],
+            [3, 0, 0, 5],
+            [2, 1, 4, -3],
+            [1, 0, 5, 0],
+        ]);
+        assert_eq!(m4.determinant(), 30);
+
+        let identity: Matrix<f64, 4, 4> = Matrix::from_data([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(identity.determinant(), 1.0);
+    }
+
+    #[test]
+    fn test_determinant_singular_matrix_is_zero() {
+        let singular2: Matrix<i32, 2, 2> = Matrix::from_data([[2, 4], [1, 2]]);
+        assert_eq!(singular2.determinant(), 0);
+
+        let singular3: Matrix<i32, 3, 3> = Matrix::from_data([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(singular3.determinant(), 0);
+    }
+
+    #[test]
+    fn test_row_and_col_extraction() {
+        let m: Matrix<i32, 2, 3> = Matrix::from_data([[1, 2, 3], [4, 5, 6]]);
+
+        let row: Array<i32, 3> = m.row(1).unwrap();
+        assert_eq!(row, Array::from_array([4, 5, 6]));
+
+        let col: Array<i32, 2> = m.col(2).unwrap();
+        assert_eq!(col, Array::from_array([3, 6]));
+
+        assert_eq!(m.row(0).unwrap().len(), 3);
+        assert_eq!(m.col(0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_row_and_col_out_of_range() {
+        let m: Matrix<i32, 2, 3> = Matrix::from_data([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m.row(2), None);
+        assert_eq!(m.col(3), None);
+        assert_eq!(m.col(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_from_flat_row_major() {
+        let flat = [1, 2, 3, 4, 5, 6];
+        let m: Matrix<i32, 2, 3> = Matrix::from_flat(&flat).unwrap();
+        for row in 0..2 {
+            for col in 0..3 {
+                assert_eq!(m.get(row, col), Some(&flat[row * 3 + col]));
+            }
+        }
+
+        let via_try_from = Matrix::<i32, 3, 2>::try_from(&flat[..]).unwrap();
+        assert_eq!(via_try_from.row(2), Some(Array::from_array([5, 6])));
+    }
+
+    #[test]
+    fn test_from_flat_wrong_length() {
+        assert!(Matrix::<i32, 2, 3>::from_flat(&[1, 2, 3, 4, 5]).is_err());
+        assert!(Matrix::<i32, 2, 3>::from_flat(&[1, 2, 3, 4, 5, 6, 7]).is_err());
+        assert!(Matrix::<i32, 2, 3>::try_from(&[][..]).is_err());
+    }
+
+    #[test]
+    fn test_map_squares_and_keeps_dimensions() {
+        let m: Matrix<i32, 2, 3> = Matrix::from_data([[1, 2, 3], [4, 5, 6]]);
+        let squared = m.map(|x| x * x);
+        assert_eq!((squared.rows(), squared.cols()), (2, 3));
+        assert_eq!(squared.row(0), Some(Array::from_array([1, 4, 9])));
+        assert_eq!(squared.row(1), Some(Array::from_array([16, 25, 36])));
+
+        let halves: Matrix<f64, 2, 3> = m.map(|&x| x as f64 / 2.0);
+        assert_eq!(halves.get(1, 2), Some(&3.0));
+    }
+
+    #[test]
+    fn test_map_visits_row_major() {
+        let m: Matrix<i32, 2, 2> = Matrix::from_data([[1, 2], [3, 4]]);
+        let mut order = Vec::new();
+        let _ = m.map(|&x| order.push(x));
+        assert_eq!(order, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_map_zero_dimensions() {
+        let no_rows: Matrix<i32, 0, 3> = Matrix::new();
+        let mapped: Matrix<u8, 0, 3> = no_rows.map(|_| unreachable!());
+        assert_eq!((mapped.rows(), mapped.cols()), (0, 3));
+
+        let no_cols: Matrix<i32, 2, 0> = Matrix::new();
+        let mapped = no_cols.map(|x| x.to_string().len());
+        assert_eq!(mapped.row(1).map(|row| row.len()), Some(0));
+    }
+
+    #[test]
+    fn test_array_contains() {
+        let arr: Array<i32, 4> = Array::from_array([3, 1, 4, 1]);
+        assert!(arr.contains(&4));
+        assert!(arr.contains(&1));
+        assert!(!arr.contains(&5));
+
+        let empty: Array<i32, 0> = Array::new();
+        assert!(!empty.contains(&0));
+    }
+
+    #[test]
+    fn test_array_iter_mut_persists() {
+        let mut arr: Array<i32, 3> = Array::from_array([1, 2, 3]);
+        for item in arr.iter_mut() {
+            *item += 10;
+        }
+        assert_eq!(arr, Array::from_array([11, 12, 13]));
+        assert!(arr.contains(&12));
+        assert!(!arr.contains(&2));
+    }
+
+    #[test]
+    fn test_array_display_string() {
+        let arr: Array<i32, 3> = Array::from_array([1, 22, 333]);
+        assert_eq!(arr.to_string(), "[1, 22, 333]");
+        assert_eq!(Array::<i32, 0>::new().to_string(), "[]");
+    }
+
+    #[test]
+    fn test_matrix_display_string() {
+        let m: Matrix<i32, 2, 3> = Matrix::from_data([[1, 2, 3], [40, 50, -6]]);
+        assert_eq!(m.to_string(), "Matrix 2x3:\n  [  1,   2,   3]\n  [ 40,  50,  -6]");
+        assert_eq!(Matrix::<i32, 0, 2>::new().to_string(), "Matrix 0x2:");
+    }
+
+    #[test]
+    fn test_array_add() {
+        let a: Array<i32, 3> = Array::from_array([1, 2, 3]);
+        let b: Array<i32, 3> = Array::from_array([10, 20, 30]);
+        assert_eq!(a + b, Array::from_array([11, 22, 33]));
+        assert_eq!(Array::<i32, 0>::new() + Array::new(), Array::new());
+    }
+
+    #[test]
+    fn test_array_sub() {
+        let a: Array<i32, 3> = Array::from_array([10, 20, 30]);
+        let b: Array<i32, 3> = Array::from_array([1, 25, 3]);
+        assert_eq!(a - b, Array::from_array([9, -5, 27]));
+    }
+
+    #[test]
+    fn test_array_mul() {
+        let a: Array<f64, 2> = Array::from_array([1.5, -2.0]);
+        let b: Array<f64, 2> = Array::from_array([2.0, 4.0]);
+        assert_eq!(a * b, Array::from_array([3.0, -8.0]));
+    }
+
+    #[test]
+    fn test_array_chunks_into_equal_parts() {
+        let arr: Array<i32, 6> = Array::from_array([1, 2, 3, 4, 5, 6]);
+        let halves: Array<Array<i32, 3>, 2> = arr.chunks();
+        assert_eq!(halves.get(0), Some(&Array::from_array([1, 2, 3])));
+        assert_eq!(halves.get(1), Some(&Array::from_array([4, 5, 6])));
+
+        let pairs = arr.chunks::<2, 3>();
+        let sums: Vec<i32> = pairs.iter().map(|pair| pair.iter().sum()).collect();
+        assert_eq!(sums, vec![3, 7, 11]);
+
+        let whole: Array<Array<i32, 6>, 1> = arr.chunks();
+        assert_eq!(whole.get(0), Some(&arr));
+    }
+
+    #[test]
+    fn test_row_operations_touch_only_target_rows() {
+        let original = Matrix::<f64, 3, 2>::from_data([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+
+        let mut swapped = original.clone();
+        swapped.swap_rows(0, 2).unwrap();
+        assert_eq!(swapped.row(0), original.row(2));
+        assert_eq!(swapped.row(1), original.row(1));
+        assert_eq!(swapped.row(2), original.row(0));
+        assert_eq!((swapped.rows(), swapped.cols()), (3, 2));
+
+        let mut scaled = original.clone();
+        scaled.scale_row(1, 0.5).unwrap();
+        assert_eq!(scaled.row(1), Some(Array::from_array([1.5, 2.0])));
+        assert_eq!(scaled.row(0), original.row(0));
+        assert_eq!(scaled.row(2), original.row(2));
+
+        let mut combined = original.clone();
+        combined.add_scaled_row(2, 0, -5.0).unwrap();
+        assert_eq!(combined.row(2), Some(Array::from_array([0.0, -4.0])));
+        assert_eq!(combined.row(0), original.row(0));
+        assert_eq!(combined.row(1), original.row(1));
+    }
+
+    #[test]
+    fn test_row_operations_reject_bad_indices() {
+        let mut m = Matrix::<f64, 2, 2>::from_data([[1.0, 2.0], [3.0, 4.0]]);
+        assert!(m.swap_rows(0, 2).is_err());
+        assert!(m.scale_row(2, 2.0).is_err());
+        assert!(m.add_scaled_row(0, 5, 1.0).is_err());
+        assert_eq!(m.row(0), Some(Array::from_array([1.0, 2.0])));
+        assert_eq!(m.row(1), Some(Array::from_array([3.0, 4.0])));
+
+        // Swapping a row with itself is a no-op
+        m.swap_rows(1, 1).unwrap();
+        assert_eq!(m.row(1), Some(Array::from_array([3.0, 4.0])));
+    }
+
+    #[test]
+    fn test_array_predicate_reductions() {
+        let mixed: Array<i32, 5> = Array::from_array([3, -1, 4, -1, 5]);
+        assert!(!mixed.all(|&x| x > 0));
+        assert!(mixed.any(|&x| x > 0));
+        assert_eq!(mixed.count_where(|&x| x > 0), 3);
+        assert_eq!(mixed.count_where(|&x| x == -1), 2);
+        assert!(!mixed.any(|&x| x > 5));
+
+        let positive: Array<i32, 4> = Array::from_array([1, 2, 3, 4]);
+        assert!(positive.all(|&x| x > 0));
+        assert_eq!(positive.count_where(|&x| x > 0), positive.len());
+    }
+
+    #[test]
+    fn test_array_predicate_reductions_on_empty() {
+        let empty: Array<i32, 0> = Array::new();
+        assert!(empty.all(|_| false));
+        assert!(!empty.any(|_| true));
+        assert_eq!(empty.count_where(|_| true), 0);
+    }
+
+    #[test]
+    fn test_approx_eq_accepts_near_equal_matrices() {
+        let a = Matrix::<f64, 2, 3>::from_data([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = Matrix::<f64, 3, 2>::from_data([[0.1, 0.2], [0.3, 0.4], [0.5, 0.6]]);
+        let product = a.multiply_with_3x2(&b);
+
+        // 0.1 + 0.2-style rounding makes the exact comparison fail
+        let expected = Matrix::from_data([[2.2, 2.8], [4.9, 6.4]]);
+        assert_ne!(product.data, expected.data);
+        assert!(product.approx_eq(&expected, 1e-9));
+        assert!(expected.approx_eq(&product, 1e-9));
+
+        let det = Matrix::<f32, 2, 2>::from_data([[0.1, 0.2], [0.3, 0.4]]).determinant();
+        let near = Matrix::<f32, 1, 1>::from_data([[det + 1e-7]]);
+        assert!(near.approx_eq(&Matrix::from_data([[-0.02]]), 1e-6));
+    }
+
+    #[test]
+    fn test_approx_eq_rejects_different_matrices() {
+        let base = Matrix::<f64, 2, 2>::from_data([[1.0, 2.0], [3.0, 4.0]]);
+        let mut off = base.clone();
+        off.set(1, 1, 4.001).unwrap();
+        assert!(!base.approx_eq(&off, 1e-9));
+        assert!(base.approx_eq(&off, 1e-2));
+
+        // The bound is inclusive and a zero tolerance means exact equality
+        assert!(base.approx_eq(&Matrix::from_data([[1.5, 2.0], [3.0, 4.0]]), 0.5));
+        assert!(base.approx_eq(&base, 0.0));
+
+        let mut nan = base.clone();
+        nan.set(0, 0, f64::NAN).unwrap();
+        assert!(!nan.approx_eq(&nan, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_sorted_keeps_input_and_length() {
+        let already: Array<i32, 4> = Array::from_array([1, 2, 2, 5]);
+        assert_eq!(already.sorted(), already);
+
+        let reversed: Array<i32, 5> = Array::from_array([9, 7, 4, 0, -3]);
+        let sorted: Array<i32, 5> = reversed.sorted();
+        assert_eq!(sorted.data, [-3, 0, 4, 7, 9]);
+        assert_eq!(reversed.data, [9, 7, 4, 0, -3]);
+
+        let empty: Array<u8, 0> = Array::new();
+        assert!(empty.sorted().is_empty());
+    }
+
+    #[test]
+    fn test_sorted_by_custom_comparator() {
+        let arr: Array<i32, 5> = Array::from_array([3, 1, 4, 1, 5]);
+        assert_eq!(arr.sorted_by(|a, b| b.cmp(a)).data, [5, 4, 3, 1, 1]);
+
+        // Stable: pairs with equal keys keep their original order
+        let pairs: Array<(u8, char), 4> = Array::from_array([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+        let by_key = pairs.sorted_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(by_key.data, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    }
+}
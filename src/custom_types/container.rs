@@ -3,6 +3,14 @@
 // and Higher-Kinded Types
 //
 
+use std::borrow::Cow;
+use std::ops::Mul;
+
+use crate::custom_types::const_generic::Array;
+use crate::custom_types::functor_monad::NonEmpty;
+
+pub mod testing;
+
 // Fitst, let's defined a trait for container-like types
 pub trait Container {
     // Associated type for the contained value
@@ -33,3 +41,676 @@ impl<T, E> Container for Result<T, E> {
         self.map(|x| f(&x))
     }
 }
+
+// Implementing Container for Vec
+impl<T> Container for Vec<T> {
+    type Item = T;
+    type Mapped<U> = Vec<U>;
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, f: F) -> Self::Mapped<U> {
+        self.iter().map(f).collect()
+    }
+}
+
+// Implementing Container for NonEmpty (the length, and so the head, is preserved)
+impl<T> Container for NonEmpty<T> {
+    type Item = T;
+    type Mapped<U> = NonEmpty<U>;
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, mut f: F) -> Self::Mapped<U> {
+        self.fmap(|x| f(&x))
+    }
+}
+
+// Implementing Container for plain Rust arrays [T; N]
+// -- each_ref() lends &T to the closure, so no Default or Copy bound is needed on T
+impl<T, const N: usize> Container for [T; N] {
+    type Item = T;
+    type Mapped<U> = [U; N];
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, f: F) -> Self::Mapped<U> {
+        self.each_ref().map(f)
+    }
+}
+
+// Implementing Container for the const-generic Array (the size N is preserved)
+impl<T, const N: usize> Container for Array<T, N> {
+    type Item = T;
+    type Mapped<U> = Array<U, N>;
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, f: F) -> Self::Mapped<U> {
+        Array {
+            data: self.data.each_ref().map(f),
+        }
+    }
+}
+
+// Implementing Container for borrowed-or-owned slices
+// -- Changing the element type always needs a new buffer, so Mapped<U> is an owned Vec<U>.
+//    The closure only sees references, so a Borrowed source is never cloned.
+impl<'a, T: Clone> Container for Cow<'a, [T]> {
+    type Item = T;
+    type Mapped<U> = Vec<U>;
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, f: F) -> Self::Mapped<U> {
+        self.iter().map(f).collect()
+    }
+}
+
+// Monadic chaining through the Container umbrella
+// -- and_then feeds each item by value to a closure that returns a whole new container,
+//    then flattens the result. An empty Option, an Err, or an empty Vec short-circuits:
+//    the closure is never called and the empty shape is carried through.
+pub trait MonadicContainer: Container {
+    fn and_then<U, F: FnMut(Self::Item) -> Self::Mapped<U>>(self, f: F) -> Self::Mapped<U>;
+}
+
+impl<T> MonadicContainer for Option<T> {
+    fn and_then<U, F: FnMut(Self::Item) -> Self::Mapped<U>>(self, f: F) -> Self::Mapped<U> {
+        Option::and_then(self, f)
+    }
+}
+
+impl<T, E> MonadicContainer for Result<T, E> {
+    fn and_then<U, F: FnMut(Self::Item) -> Self::Mapped<U>>(self, f: F) -> Self::Mapped<U> {
+        Result::and_then(self, f)
+    }
+}
+
+impl<T> MonadicContainer for Vec<T> {
+    fn and_then<U, F: FnMut(Self::Item) -> Self::Mapped<U>>(self, f: F) -> Self::Mapped<U> {
+        self.into_iter().flat_map(f).collect()
+    }
+}
+
+// Same-type mapping that reuses the existing buffer
+// -- Container::map must allocate because Mapped<U> may differ from Self. When U == T the
+//    elements can be overwritten where they are, so Vec keeps its allocation and arrays stay put.
+pub trait ContainerInPlace: Container {
+    fn map_in_place<F: FnMut(&Self::Item) -> Self::Item>(self, f: F) -> Self;
+}
+
+impl<T> ContainerInPlace for Vec<T> {
+    fn map_in_place<F: FnMut(&Self::Item) -> Self::Item>(mut self, mut f: F) -> Self {
+        for item in self.iter_mut() {
+            *item = f(item);
+        }
+        self
+    }
+}
+
+impl<T, const N: usize> ContainerInPlace for [T; N] {
+    fn map_in_place<F: FnMut(&Self::Item) -> Self::Item>(mut self, mut f: F) -> Self {
+        for item in self.iter_mut() {
+            *item = f(item);
+        }
+        self
+    }
+}
+
+impl<T, const N: usize> ContainerInPlace for Array<T, N> {
+    fn map_in_place<F: FnMut(&Self::Item) -> Self::Item>(mut self, f: F) -> Self {
+        self.data = self.data.map_in_place(f);
+        self
+    }
+}
+
+// Borrow-aware mapping that keeps the element type
+// -- A Borrowed value stays borrowed until the closure actually changes an element;
+//    only then is the untouched prefix copied into an owned buffer.
+pub trait ContainerCow<'a>: Sized {
+    type Item: PartialEq;
+
+    fn map_cow<F: FnMut(&Self::Item) -> Self::Item>(self, f: F) -> Self;
+
+    // Rewrites an Owned value in its existing buffer and returns true.
+    // A Borrowed value is left untouched and false is returned.
+    fn map_in_place_if_owned<F: FnMut(&Self::Item) -> Self::Item>(&mut self, f: F) -> bool;
+}
+
+impl<'a, T: Clone + PartialEq> ContainerCow<'a> for Cow<'a, [T]> {
+    type Item = T;
+
+    fn map_cow<F: FnMut(&Self::Item) -> Self::Item>(mut self, mut f: F) -> Self {
+        if self.map_in_place_if_owned(&mut f) {
+            return self;
+        }
+        let Cow::Borrowed(slice) = self else {
+            return self;
+        };
+
+        let mut owned: Option<Vec<T>> = None;
+        for (i, item) in slice.iter().enumerate() {
+            let mapped = f(item);
+            match owned.as_mut() {
+                Some(vec) => vec.push(mapped),
+                None if mapped != *item => {
+                    let mut vec = Vec::with_capacity(slice.len());
+                    vec.extend_from_slice(&slice[..i]);
+                    vec.push(mapped);
+                    owned = Some(vec);
+                }
+                None => {}
+            }
+        }
+        owned.map_or(Cow::Borrowed(slice), Cow::Owned)
+    }
+
+    fn map_in_place_if_owned<F: FnMut(&Self::Item) -> Self::Item>(&mut self, mut f: F) -> bool {
+        match self {
+            Cow::Owned(vec) => {
+                for item in vec.iter_mut() {
+                    *item = f(item);
+                }
+                true
+            }
+            Cow::Borrowed(_) => false,
+        }
+    }
+}
+
+impl<'a> ContainerCow<'a> for Cow<'a, str> {
+    type Item = char;
+
+    fn map_cow<F: FnMut(&Self::Item) -> Self::Item>(mut self, mut f: F) -> Self {
+        if self.map_in_place_if_owned(&mut f) {
+            return self;
+        }
+        let Cow::Borrowed(text) = self else {
+            return self;
+        };
+
+        let mut owned: Option<String> = None;
+        for (i, ch) in text.char_indices() {
+            let mapped = f(&ch);
+            match owned.as_mut() {
+                Some(string) => string.push(mapped),
+                None if mapped != ch => {
+                    let mut string = String::with_capacity(text.len());
+                    string.push_str(&text[..i]);
+                    string.push(mapped);
+                    owned = Some(string);
+                }
+                None => {}
+            }
+        }
+        owned.map_or(Cow::Borrowed(text), Cow::Owned)
+    }
+
+    // Mapped chars may have a different UTF-8 width, so the owned String is rebuilt
+    // rather than patched byte-by-byte; the Borrowed case is still never touched.
+    fn map_in_place_if_owned<F: FnMut(&Self::Item) -> Self::Item>(&mut self, mut f: F) -> bool {
+        match self {
+            Cow::Owned(string) => {
+                *string = string.chars().map(|ch| f(&ch)).collect();
+                true
+            }
+            Cow::Borrowed(_) => false,
+        }
+    }
+}
+
+// Deferred containers: Mapped as a pending computation instead of a built structure
+// -- lazy_map only records the closure; nothing runs until collect_container is called.
+//    Stacked maps are nested adapters, so every element flows through all of them in one pass.
+pub trait LazyContainer: Sized {
+    type Item;
+
+    // Associated type constructor over both the new element type and the pending closure
+    type Mapped<U, G>: LazyContainer<Item = U>
+    where
+        G: FnMut(Self::Item) -> U;
+
+    fn lazy_map<U, G>(self, g: G) -> Self::Mapped<U, G>
+    where
+        G: FnMut(Self::Item) -> U;
+
+    fn collect_container<C: FromIterator<Self::Item>>(self) -> C;
+}
+
+// An iterator plus a pending map closure
+pub struct LazyMapped<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F> LazyMapped<I, F> {
+    pub fn new(iter: I, f: F) -> Self {
+        LazyMapped { iter, f }
+    }
+}
+
+// The first stage of every lazy pipeline: the source iterator with an identity map
+pub type LazySource<I> = LazyMapped<I, fn(<I as Iterator>::Item) -> <I as Iterator>::Item>;
+
+// Start a lazy pipeline from anything iterable
+pub fn lazy<I: IntoIterator>(source: I) -> LazySource<I::IntoIter> {
+    LazyMapped::new(source.into_iter(), std::convert::identity)
+}
+
+impl<I, U, F> Iterator for LazyMapped<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> U,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(&mut self.f)
+    }
+}
+
+impl<I, U, F> LazyContainer for LazyMapped<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> U,
+{
+    type Item = U;
+    type Mapped<V, G> = LazyMapped<Self, G>
+    where
+        G: FnMut(Self::Item) -> V;
+
+    fn lazy_map<V, G>(self, g: G) -> Self::Mapped<V, G>
+    where
+        G: FnMut(Self::Item) -> V,
+    {
+        LazyMapped::new(self, g)
+    }
+
+    fn collect_container<C: FromIterator<Self::Item>>(self) -> C {
+        self.collect()
+    }
+}
+
+// Two-channel containers (Bifunctor at the container level)
+// -- Container only maps the success channel, so Result's E is fixed forever.
+//    ContainerBoth rewrites both channels in one call; a channel that holds no value passes through.
+pub trait ContainerBoth {
+    // The first channel (Ok for Result, .0 for tuples)
+    type First;
+    // The second channel (Err for Result, .1 for tuples)
+    type Second;
+
+    // Associated type constructor with two parameters, one per channel
+    type MappedBoth<U, F2>: ContainerBoth<First = U, Second = F2>;
+
+    fn map_both<U, F2, F, G>(self, f: F, g: G) -> Self::MappedBoth<U, F2>
+    where
+        F: FnOnce(Self::First) -> U,
+        G: FnOnce(Self::Second) -> F2;
+}
+
+// Implementing ContainerBoth for Result: only the channel that is present gets mapped
+impl<T, E> ContainerBoth for Result<T, E> {
+    type First = T;
+    type Second = E;
+    type MappedBoth<U, F2> = Result<U, F2>;
+
+    fn map_both<U, F2, F, G>(self, f: F, g: G) -> Self::MappedBoth<U, F2>
+    where
+        F: FnOnce(Self::First) -> U,
+        G: FnOnce(Self::Second) -> F2,
+    {
+        match self {
+            Ok(value) => Ok(f(value)),
+            Err(error) => Err(g(error)),
+        }
+    }
+}
+
+// Implementing ContainerBoth for pairs: both channels are always present
+impl<A, B> ContainerBoth for (A, B) {
+    type First = A;
+    type Second = B;
+    type MappedBoth<U, F2> = (U, F2);
+
+    fn map_both<U, F2, F, G>(self, f: F, g: G) -> Self::MappedBoth<U, F2>
+    where
+        F: FnOnce(Self::First) -> U,
+        G: FnOnce(Self::Second) -> F2,
+    {
+        (f(self.0), g(self.1))
+    }
+}
+
+// Generic numeric helpers built on top of Container
+// -- Widening uses the lossless `From` conversions from std (i8 -> i16 -> i32 -> i64),
+//    so no external numeric crate is needed.
+
+// Widen every element into a larger numeric type without changing the container shape
+pub fn widen_container<C, U>(container: C) -> C::Mapped<U>
+where
+    C: Container,
+    C::Item: Copy + Into<U>,
+{
+    container.map(|&x| x.into())
+}
+
+// Widen every element and multiply it by `factor` in the wider type,
+// so `i32 * 2` computed as `i64` can never overflow
+pub fn scale_container<C, T, U>(container: C, factor: T) -> C::Mapped<U>
+where
+    C: Container<Item = T>,
+    T: Copy + Into<U>,
+    U: Mul<Output = U>,
+{
+    container.map(|&x| x.into() * factor.into())
+}
+
+// Integer types that support multiplication clamped at their numeric bounds
+pub trait SaturatingMul: Copy {
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_saturating_mul {
+    ($($t:ty),*) => {
+        $(
+            impl SaturatingMul for $t {
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$t>::saturating_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_mul!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// Multiply every element by `factor` in the same type, clamping at MIN/MAX instead of overflowing
+pub fn saturating_scale<C>(container: C, factor: C::Item) -> C::Mapped<C::Item>
+where
+    C: Container,
+    C::Item: SaturatingMul,
+{
+    container.map(|&x| SaturatingMul::saturating_mul(x, factor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct MyError {
+        message: String,
+    }
+
+    // One generic function rewriting both channels of any ContainerBoth
+    fn describe_both<C>(container: C) -> C::MappedBoth<String, MyError>
+    where
+        C: ContainerBoth,
+        C::First: std::fmt::Display,
+        C::Second: std::fmt::Display,
+    {
+        container.map_both(
+            |value| format!("value = {}", value),
+            |error| MyError { message: error.to_string() },
+        )
+    }
+
+    #[test]
+    fn test_widen_container() {
+        let option: Option<i8> = Some(i8::MAX);
+        let widened: Option<i16> = widen_container(option);
+        assert_eq!(widened, Some(127));
+
+        let result: Result<i16, &str> = Ok(i16::MIN);
+        let widened: Result<i32, &str> = widen_container(result);
+        assert_eq!(widened, Ok(-32768));
+
+        let vec: Vec<i32> = vec![i32::MIN, 0, i32::MAX];
+        let widened: Vec<i64> = widen_container(vec);
+        assert_eq!(widened, vec![i32::MIN as i64, 0, i32::MAX as i64]);
+    }
+
+    #[test]
+    fn test_scale_container_without_overflow() {
+        let option: Option<i32> = Some(i32::MAX);
+        let scaled: Option<i64> = scale_container(option, 2);
+        assert_eq!(scaled, Some(i32::MAX as i64 * 2));
+
+        let result: Result<i32, &str> = Err("no value");
+        let scaled: Result<i64, &str> = scale_container(result, 2);
+        assert_eq!(scaled, Err("no value"));
+
+        let vec: Vec<i8> = vec![i8::MIN, -1, i8::MAX];
+        let scaled: Vec<i16> = scale_container(vec, 3);
+        assert_eq!(scaled, vec![-384, -3, 381]);
+
+        let array: Array<i16, 3> = Array::from_array([1, i16::MAX, i16::MIN]);
+        let scaled: Array<i32, 3> = scale_container(array, 4);
+        assert_eq!(scaled.data, [4, 131068, -131072]);
+    }
+
+    #[test]
+    fn test_saturating_scale_at_limits() {
+        assert_eq!(saturating_scale(Some(i32::MAX), 2), Some(i32::MAX));
+        assert_eq!(saturating_scale(Some(i32::MIN), 2), Some(i32::MIN));
+        assert_eq!(saturating_scale(Some(10_i32), 3), Some(30));
+
+        let result: Result<u8, &str> = Ok(200);
+        assert_eq!(saturating_scale(result, 2), Ok(u8::MAX));
+
+        let vec: Vec<i8> = vec![100, -100, 5];
+        assert_eq!(saturating_scale(vec, 2), vec![i8::MAX, i8::MIN, 10]);
+
+        let array: Array<u16, 2> = Array::from_array([u16::MAX, 7]);
+        assert_eq!(saturating_scale(array, 10).data, [u16::MAX, 70]);
+    }
+
+    #[test]
+    fn test_map_both_result() {
+        let ok: Result<i32, &'static str> = Ok(42);
+        assert_eq!(describe_both(ok), Ok("value = 42".to_string()));
+
+        let err: Result<i32, &'static str> = Err("boom");
+        assert_eq!(describe_both(err), Err(MyError { message: "boom".to_string() }));
+    }
+
+    #[test]
+    fn test_map_both_passthrough() {
+        // Only the channel that holds a value invokes its closure
+        let ok: Result<i32, &str> = Ok(1);
+        let mapped: Result<i32, usize> = ok.map_both(|x| x + 1, |_| panic!("error channel is empty"));
+        assert_eq!(mapped, Ok(2));
+
+        let err: Result<i32, &str> = Err("four");
+        let mapped: Result<i32, usize> = err.map_both(|_| panic!("success channel is empty"), |e| e.len());
+        assert_eq!(mapped, Err(4));
+    }
+
+    #[test]
+    fn test_map_both_tuple() {
+        let pair = (3, "three");
+        assert_eq!(
+            describe_both(pair),
+            ("value = 3".to_string(), MyError { message: "three".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_map_cow_identity_keeps_borrowed_slice() {
+        let data = [1, 2, 3];
+        let cow: Cow<[i32]> = Cow::Borrowed(&data);
+        let mapped = cow.map_cow(|&x| if x > 10 { x * 2 } else { x });
+
+        assert!(matches!(mapped, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(mapped.as_ptr(), data.as_ptr()));
+    }
+
+    #[test]
+    fn test_map_cow_detaches_on_change() {
+        let data = [1, 2, 3];
+        let mapped = Cow::Borrowed(&data[..]).map_cow(|&x| if x == 2 { 20 } else { x });
+
+        assert!(matches!(mapped, Cow::Owned(_)));
+        assert_eq!(&*mapped, &[1, 20, 3]);
+    }
+
+    #[test]
+    fn test_map_in_place_if_owned_slice() {
+        let mut owned: Cow<[i32]> = Cow::Owned(vec![1, 2, 3]);
+        let buffer = owned.as_ptr();
+        assert!(owned.map_in_place_if_owned(|&x| x * 10));
+        assert_eq!(&*owned, &[10, 20, 30]);
+        assert!(std::ptr::eq(owned.as_ptr(), buffer));
+
+        let data = [1, 2, 3];
+        let mut borrowed: Cow<[i32]> = Cow::Borrowed(&data);
+        assert!(!borrowed.map_in_place_if_owned(|&x| x * 10));
+        assert_eq!(&*borrowed, &[1, 2, 3]);
+
+        let mapped = Cow::<[i32]>::Owned(vec![4, 5]).map_cow(|&x| x + 1);
+        assert!(matches!(mapped, Cow::Owned(ref v) if v == &[5, 6]));
+    }
+
+    #[test]
+    fn test_map_cow_str() {
+        let text = "hello world";
+        let unchanged = Cow::Borrowed(text).map_cow(|&c| if c == '!' { '?' } else { c });
+        assert!(matches!(unchanged, Cow::Borrowed(s) if std::ptr::eq(s, text)));
+
+        let changed = Cow::Borrowed("héllo wörld").map_cow(|&c| if c == ' ' { '_' } else { c });
+        assert!(matches!(changed, Cow::Owned(_)));
+        assert_eq!(changed, "héllo_wörld");
+
+        let owned: Cow<str> = Cow::Owned("abc".to_string());
+        assert_eq!(owned.map_cow(|c| c.to_ascii_uppercase()), "ABC");
+    }
+
+    #[test]
+    fn test_container_for_cow_slice_changes_type() {
+        let data = [1, 2, 3];
+        let mapped: Vec<String> = Container::map(Cow::Borrowed(&data[..]), |x| x.to_string());
+        assert_eq!(mapped, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_container_for_plain_arrays() {
+        let empty: [String; 0] = [];
+        let mapped: [usize; 0] = Container::map(empty, |s| s.len());
+        assert_eq!(mapped, []);
+
+        // String is neither Copy nor needed to be Default
+        let single = [String::from("one")];
+        assert_eq!(Container::map(single, |s| s.len()), [3]);
+
+        let numbers = [1, 2, 3, 4, 5, 6, 7, 8];
+        let labels = Container::map(numbers, |n| format!("#{}", n));
+        assert_eq!(labels, ["#1", "#2", "#3", "#4", "#5", "#6", "#7", "#8"]);
+
+        let scaled: [i64; 3] = scale_container([i32::MAX, 0, -1], 2);
+        assert_eq!(scaled, [i32::MAX as i64 * 2, 0, -2]);
+    }
+
+    #[test]
+    fn test_lazy_map_runs_nothing_until_collected() {
+        let calls = std::cell::Cell::new(0);
+        let pending = lazy(vec![1, 2, 3]).lazy_map(|x| {
+            calls.set(calls.get() + 1);
+            x * 10
+        });
+        assert_eq!(calls.get(), 0);
+
+        let collected: Vec<i32> = pending.collect_container();
+        assert_eq!(collected, vec![10, 20, 30]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_lazy_maps_fuse_into_single_pass() {
+        let log = std::cell::RefCell::new(Vec::new());
+        let pending = lazy([1, 2])
+            .lazy_map(|x| {
+                log.borrow_mut().push(format!("a{}", x));
+                x + 1
+            })
+            .lazy_map(|x| {
+                log.borrow_mut().push(format!("b{}", x));
+                x * 2
+            })
+            .lazy_map(|x| {
+                log.borrow_mut().push(format!("c{}", x));
+                x.to_string()
+            });
+        assert!(log.borrow().is_empty());
+
+        let collected: Vec<String> = pending.collect_container();
+        assert_eq!(collected, vec!["4", "6"]);
+        // Each element passes through all three stages before the next one starts
+        assert_eq!(*log.borrow(), vec!["a1", "b2", "c4", "a2", "b3", "c6"]);
+    }
+
+    #[test]
+    fn test_map_in_place_matches_allocating_map_on_large_vec() {
+        let input: Vec<i64> = (0..1_000_000).collect();
+        let transform = |&x: &i64| x.wrapping_mul(31) ^ 0x5A5A;
+
+        let allocated = Container::map(input.clone(), transform);
+        let buffer = input.as_ptr();
+        let in_place = input.map_in_place(transform);
+
+        assert_eq!(in_place, allocated);
+        assert!(std::ptr::eq(in_place.as_ptr(), buffer));
+    }
+
+    #[test]
+    fn test_map_in_place_arrays() {
+        let plain = [1, 2, 3, 4];
+        assert_eq!(plain.map_in_place(|x| x * x), Container::map(plain, |x| x * x));
+
+        let wrapped = Array::from_array([5, 6, 7]);
+        let expected = Container::map(wrapped, |x| x - 1);
+        assert_eq!(wrapped.map_in_place(|x| x - 1), expected);
+    }
+
+    // One generic function that binds through any MonadicContainer
+    fn checked_halve<C>(container: C) -> C::Mapped<i32>
+    where
+        C: MonadicContainer<Item = i32>,
+        C::Mapped<i32>: From<Option<i32>>,
+    {
+        container.and_then(|x| (x % 2 == 0).then_some(x / 2).into())
+    }
+
+    #[test]
+    fn test_and_then_option() {
+        assert_eq!(MonadicContainer::and_then(Some(8), |x| Some(x + 1)), Some(9));
+        assert_eq!(MonadicContainer::and_then(Some(3), |x: i32| x.checked_sub(5).filter(|v| *v > 0)), None);
+        assert_eq!(checked_halve(Some(8)), Some(4));
+        assert_eq!(checked_halve(Some(7)), None);
+
+        // None short-circuits before the closure runs
+        let none: Option<i32> = None;
+        assert_eq!(MonadicContainer::and_then(none, |_| -> Option<i32> { panic!("closure called on None") }), None);
+    }
+
+    #[test]
+    fn test_and_then_result() {
+        let parsed: Result<&str, String> = Ok("42");
+        let chained = MonadicContainer::and_then(parsed, |s| s.parse::<i32>().map_err(|e| e.to_string()));
+        assert_eq!(chained, Ok(42));
+
+        let bad: Result<&str, String> = Ok("forty-two");
+        let chained = MonadicContainer::and_then(bad, |s| s.parse::<i32>().map_err(|e| e.to_string()));
+        assert_eq!(chained, Err("invalid digit found in string".to_string()));
+
+        // The first Err is kept and later steps never run
+        let failed: Result<i32, String> = Err("no input".to_string());
+        let chained = MonadicContainer::and_then(failed, |_| -> Result<i32, String> { panic!("closure called on Err") });
+        assert_eq!(chained, Err("no input".to_string()));
+    }
+
+    #[test]
+    fn test_and_then_vec() {
+        let expanded = vec![1, 2, 3].and_then(|x| vec![x; x as usize]);
+        assert_eq!(expanded, vec![1, 2, 2, 3, 3, 3]);
+
+        let evens_halved = vec![1, 2, 3, 4].and_then(|x| if x % 2 == 0 { vec![x / 2] } else { vec![] });
+        assert_eq!(evens_halved, vec![1, 2]);
+
+        // An empty Vec short-circuits, and so does a step that always returns empty
+        let empty: Vec<i32> = Vec::new();
+        assert!(empty.and_then(|_| -> Vec<i32> { panic!("closure called on empty Vec") }).is_empty());
+        assert!(vec![1, 2].and_then(|_| Vec::<String>::new()).is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+//
+// Test helpers for Container implementations
+// -- Every helper only relies on the Container trait itself, so a new impl can be
+//    checked with a single call. Failures name the container via core::any::type_name.
+//
+use core::any::type_name;
+use std::fmt::Debug;
+
+use super::Container;
+
+// Map `input` with `f` and assert the result equals `expected`
+pub fn map_and_compare<C, U, F>(input: C, f: F, expected: C::Mapped<U>)
+where
+    C: Container,
+    F: FnMut(&C::Item) -> U,
+    C::Mapped<U>: PartialEq + Debug,
+{
+    let actual = input.map(f);
+    assert_eq!(
+        actual,
+        expected,
+        "map_and_compare failed for container `{}`",
+        type_name::<C>()
+    );
+}
+
+// Map `input` forward with `f` and back with `g`, asserting the original container comes back
+// -- The mapped container's own Mapped constructor must lead back to C, which holds for
+//    Option, Result, Vec, Array and [T; N].
+pub fn roundtrip_mapped<C, U, F, G>(input: C, f: F, g: G)
+where
+    C: Container + Clone + PartialEq + Debug,
+    C::Mapped<U>: Container<Item = U, Mapped<C::Item> = C>,
+    F: FnMut(&C::Item) -> U,
+    G: FnMut(&U) -> C::Item,
+{
+    let roundtripped = input.clone().map(f).map(g);
+    assert_eq!(
+        roundtripped,
+        input,
+        "roundtrip_mapped failed for container `{}` (via `{}`)",
+        type_name::<C>(),
+        type_name::<C::Mapped<U>>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_types::const_generic::Array;
+
+    #[test]
+    fn test_map_and_compare_across_containers() {
+        map_and_compare(Some(2), |x| x * 10, Some(20));
+        map_and_compare(Err::<i32, &str>("e"), |x| x + 1, Err("e"));
+        map_and_compare(vec![1, 2, 3], |x| x.to_string(), vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        map_and_compare([1_u8, 2], |&x| x as u32 * 1000, [1000, 2000]);
+    }
+
+    #[test]
+    fn test_roundtrip_mapped_across_containers() {
+        roundtrip_mapped(Some(5), |x| x.to_string(), |s| s.parse().unwrap());
+        roundtrip_mapped(Ok::<i32, String>(7), |&x| x as i64, |&x| x as i32);
+        roundtrip_mapped(vec![1, -2, 3], |&x| -x, |&x| -x);
+        roundtrip_mapped(Array::from_array([4, 5, 6]), |&x| x as f64, |&x| x as i32);
+    }
+
+    #[test]
+    #[should_panic(expected = "map_and_compare failed for container `core::option::Option<i32>`")]
+    fn test_map_and_compare_failure_names_container() {
+        map_and_compare(Some(1), |x| x + 1, Some(3));
+    }
+}
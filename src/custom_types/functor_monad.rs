@@ -2,6 +2,8 @@
 // Advanced Higher-Kinded Types: Functor and Monad Patterns
 //
 
+pub mod laws;
+
 // A trait representing a higher-kinded type with one type parameter
 pub trait HKT<T> {
     type Higher<U>: HKT<U>;
@@ -12,6 +14,25 @@ pub trait Functor<T>: HKT<T> {
     fn fmap<U, F>(self, f: F) -> Self::Higher<U>
     where
         F: FnOnce(T) -> U;
+
+    /// Discard the contents, keeping only the shape (Some/None, Ok/Err)
+    fn void(self) -> Self::Higher<()>
+    where
+        Self: Sized,
+    {
+        self.fmap(|_| ())
+    }
+
+    /// Swap the contents for `value`, keeping the shape (Haskell's `<$`)
+    ///
+    /// Not called `replace`, which would shadow Option's inherent `replace(&mut self, ..)`
+    /// wherever Functor is in scope.
+    fn map_const<U>(self, value: U) -> Self::Higher<U>
+    where
+        Self: Sized,
+    {
+        self.fmap(|_| value)
+    }
 }
 
 // Applicative trait extending Functor
@@ -27,6 +48,16 @@ pub trait Monad<T>: Applicative<T> {
     fn bind<U, F>(self, f: F) -> Self::Higher<U>
     where
         F: FnOnce(T) -> Self::Higher<U>;
+
+    /// Sequence two effects, discarding the first result (Haskell's `>>`)
+    ///
+    /// `next` is evaluated eagerly; use `bind(|_| ..)` when building it is expensive.
+    fn then<U>(self, next: Self::Higher<U>) -> Self::Higher<U>
+    where
+        Self: Sized,
+    {
+        self.bind(|_| next)
+    }
 }
 
 // Example implementation for Option
@@ -114,6 +145,127 @@ impl<T, E> Monad<T> for Result<T, E> {
     }
 }
 
+// Error-accumulating alternative to Result
+// -- Result's apply stops at the first Err. Validated keeps going: when both the value
+//    and the function are invalid, their error lists are concatenated (function errors
+//    first, so map2(a, b, f) reports a's errors before b's). This makes it suitable for
+//    checking independent fields, e.g. a form, and reporting every problem at once.
+// -- There is deliberately no Monad instance: bind needs the first value to run the
+//    next step, so it could not keep collecting errors once one step failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T, E>(pub Result<T, Vec<E>>);
+
+impl<T, E> Validated<T, E> {
+    /// A failed validation with a single error
+    pub fn invalid(error: E) -> Self {
+        Validated(Err(vec![error]))
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validated<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        Validated(result.map_err(|e| vec![e]))
+    }
+}
+
+impl<T, E> HKT<T> for Validated<T, E> {
+    type Higher<U> = Validated<U, E>;
+}
+
+impl<T, E> Functor<T> for Validated<T, E> {
+    fn fmap<U, F>(self, f: F) -> Self::Higher<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Validated(self.0.map(f))
+    }
+}
+
+impl<T, E> Applicative<T> for Validated<T, E> {
+    fn pure(value: T) -> Self {
+        Validated(Ok(value))
+    }
+
+    fn apply<U, F>(self, f: Self::Higher<F>) -> Self::Higher<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match (self.0, f.0) {
+            (Ok(value), Ok(func)) => Validated(Ok(func(value))),
+            (Err(errors), Ok(_)) | (Ok(_), Err(errors)) => Validated(Err(errors)),
+            (Err(value_errors), Err(mut func_errors)) => {
+                func_errors.extend(value_errors);
+                Validated(Err(func_errors))
+            }
+        }
+    }
+}
+
+// List with at least one element
+// -- The fields are private, so the only ways in are new/singleton and the mapping
+//    methods below, all of which keep a head: non-emptiness is upheld by the API even
+//    though the type system cannot state it (Vec<T> with a len() >= 1 check could not).
+// -- Functor/Applicative/Monad take FnOnce closures, which can run for a single slot
+//    only (see the note in laws.rs), so NonEmpty implements HKT and provides fmap and
+//    bind as inherent methods with FnMut instead. Container::map is implemented too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonEmpty<T> {
+    head: T,
+    tail: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    pub fn new(head: T, tail: Vec<T>) -> Self {
+        NonEmpty { head, tail }
+    }
+
+    /// A one-element list (the `pure` of this type)
+    pub fn singleton(head: T) -> Self {
+        NonEmpty { head, tail: Vec::new() }
+    }
+
+    /// First element; always present
+    pub fn head(&self) -> &T {
+        &self.head
+    }
+
+    /// Number of elements, never 0 (hence no is_empty)
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        1 + self.tail.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::once(&self.head).chain(&self.tail)
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        let mut items = Vec::with_capacity(self.len());
+        items.push(self.head);
+        items.extend(self.tail);
+        items
+    }
+
+    /// Apply `f` to every element, keeping the length
+    pub fn fmap<U, F: FnMut(T) -> U>(self, mut f: F) -> NonEmpty<U> {
+        NonEmpty { head: f(self.head), tail: self.tail.into_iter().map(f).collect() }
+    }
+
+    /// Flat-map: concatenate the lists `f` returns, in order. Every list has a head,
+    /// so the result is non-empty too.
+    pub fn bind<U, F: FnMut(T) -> NonEmpty<U>>(self, mut f: F) -> NonEmpty<U> {
+        let NonEmpty { head, mut tail } = f(self.head);
+        for item in self.tail {
+            tail.extend(f(item).into_vec());
+        }
+        NonEmpty { head, tail }
+    }
+}
+
+impl<T> HKT<T> for NonEmpty<T> {
+    type Higher<U> = NonEmpty<U>;
+}
+
 // Simplified function that works with Option specifically
 pub fn chain_option_operations<T, U, V>(
     m: Option<T>,
@@ -132,6 +284,56 @@ pub fn chain_result_operations<T, U, V, E>(
     m.bind(f).bind(g)
 }
 
+// Generic chaining over any Monad
+// -- bind on M::Higher<U> yields <M::Higher<U> as HKT<U>>::Higher<V>, and nothing in HKT
+//    says that is the same type as M::Higher<V> (Option<U> -> Option<V> is obvious to us,
+//    not to the trait system). The extra `Higher<V> = M::Higher<V>` bound states exactly
+//    that, which keeps the signature readable and holds for every instance in this crate.
+pub fn chain_operations<M, T, U, V>(
+    m: M,
+    f: impl FnOnce(T) -> M::Higher<U>,
+    g: impl FnOnce(U) -> M::Higher<V>,
+) -> M::Higher<V>
+where
+    M: Monad<T>,
+    M::Higher<U>: Monad<U, Higher<V> = M::Higher<V>>,
+{
+    m.bind(f).bind::<V, _>(g)
+}
+
+// Lift a binary function into two applicative contexts
+// -- map2 partially applies f inside `ma` with fmap, then feeds `mb` through apply.
+//    The partially applied function has to be spelled out in the bounds (as
+//    M::Higher<G>), but a closure type cannot be named, so it is boxed as a
+//    `dyn FnOnce(B) -> C`. That one allocation is the price of the fully generic form.
+// -- For Result, when both inputs are Err the error from `mb` is returned, since apply
+//    inspects its value before its function.
+pub type BoxedFn<'f, B, C> = Box<dyn FnOnce(B) -> C + 'f>;
+
+pub fn map2<'f, M, A, B, C, F>(ma: M, mb: M::Higher<B>, f: F) -> M::Higher<C>
+where
+    M: Functor<A>,
+    M::Higher<B>: Applicative<B, Higher<BoxedFn<'f, B, C>> = M::Higher<BoxedFn<'f, B, C>>, Higher<C> = M::Higher<C>>,
+    A: 'f,
+    F: FnOnce(A, B) -> C + 'f,
+{
+    let partial = ma.fmap(move |a| Box::new(move |b| f(a, b)) as BoxedFn<'f, B, C>);
+    mb.apply::<C, BoxedFn<'f, B, C>>(partial)
+}
+
+// Conditional effects
+// -- `when(cond, action)` runs `action` only if cond holds; otherwise it is replaced by
+//    the no-op success pure(()). The action is built eagerly, so pass a value whose
+//    construction is cheap (e.g. the result of a validation already performed).
+pub fn when<A: Applicative<()>>(cond: bool, action: A) -> A {
+    if cond { action } else { A::pure(()) }
+}
+
+// The negation of `when`: runs `action` only if cond does not hold
+pub fn unless<A: Applicative<()>>(cond: bool, action: A) -> A {
+    when(!cond, action)
+}
+
 // Example usage with Option
 pub fn option_example() {
     let result = Option::pure(5)
@@ -227,4 +429,145 @@ mod tests {
         let result3: Result<i32, &str> = Ok(10).apply(Err::<fn(i32) -> i32, &str>("func error"));
         assert_eq!(result3, Err("func error"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_option_void_and_map_const() {
+        assert_eq!(Some(5).void(), Some(()));
+        assert_eq!(None::<i32>.void(), None);
+        assert_eq!(Some(5).map_const("x"), Some("x"));
+        assert_eq!(None::<i32>.map_const("x"), None);
+
+        // Option's own replace still mutates in place with Functor in scope
+        let mut opt = Some(1);
+        assert_eq!(opt.replace(2), Some(1));
+        assert_eq!(opt, Some(2));
+    }
+
+    #[test]
+    fn test_result_void_and_map_const() {
+        assert_eq!(Ok::<i32, &str>(1).void(), Ok(()));
+        assert_eq!(Err::<i32, &str>("boom").void(), Err("boom"));
+        assert_eq!(Ok::<i32, &str>(1).map_const("x"), Ok("x"));
+        assert_eq!(Err::<i32, &str>("boom").map_const(vec![1]), Err("boom"));
+    }
+
+    #[test]
+    fn test_generic_chain_operations_matches_specialized() {
+        let double = |x: i32| Some(x * 2);
+        let inc = |x: i32| Some(x + 1);
+        assert_eq!(chain_operations(Some(5), double, inc), chain_option_operations(Some(5), double, inc));
+        assert_eq!(chain_operations(None, double, inc), None);
+        assert_eq!(chain_operations(Some(5), |_: i32| None::<i32>, inc), None);
+
+        let half = |x: i32| if x % 2 == 0 { Ok(x / 2) } else { Err("odd") };
+        let show = |x: i32| Ok::<String, &str>(x.to_string());
+        assert_eq!(chain_operations(Ok(8), half, show), chain_result_operations(Ok(8), half, show));
+        assert_eq!(chain_operations(Ok(7), half, show), Err("odd"));
+        assert_eq!(chain_operations(Err::<i32, &str>("early"), half, show), Err("early"));
+    }
+
+    #[test]
+    fn test_when_and_unless_for_option() {
+        assert_eq!(when(true, Some(())), Some(()));
+        assert_eq!(when(true, None), None);
+        assert_eq!(when(false, None), Some(()));
+        assert_eq!(unless(false, None), None);
+        assert_eq!(unless(true, None), Some(()));
+    }
+
+    #[test]
+    fn test_when_and_unless_for_result() {
+        let failing: Result<(), &str> = Err("validation failed");
+        assert_eq!(when(true, failing), Err("validation failed"));
+        assert_eq!(when(false, failing), Ok(()));
+        assert_eq!(unless(true, failing), Ok(()));
+        assert_eq!(unless(false, failing), Err("validation failed"));
+        assert_eq!(when(true, Ok::<(), &str>(())), Ok(()));
+    }
+
+    #[test]
+    fn test_map2_option() {
+        assert_eq!(map2(Some(2), Some(3), |a, b| a + b), Some(5));
+        assert_eq!(map2(None::<i32>, Some(3), |a, b| a + b), None);
+        assert_eq!(map2(Some(2), None::<i32>, |a, b| a + b), None);
+        assert_eq!(map2(None::<i32>, None::<i32>, |a, b| a + b), None);
+        assert_eq!(map2(Some("n"), Some(3), |s: &str, n: usize| s.repeat(n)), Some("nnn".to_string()));
+    }
+
+    #[test]
+    fn test_map2_result() {
+        let ok = |x: i32| Ok::<i32, &str>(x);
+        assert_eq!(map2(ok(6), ok(7), |a, b| a * b), Ok(42));
+        assert_eq!(map2(Err("first"), ok(7), |a: i32, b| a * b), Err("first"));
+        assert_eq!(map2(ok(6), Err("second"), |a, b: i32| a * b), Err("second"));
+        // Both failed: apply checks its value (mb) before its function (built from ma)
+        assert_eq!(map2(Err::<i32, &str>("first"), Err::<i32, &str>("second"), |a, b| a * b), Err("second"));
+    }
+
+    #[test]
+    fn test_validated_collects_both_errors() {
+        let name: Validated<&str, &str> = Validated::invalid("name is empty");
+        let age: Validated<u32, &str> = Validated::invalid("age is negative");
+        assert_eq!(
+            map2(name, age, |n, a| (n, a)),
+            Validated(Err(vec!["name is empty", "age is negative"]))
+        );
+
+        let one_bad = map2(Validated::<_, &str>::pure("ada"), Validated::invalid("age is negative"), |n, a: u32| (n, a));
+        assert_eq!(one_bad, Validated(Err(vec!["age is negative"])));
+    }
+
+    #[test]
+    fn test_validated_all_success_combines() {
+        let name = Validated::<_, String>::pure("ada");
+        let age = Validated::from(Ok::<u32, String>(36));
+        assert_eq!(map2(name, age, |n, a| format!("{} ({})", n, a)), Validated(Ok("ada (36)".to_string())));
+        assert_eq!(Validated::<i32, ()>::pure(2).fmap(|x| x * 10), Validated(Ok(20)));
+    }
+
+    #[test]
+    fn test_validated_apply_error_order() {
+        let value: Validated<i32, &str> = Validated(Err(vec!["v1", "v2"]));
+        let func: Validated<fn(i32) -> i32, &str> = Validated::invalid("f1");
+        assert_eq!(value.apply(func), Validated(Err(vec!["f1", "v1", "v2"])));
+    }
+
+    #[test]
+    fn test_then_option() {
+        assert_eq!(Some(1).then(Some("next")), Some("next"));
+        assert_eq!(Some(1).then(None::<&str>), None);
+        assert_eq!(None::<i32>.then(Some("next")), None);
+    }
+
+    #[test]
+    fn test_then_result() {
+        let check: Result<(), &str> = Ok(());
+        assert_eq!(check.then(Ok::<i32, &str>(42)), Ok(42));
+        assert_eq!(Err::<(), &str>("invalid").then(Ok::<i32, &str>(42)), Err("invalid"));
+        assert_eq!(Ok::<(), &str>(()).then(Err::<i32, &str>("later")), Err("later"));
+    }
+
+    #[test]
+    fn test_non_empty_fmap_preserves_length() {
+        let numbers = NonEmpty::new(1, vec![2, 3]);
+        let labels = numbers.clone().fmap(|x| format!("#{}", x));
+        assert_eq!(labels.len(), numbers.len());
+        assert_eq!(labels.into_vec(), vec!["#1", "#2", "#3"]);
+
+        let single = NonEmpty::singleton('a').fmap(|c| c.to_ascii_uppercase());
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.head(), &'A');
+    }
+
+    #[test]
+    fn test_non_empty_bind_flattens() {
+        let repeated = NonEmpty::new(1, vec![2, 3]).bind(|x| NonEmpty::new(x, vec![x * 10]));
+        assert_eq!(repeated.len(), 6);
+        assert_eq!(repeated.into_vec(), vec![1, 10, 2, 20, 3, 30]);
+
+        // Even when every step yields a single element, the result keeps its head
+        let collapsed = NonEmpty::new("a", vec!["b"]).bind(|s| NonEmpty::singleton(s.len()));
+        assert_eq!(collapsed, NonEmpty::new(1, vec![1]));
+        assert_eq!(collapsed.iter().sum::<usize>(), 2);
+    }
+}
@@ -0,0 +1,92 @@
+//
+// Functor law harness
+// -- Generic checks for the two Functor laws, parameterized over the concrete functor.
+//    A new instance is verified by calling both functions with a few sample values.
+//    Failures name the law and the functor via core::any::type_name.
+//
+// Note: Functor::fmap takes an FnOnce, so only single-slot functors (Option, Result)
+//       can implement it. Multi-element containers such as Vec are covered by the
+//       Container trait and its container::testing helpers instead.
+//
+use core::any::type_name;
+use std::fmt::Debug;
+
+use super::Functor;
+
+// Identity law: fmap(id) == id
+pub fn assert_functor_identity<F, T>(fa: F)
+where
+    F: Functor<T, Higher<T> = F> + Clone + PartialEq + Debug,
+{
+    let mapped = fa.clone().fmap(|x| x);
+    assert_eq!(
+        mapped,
+        fa,
+        "functor identity law failed for `{}`: fmap(id) != id",
+        type_name::<F>()
+    );
+}
+
+// Composition law: fmap(g ∘ f) == fmap(g) ∘ fmap(f)
+pub fn assert_functor_composition<FA, T, U, V, F, G>(fa: FA, f: F, g: G)
+where
+    FA: Functor<T> + Clone,
+    FA::Higher<U>: Functor<U, Higher<V> = FA::Higher<V>>,
+    FA::Higher<V>: PartialEq + Debug,
+    F: Fn(T) -> U,
+    G: Fn(U) -> V,
+{
+    let composed = fa.clone().fmap(|x| g(f(x)));
+    let chained = fa.fmap(&f).fmap(&g);
+    assert_eq!(
+        composed,
+        chained,
+        "functor composition law failed for `{}`: fmap(g . f) != fmap(g) . fmap(f)",
+        type_name::<FA>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_types::functor_monad::HKT;
+
+    #[test]
+    fn test_option_functor_laws() {
+        assert_functor_identity(Some(42));
+        assert_functor_identity(None::<i32>);
+        assert_functor_composition(Some(3), |x: i32| x * 2, |x: i32| x.to_string());
+        assert_functor_composition(None::<i32>, |x: i32| x * 2, |x: i32| x.to_string());
+    }
+
+    #[test]
+    fn test_result_functor_laws() {
+        assert_functor_identity(Ok::<i32, String>(7));
+        assert_functor_identity(Err::<i32, String>("boom".to_string()));
+        assert_functor_composition(Ok::<&str, ()>("hello"), str::len, |n: usize| n % 2 == 1);
+        assert_functor_composition(Err::<&str, u8>(1), str::len, |n: usize| n % 2 == 1);
+    }
+
+    // A deliberately broken functor: fmap drops the value, violating identity
+    #[derive(Debug, Clone, PartialEq)]
+    struct Forgetful<T>(Option<T>);
+
+    impl<T> HKT<T> for Forgetful<T> {
+        type Higher<U> = Forgetful<U>;
+    }
+
+    impl<T> Functor<T> for Forgetful<T> {
+        fn fmap<U, F>(self, _f: F) -> Forgetful<U>
+        where
+            F: FnOnce(T) -> U,
+        {
+            Forgetful(None)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "functor identity law failed for")]
+    fn test_identity_failure_names_law() {
+        assert_functor_identity(Forgetful(Some(1)));
+    }
+}
@@ -4,6 +4,13 @@
 // -- Generic Associated Types allow for even more powerful abstractions 
 // by making associated types generic over parameters:
 
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
 // Define a trait for streaming operations
 pub trait Stream {
     type Item<'a>
@@ -18,13 +25,135 @@ pub trait Stream {
         Self: Sized;
 
     fn reset_position(&mut self) -> &mut Self;
+
+    /// Bounds on the number of remaining items, as for Iterator::size_hint.
+    /// The default `(0, None)` promises nothing.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+// How a StringStream decides where one token ends and the next begins
+#[derive(Clone)]
+pub enum Splitter {
+    Whitespace,
+    Delimiter(char),
+    Predicate(Rc<dyn Fn(char) -> bool>),
+}
+
+impl Splitter {
+    fn is_separator(&self, c: char) -> bool {
+        match self {
+            Splitter::Whitespace => c.is_whitespace(),
+            Splitter::Delimiter(delimiter) => c == *delimiter,
+            Splitter::Predicate(predicate) => predicate(c),
+        }
+    }
+}
+
+impl fmt::Debug for Splitter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Splitter::Whitespace => write!(f, "Whitespace"),
+            Splitter::Delimiter(delimiter) => write!(f, "Delimiter({:?})", delimiter),
+            Splitter::Predicate(_) => write!(f, "Predicate(..)"),
+        }
+    }
+}
+
+// Byte range of the next token and the position to resume from afterwards.
+// Uses char-aware searches so multi-byte text never splits a character.
+// -- Shared by StringStream and StrStream, which differ only in who owns the text.
+fn token_bounds(
+    data: &str,
+    position: usize,
+    splitter: &Splitter,
+    keep_empty: bool,
+    finished: bool,
+) -> Option<(usize, usize, usize)> {
+    let rest = data.get(position..)?;
+
+    if keep_empty {
+        if finished {
+            return None;
+        }
+        return Some(match rest.char_indices().find(|&(_, c)| splitter.is_separator(c)) {
+            Some((offset, separator)) => {
+                let end = position + offset;
+                (position, end, end + separator.len_utf8())
+            }
+            // The last token: resuming at len + 1 marks the stream as finished
+            None => (position, data.len(), data.len() + 1),
+        });
+    }
+
+    let start = position + rest.find(|c: char| !splitter.is_separator(c))?;
+    let end = data[start..]
+        .find(|c: char| splitter.is_separator(c))
+        .map_or(data.len(), |len| start + len);
+    // Skip the separators after the token, so position rests on the next token
+    let resume = data[end..]
+        .find(|c: char| !splitter.is_separator(c))
+        .map_or(data.len(), |gap| end + gap);
+    Some((start, end, resume))
 }
 
 // Example implementation for a string stream
+// -- Splits on whitespace by default. Empty tokens between adjacent separators are
+//    dropped unless keep_empty is enabled, in which case the stream follows str::split
+//    exactly (so empty input yields a single empty token).
 #[derive(Debug, Clone)]
 pub struct StringStream {
     pub data: String,
     pub position: usize,
+    splitter: Splitter,
+    keep_empty: bool,
+    // keep_empty mode only: the final (possibly empty) token has been yielded
+    finished: bool,
+}
+
+impl StringStream {
+    pub fn new(data: impl Into<String>) -> Self {
+        StringStream {
+            data: data.into(),
+            position: 0,
+            splitter: Splitter::Whitespace,
+            keep_empty: false,
+            finished: false,
+        }
+    }
+
+    /// Split on a single delimiter character, e.g. ',' for CSV-ish input
+    pub fn with_delimiter(data: impl Into<String>, delimiter: char) -> Self {
+        StringStream {
+            splitter: Splitter::Delimiter(delimiter),
+            ..StringStream::new(data)
+        }
+    }
+
+    /// Split on every character matching the predicate
+    pub fn with_split_fn(data: impl Into<String>, f: impl Fn(char) -> bool + 'static) -> Self {
+        StringStream {
+            splitter: Splitter::Predicate(Rc::new(f)),
+            ..StringStream::new(data)
+        }
+    }
+
+    /// Keep (true) or drop (false, the default) empty tokens between adjacent separators.
+    /// Kept tokens are exactly those of str::split, including one "" for empty input.
+    pub fn keep_empty(mut self, keep_empty: bool) -> Self {
+        self.keep_empty = keep_empty;
+        self
+    }
+
+    fn next_token_bounds(&self) -> Option<(usize, usize, usize)> {
+        token_bounds(&self.data, self.position, &self.splitter, self.keep_empty, self.finished)
+    }
+
+    fn advance_to(&mut self, resume: usize) {
+        self.finished = resume > self.data.len();
+        self.position = resume.min(self.data.len());
+    }
 }
 
 impl Stream for StringStream {
@@ -32,31 +161,335 @@ impl Stream for StringStream {
     where Self: 'a;
 
     fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
-        if self.position >= self.data.len() {
-            return None;
+        let (start, end, resume) = self.next_token_bounds()?;
+        self.advance_to(resume);
+        Some(&self.data[start..end])
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (start, end, resume) = self.next_token_bounds()?;
+        self.advance_to(resume);
+        Some((&self.data[start..end], start))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self.finished = false;
+        self
+    }
+}
+
+// Borrowing counterpart of StringStream
+// -- StringStream owns its String, so two cursors over it would mean cloning the text.
+//    StrStream only borrows a &'s str, which makes it cheap to copy the cursor (tee)
+//    or to cut the text into two disjoint streams (split_at).
+#[derive(Debug, Clone)]
+pub struct StrStream<'s> {
+    pub data: &'s str,
+    pub position: usize,
+    splitter: Splitter,
+    keep_empty: bool,
+    finished: bool,
+}
+
+impl<'s> StrStream<'s> {
+    /// Split borrowed text on whitespace
+    pub fn new(data: &'s str) -> Self {
+        StrStream {
+            data,
+            position: 0,
+            splitter: Splitter::Whitespace,
+            keep_empty: false,
+            finished: false,
         }
+    }
 
-        let slice = &self.data[self.position..];
-        let word_end = slice.find(' ').unwrap_or(slice.len());
-        let result = &slice[..word_end];
-        self.position += word_end + 1;
-        Some(result)
+    fn next_token_bounds(&self) -> Option<(usize, usize, usize)> {
+        token_bounds(self.data, self.position, &self.splitter, self.keep_empty, self.finished)
+    }
+}
+
+impl StringStream {
+    /// A borrowing stream over this text that starts at the current position and keeps
+    /// the same splitting rules
+    pub fn as_str_stream(&self) -> StrStream<'_> {
+        StrStream {
+            data: &self.data,
+            position: self.position,
+            splitter: self.splitter.clone(),
+            keep_empty: self.keep_empty,
+            finished: self.finished,
+        }
+    }
+}
+
+impl<'s> Stream for StrStream<'s> {
+    type Item<'a> = &'a str
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let (start, end, resume) = self.next_token_bounds()?;
+        self.finished = resume > self.data.len();
+        self.position = resume.min(self.data.len());
+        Some(&self.data[start..end])
     }
 
     fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
     where
         Self: Sized
     {
-        if self.position >= self.data.len() {
+        let (start, end, resume) = self.next_token_bounds()?;
+        self.finished = resume > self.data.len();
+        self.position = resume.min(self.data.len());
+        Some((&self.data[start..end], start))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self.finished = false;
+        self
+    }
+}
+
+// Where and why parsing stopped; position is a byte offset into the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error at byte {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Minimal parsing layer over a word stream
+// -- Token positions (what next_with_position reports) become error positions, so a
+//    failed expect can point at the offending word. A token that does not match is
+//    left in place, which lets callers try alternatives.
+// -- Items are &'s str rather than borrows of the ParseStream, because the text is
+//    borrowed from outside; parsed tokens can therefore be kept across calls.
+#[derive(Debug, Clone)]
+pub struct ParseStream<'s> {
+    tokens: StrStream<'s>,
+}
+
+impl<'s> ParseStream<'s> {
+    /// Parse whitespace-separated words
+    pub fn new(data: &'s str) -> Self {
+        ParseStream { tokens: StrStream::new(data) }
+    }
+
+    // The next token with its byte offset, without advancing
+    fn peek_token(&self) -> Option<(&'s str, usize, usize)> {
+        let (start, end, resume) = self.tokens.next_token_bounds()?;
+        Some((&self.tokens.data[start..end], start, resume))
+    }
+
+    fn advance_to(&mut self, resume: usize) {
+        self.tokens.finished = resume > self.tokens.data.len();
+        self.tokens.position = resume.min(self.tokens.data.len());
+    }
+
+    /// Consume the next token if it satisfies `predicate`; otherwise fail with an error
+    /// naming `description` and pointing at the token (or at the end of input)
+    pub fn expect<P>(&mut self, predicate: P, description: &str) -> Result<&'s str, ParseError>
+    where
+        P: FnOnce(&str) -> bool,
+    {
+        match self.peek_token() {
+            Some((token, _, resume)) if predicate(token) => {
+                self.advance_to(resume);
+                Ok(token)
+            }
+            Some((token, start, _)) => Err(ParseError {
+                position: start,
+                message: format!("expected {}, found {:?}", description, token),
+            }),
+            None => Err(ParseError {
+                position: self.tokens.data.len(),
+                message: format!("expected {}, found end of input", description),
+            }),
+        }
+    }
+
+    /// Consume the next token only if it satisfies `predicate`
+    pub fn try_next_matching<P>(&mut self, predicate: P) -> Option<&'s str>
+    where
+        P: FnOnce(&str) -> bool,
+    {
+        let (token, _, resume) = self.peek_token()?;
+        if !predicate(token) {
             return None;
         }
+        self.advance_to(resume);
+        Some(token)
+    }
+
+    /// Number of tokens left; scans ahead, so it costs one pass over the rest of the input
+    pub fn remaining_hint(&self) -> usize {
+        let mut probe = self.tokens.clone();
+        let mut count = 0;
+        while probe.next().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Succeed only when every token has been consumed
+    pub fn expect_end(&self) -> Result<(), ParseError> {
+        match self.peek_token() {
+            None => Ok(()),
+            Some((token, start, _)) => Err(ParseError {
+                position: start,
+                message: format!("expected end of input, found {:?}", token),
+            }),
+        }
+    }
+}
+
+impl<'s> From<StrStream<'s>> for ParseStream<'s> {
+    fn from(tokens: StrStream<'s>) -> Self {
+        ParseStream { tokens }
+    }
+}
+
+impl<'s> Stream for ParseStream<'s> {
+    type Item<'a> = &'s str
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let (token, _, resume) = self.peek_token()?;
+        self.advance_to(resume);
+        Some(token)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (token, start, resume) = self.peek_token()?;
+        self.advance_to(resume);
+        Some((token, start))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.tokens.reset_position();
+        self
+    }
+}
+
+// Character-by-character stream over owned text
+// -- Advances one char (not one byte) per call; positions are byte offsets of each
+//    char, so they always land on a char boundary and can be passed to seek.
+// -- Note that a user-perceived character made of several code points (e.g. "e" plus a
+//    combining accent) is yielded as several chars.
+#[derive(Debug, Clone)]
+pub struct CharStream {
+    pub data: String,
+    pub position: usize,
+}
+
+impl CharStream {
+    pub fn new(data: impl Into<String>) -> Self {
+        CharStream { data: data.into(), position: 0 }
+    }
+}
+
+impl Stream for CharStream {
+    type Item<'a> = char
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(c, _)| c)
+    }
 
-        let slice = &self.data[self.position..];
-        let word_end = slice.find(' ').unwrap_or(slice.len());
-        let result = &slice[..word_end];
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let c = self.data.get(self.position..)?.chars().next()?;
         let position = self.position;
-        self.position += word_end + 1;
-        Some((result, position))
+        self.position += c.len_utf8();
+        Some((c, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+}
+
+// Sentence-by-sentence stream over borrowed text
+// -- A sentence ends at '.', '!' or '?' followed by whitespace and then an uppercase
+//    letter (or the end of the text), so "e.g. this" stays in one sentence. Text after
+//    the last terminator is yielded as a final sentence even without punctuation.
+// -- Sentences are trimmed; positions are the byte offset of their first character.
+#[derive(Debug, Clone)]
+pub struct SentenceStream<'s> {
+    data: &'s str,
+    pub position: usize,
+}
+
+impl<'s> SentenceStream<'s> {
+    pub fn new(data: &'s str) -> Self {
+        SentenceStream { data, position: 0 }
+    }
+
+    // Byte range of the next trimmed sentence and the position to resume from
+    fn next_sentence_bounds(&self) -> Option<(usize, usize, usize)> {
+        let rest = self.data.get(self.position..)?;
+        let start = self.position + rest.find(|c: char| !c.is_whitespace())?;
+        let text = &self.data[start..];
+
+        let mut chars = text.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            if !matches!(c, '.' | '!' | '?') {
+                continue;
+            }
+            let end = start + offset + c.len_utf8();
+            if !chars.peek().is_some_and(|&(_, next)| next.is_whitespace()) {
+                // Either mid-token ("3.14", "?!") or the very end of the text
+                if chars.peek().is_none() {
+                    return Some((start, end, end));
+                }
+                continue;
+            }
+            let after_gap = self.data[end..].trim_start();
+            if after_gap.chars().next().is_none_or(char::is_uppercase) {
+                return Some((start, end, end));
+            }
+        }
+        let end = start + text.trim_end().len();
+        Some((start, end, self.data.len()))
+    }
+}
+
+impl<'s> Stream for SentenceStream<'s> {
+    type Item<'a> = &'s str
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(sentence, _)| sentence)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (start, end, resume) = self.next_sentence_bounds()?;
+        self.position = resume;
+        Some((&self.data[start..end], start))
     }
 
     fn reset_position(&mut self) -> &mut Self {
@@ -65,6 +498,62 @@ impl Stream for StringStream {
     }
 }
 
+// Everything known about one StringStream token
+// -- byte_offset and len are in bytes, so text == data[byte_offset..byte_offset + len];
+//    index counts tokens, which diverges from byte_offset as soon as separators repeat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenInfo<'a> {
+    pub text: &'a str,
+    pub byte_offset: usize,
+    pub index: usize,
+    pub len: usize,
+}
+
+impl StringStream {
+    /// Yield a TokenInfo per token instead of the bare text
+    pub fn with_metadata(self) -> WithMetadata {
+        WithMetadata { stream: self, index: 0 }
+    }
+}
+
+pub struct WithMetadata {
+    stream: StringStream,
+    index: usize,
+}
+
+impl WithMetadata {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &StringStream {
+        &self.stream
+    }
+}
+
+impl Stream for WithMetadata {
+    type Item<'a> = TokenInfo<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(info, _)| info)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (text, byte_offset) = self.stream.next_with_position()?;
+        let index = self.index;
+        self.index += 1;
+        Some((TokenInfo { text, byte_offset, index, len: text.len() }, byte_offset))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.index = 0;
+        self
+    }
+}
+
 // Example implementation for an integer stream
 pub struct IntStream {
     pub data: Vec<i32>,
@@ -104,4 +593,5129 @@ impl Stream for IntStream {
         self.position = 0;
         self
     }
-}
\ No newline at end of file
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.len().saturating_sub(self.position);
+        (remaining, Some(remaining))
+    }
+}
+
+// Lazy counterpart of IntStream: values are computed, not stored
+// -- Yields start, start + step, ... while the value is short of `end` (exclusive, as
+//    with Range), counting down for a negative step. Item<'a> = i32 is an owned value,
+//    so the lifetime parameter goes unused: not every Stream has to lend.
+#[derive(Debug, Clone)]
+pub struct RangeStream {
+    start: i32,
+    current: i32,
+    end: i32,
+    step: i32,
+}
+
+impl RangeStream {
+    /// Fails when `step` is 0, which would never reach `end`
+    pub fn new(start: i32, end: i32, step: i32) -> Result<Self, &'static str> {
+        if step == 0 {
+            return Err("Step must be non-zero");
+        }
+        Ok(RangeStream { start, current: start, end, step })
+    }
+
+    fn in_range(&self) -> bool {
+        if self.step > 0 { self.current < self.end } else { self.current > self.end }
+    }
+}
+
+impl Stream for RangeStream {
+    type Item<'a> = i32
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(value, _)| value)
+    }
+
+    /// Positions count values yielded so far, as for IntStream
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if !self.in_range() {
+            return None;
+        }
+        let value = self.current;
+        let position = ((value as i64 - self.start as i64) / self.step as i64) as usize;
+        // Overflowing i32 means the next value would be past `end` anyway
+        self.current = value.checked_add(self.step).unwrap_or(self.end);
+        Some((value, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.current = self.start;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if !self.in_range() {
+            return (0, Some(0));
+        }
+        let (distance, step) = ((self.end as i64 - self.current as i64).abs(), (self.step as i64).abs());
+        let remaining = ((distance + step - 1) / step) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+// Generic stream borrowing any slice
+// -- The stream itself borrows the slice for 's, while each item is lent for 'a.
+//    `Self: 'a` on Item<'a> implies 's: 'a, so an item can never outlive the slice.
+#[derive(Debug, Clone)]
+pub struct SliceStream<'s, T> {
+    pub data: &'s [T],
+    pub position: usize,
+}
+
+impl<'s, T> SliceStream<'s, T> {
+    pub fn from_slice(data: &'s [T]) -> Self {
+        SliceStream { data, position: 0 }
+    }
+
+    /// Number of items not yet yielded
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+}
+
+impl<'s, T> Stream for SliceStream<'s, T> {
+    type Item<'a> = &'a T
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let result = self.data.get(self.position)?;
+        self.position += 1;
+        Some(result)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let result = self.data.get(self.position)?;
+        let position = self.position;
+        self.position += 1;
+        Some((result, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+// Stream over the rows of a Matrix, each lent as a fixed-size array
+// -- The row length C stays in the item type, so consumers get [T; C] rather than
+//    an unsized slice. Positions are row indices.
+#[derive(Debug, Clone)]
+pub struct RowStream<'m, T, const R: usize, const C: usize> {
+    rows: &'m [[T; C]; R],
+    pub position: usize,
+}
+
+impl<'m, T, const R: usize, const C: usize> RowStream<'m, T, R, C> {
+    pub fn new(rows: &'m [[T; C]; R]) -> Self {
+        RowStream { rows, position: 0 }
+    }
+}
+
+impl<'m, T, const R: usize, const C: usize> Stream for RowStream<'m, T, R, C> {
+    type Item<'a> = &'a [T; C]
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(row, _)| row)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let row = self.rows.get(self.position)?;
+        let position = self.position;
+        self.position += 1;
+        Some((row, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+}
+
+// Stream over the elements of a const-generic Array
+#[derive(Debug, Clone)]
+pub struct ArrayStream<'s, T, const N: usize> {
+    data: &'s [T; N],
+    pub position: usize,
+}
+
+impl<'s, T, const N: usize> ArrayStream<'s, T, N> {
+    pub fn new(data: &'s [T; N]) -> Self {
+        ArrayStream { data, position: 0 }
+    }
+}
+
+impl<'s, T, const N: usize> Stream for ArrayStream<'s, T, N> {
+    type Item<'a> = &'a T
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(item, _)| item)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let item = self.data.get(self.position)?;
+        let position = self.position;
+        self.position += 1;
+        Some((item, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = N.saturating_sub(self.position);
+        (remaining, Some(remaining))
+    }
+}
+
+// Fixed-size chunks of a byte slice, with the chunk size N in the type
+// -- Items are &[u8; N] rather than &[u8], so callers get the length checked at compile
+//    time (e.g. u32::from_le_bytes(*chunk) for N = 4). The trailing bytes that do not
+//    fill a whole chunk are never yielded; remainder() exposes them instead.
+#[derive(Debug, Clone)]
+pub struct ByteChunkStream<'s, const N: usize> {
+    data: &'s [u8],
+    pub position: usize,
+}
+
+impl<'s, const N: usize> ByteChunkStream<'s, N> {
+    pub fn new(data: &'s [u8]) -> Self {
+        const { assert!(N > 0, "chunk size N must be non-zero") };
+        ByteChunkStream { data, position: 0 }
+    }
+
+    /// The trailing bytes after the last full chunk (empty when N divides the length)
+    pub fn remainder(&self) -> &'s [u8] {
+        &self.data[self.data.len() - self.data.len() % N..]
+    }
+}
+
+impl<'s, const N: usize> Stream for ByteChunkStream<'s, N> {
+    type Item<'a> = &'a [u8; N]
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(chunk, _)| chunk)
+    }
+
+    /// Positions are byte offsets, so they step by N
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (chunk, _) = self.data.get(self.position..)?.split_first_chunk::<N>()?;
+        let position = self.position;
+        self.position += N;
+        Some((chunk, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.len().saturating_sub(self.position) / N;
+        (remaining, Some(remaining))
+    }
+}
+
+// Line-by-line stream over borrowed text
+// -- Lines end at \n, and a trailing \r is stripped so CRLF input yields the same lines.
+//    Items borrow from the source text ('s), not from the stream, so they may outlive
+//    the `&mut self` borrow that produced them.
+#[derive(Debug, Clone)]
+pub struct LineStream<'s> {
+    pub data: &'s str,
+    pub position: usize,
+    pub skip_empty: bool,
+}
+
+impl<'s> LineStream<'s> {
+    /// Stream every line, including empty ones
+    pub fn new(data: &'s str) -> Self {
+        LineStream { data, position: 0, skip_empty: false }
+    }
+
+    /// Choose whether empty lines are skipped or yielded as ""
+    pub fn with_skip_empty(mut self, skip_empty: bool) -> Self {
+        self.skip_empty = skip_empty;
+        self
+    }
+
+    // Returns the next line and the byte offset where it starts, advancing past its terminator
+    fn advance(&mut self) -> Option<(&'s str, usize)> {
+        while self.position < self.data.len() {
+            let start = self.position;
+            let rest = &self.data[start..];
+            let (raw, consumed) = match rest.find('\n') {
+                Some(end) => (&rest[..end], end + 1),
+                None => (rest, rest.len()),
+            };
+            self.position += consumed;
+
+            let line = raw.strip_suffix('\r').unwrap_or(raw);
+            if line.is_empty() && self.skip_empty {
+                continue;
+            }
+            return Some((line, start));
+        }
+        None
+    }
+}
+
+impl<'s> Stream for LineStream<'s> {
+    type Item<'a> = &'s str
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.advance().map(|(line, _)| line)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.advance()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+}
+
+// One CSV record borrowed from the source text
+// -- Fields are stored as byte ranges into the record's line. field() returns the raw
+//    text between the quotes, so an escaped quote still reads as "" there;
+//    unescaped() resolves it, allocating only when the field actually contains one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record<'s> {
+    line: &'s str,
+    spans: Vec<(usize, usize, bool)>,
+}
+
+impl<'s> Record<'s> {
+    // Parse one line: comma separated, optional double-quote quoting with "" escapes.
+    // Text after a closing quote up to the next comma is dropped, and an unterminated
+    // quote takes the rest of the line as its field.
+    fn parse(line: &'s str) -> Self {
+        let mut spans = Vec::new();
+        let mut position = 0;
+        loop {
+            let rest = &line[position..];
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let start = position + 1;
+                let mut escaped = false;
+                let mut close = None;
+                let mut chars = quoted.char_indices().peekable();
+                while let Some((offset, c)) = chars.next() {
+                    if c != '"' {
+                        continue;
+                    }
+                    if chars.next_if(|&(_, next)| next == '"').is_some() {
+                        escaped = true;
+                    } else {
+                        close = Some(start + offset);
+                        break;
+                    }
+                }
+                let Some(end) = close else {
+                    spans.push((start, line.len(), escaped));
+                    break;
+                };
+                spans.push((start, end, escaped));
+                match line[end..].find(',') {
+                    Some(comma) => position = end + comma + 1,
+                    None => break,
+                }
+            } else {
+                match rest.find(',') {
+                    Some(comma) => {
+                        spans.push((position, position + comma, false));
+                        position += comma + 1;
+                    }
+                    None => {
+                        spans.push((position, line.len(), false));
+                        break;
+                    }
+                }
+            }
+        }
+        Record { line, spans }
+    }
+
+    /// Raw text of field `i`, without surrounding quotes
+    pub fn field(&self, i: usize) -> Option<&'s str> {
+        let line = self.line;
+        self.spans.get(i).map(|&(start, end, _)| &line[start..end])
+    }
+
+    /// Field `i` with "" escapes resolved to a single quote
+    pub fn unescaped(&self, i: usize) -> Option<Cow<'s, str>> {
+        let &(_, _, escaped) = self.spans.get(i)?;
+        let raw = self.field(i)?;
+        Some(if escaped { Cow::Owned(raw.replace("\"\"", "\"")) } else { Cow::Borrowed(raw) })
+    }
+
+    /// Raw text of every field, in order
+    pub fn fields(&self) -> impl Iterator<Item = &'s str> + '_ {
+        let line = self.line;
+        self.spans.iter().map(move |&(start, end, _)| &line[start..end])
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The whole record line as it appears in the source (without its line ending)
+    pub fn as_str(&self) -> &'s str {
+        self.line
+    }
+}
+
+// Record-by-record stream over borrowed CSV text
+// -- Records are lines (quoted fields cannot span lines in this dialect). Blank lines
+//    are skipped, so a trailing newline never produces an extra record.
+#[derive(Debug, Clone)]
+pub struct CsvStream<'s> {
+    lines: LineStream<'s>,
+}
+
+impl<'s> CsvStream<'s> {
+    pub fn new(data: &'s str) -> Self {
+        CsvStream { lines: LineStream::new(data).with_skip_empty(true) }
+    }
+
+    /// Byte offset where the next record will be searched for
+    pub fn position(&self) -> usize {
+        self.lines.position
+    }
+}
+
+impl<'s> Stream for CsvStream<'s> {
+    type Item<'a> = Record<'s>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.lines.next().map(Record::parse)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.lines
+            .next_with_position()
+            .map(|(line, position)| (Record::parse(line), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.lines.reset_position();
+        self
+    }
+}
+
+// key=value config entries over borrowed text
+// -- Blank lines and lines starting with # (after leading whitespace) are skipped, as
+//    are lines without '='. Keys and values are trimmed; a value may itself contain '='
+//    since only the first one splits. Items borrow from the source text ('s).
+#[derive(Debug, Clone)]
+pub struct KeyValueStream<'s> {
+    lines: LineStream<'s>,
+}
+
+impl<'s> KeyValueStream<'s> {
+    pub fn new(data: &'s str) -> Self {
+        KeyValueStream { lines: LineStream::new(data).with_skip_empty(true) }
+    }
+
+    fn advance(&mut self) -> Option<((&'s str, &'s str), usize)> {
+        while let Some((line, position)) = self.lines.next_with_position() {
+            let line = line.trim_start();
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                return Some(((key.trim(), value.trim()), position));
+            }
+        }
+        None
+    }
+
+    /// Collect the remaining entries; for duplicate keys the last one wins
+    pub fn collect_map(mut self) -> HashMap<&'s str, &'s str> {
+        let mut map = HashMap::new();
+        while let Some(((key, value), _)) = self.advance() {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<'s> Stream for KeyValueStream<'s> {
+    type Item<'a> = (&'s str, &'s str)
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.advance().map(|(entry, _)| entry)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.advance()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.lines.reset_position();
+        self
+    }
+}
+
+// Lexical tokens of a JSON-like document, borrowing from the source text
+// -- Str holds the raw text between the quotes (escapes are left as written) and
+//    Number the literal as written, so lexing never allocates.
+// -- Error carries the offending text and its byte offset; lexing resumes after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token<'s> {
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    Str(&'s str),
+    Number(&'s str),
+    Bool(bool),
+    Null,
+    Error(&'s str, usize),
+}
+
+// JSON number grammar: -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+fn is_json_number(text: &str) -> bool {
+    fn digits(bytes: &[u8], i: &mut usize) -> usize {
+        let start = *i;
+        while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+            *i += 1;
+        }
+        *i - start
+    }
+
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if bytes.first() == Some(&b'-') {
+        i += 1;
+    }
+    let int_start = i;
+    let int_digits = digits(bytes, &mut i);
+    if int_digits == 0 || (int_digits > 1 && bytes[int_start] == b'0') {
+        return false;
+    }
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        if digits(bytes, &mut i) == 0 {
+            return false;
+        }
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        if digits(bytes, &mut i) == 0 {
+            return false;
+        }
+    }
+    i == bytes.len()
+}
+
+// Allocation-free lexer over borrowed JSON-ish text
+#[derive(Debug, Clone)]
+pub struct JsonTokenStream<'s> {
+    pub data: &'s str,
+    pub position: usize,
+}
+
+impl<'s> JsonTokenStream<'s> {
+    pub fn new(data: &'s str) -> Self {
+        JsonTokenStream { data, position: 0 }
+    }
+
+    // Lex one token, returning it with its start offset and advancing past it
+    fn lex(&mut self) -> Option<(Token<'s>, usize)> {
+        let data = self.data;
+        let rest = data.get(self.position..)?;
+        let start = self.position + rest.find(|c: char| !c.is_whitespace())?;
+        let c = data[start..].chars().next()?;
+
+        let (token, end) = match c {
+            '{' => (Token::LBrace, start + 1),
+            '}' => (Token::RBrace, start + 1),
+            '[' => (Token::LBracket, start + 1),
+            ']' => (Token::RBracket, start + 1),
+            ':' => (Token::Colon, start + 1),
+            ',' => (Token::Comma, start + 1),
+            '"' => {
+                // A backslash escapes whatever follows, including a quote
+                let mut escaped = false;
+                let close = data[start + 1..].char_indices().find_map(|(offset, c)| {
+                    match (escaped, c) {
+                        (true, _) => escaped = false,
+                        (false, '\\') => escaped = true,
+                        (false, '"') => return Some(start + 1 + offset),
+                        _ => {}
+                    }
+                    None
+                });
+                match close {
+                    Some(close) => (Token::Str(&data[start + 1..close]), close + 1),
+                    None => (Token::Error(&data[start..], start), data.len()),
+                }
+            }
+            '-' | '0'..='9' => {
+                let end = data[start..]
+                    .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+                    .map_or(data.len(), |len| start + len);
+                let text = &data[start..end];
+                let token = if is_json_number(text) { Token::Number(text) } else { Token::Error(text, start) };
+                (token, end)
+            }
+            c if c.is_alphabetic() => {
+                let end = data[start..]
+                    .find(|c: char| !c.is_alphanumeric())
+                    .map_or(data.len(), |len| start + len);
+                let token = match &data[start..end] {
+                    "true" => Token::Bool(true),
+                    "false" => Token::Bool(false),
+                    "null" => Token::Null,
+                    word => Token::Error(word, start),
+                };
+                (token, end)
+            }
+            other => {
+                let end = start + other.len_utf8();
+                (Token::Error(&data[start..end], start), end)
+            }
+        };
+        self.position = end;
+        Some((token, start))
+    }
+}
+
+impl<'s> Stream for JsonTokenStream<'s> {
+    type Item<'a> = Token<'s>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.lex().map(|(token, _)| token)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.lex()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+}
+
+// Drop a trailing "\n" or "\r\n" left by read_line
+fn strip_line_ending(buffer: &mut String) {
+    if buffer.ends_with('\n') {
+        buffer.pop();
+        if buffer.ends_with('\r') {
+            buffer.pop();
+        }
+    }
+}
+
+// Lending stream over any buffered reader
+// -- Every line read (by next, or ahead of it by peek) refills the same internal String,
+//    so each item borrows the stream itself (Item<'a> = &'a str) and must be dropped
+//    before the next call.
+//    This is the classic case a plain Iterator cannot express.
+pub struct ReaderStream<R> {
+    reader: R,
+    buffer: String,
+    // Byte offset in the source where the next line starts
+    offset: usize,
+    error: Option<std::io::Error>,
+    // Start offset of a line peek already read into the buffer, which next lends next
+    peeked: Option<usize>,
+}
+
+impl<R: std::io::BufRead> ReaderStream<R> {
+    pub fn new(reader: R) -> Self {
+        ReaderStream {
+            reader,
+            buffer: String::new(),
+            offset: 0,
+            error: None,
+            peeked: None,
+        }
+    }
+
+    /// The I/O error that ended the stream early, if any
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    // Refills the buffer with the next line; returns the line's starting byte offset
+    fn fill_next_line(&mut self) -> Option<usize> {
+        if self.error.is_some() {
+            return None;
+        }
+        if let Some(start) = self.peeked.take() {
+            return Some(start);
+        }
+
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(read) => {
+                let start = self.offset;
+                self.offset += read;
+                strip_line_ending(&mut self.buffer);
+                Some(start)
+            }
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+}
+
+// Rewinding needs Seek, which stdin, sockets and pipes do not have
+impl<R: std::io::BufRead + std::io::Seek> ReaderStream<R> {
+    /// Seek the reader back to its start so the lines are read again from offset 0.
+    /// On failure the stream is left as it was.
+    pub fn rewind(&mut self) -> std::io::Result<()> {
+        self.reader.seek(std::io::SeekFrom::Start(0))?;
+        self.buffer.clear();
+        self.offset = 0;
+        self.error = None;
+        self.peeked = None;
+        Ok(())
+    }
+}
+
+// Works with any BufRead, seekable or not; only resetting needs rewind and Seek
+impl<R: std::io::BufRead> Stream for ReaderStream<R> {
+    type Item<'a> = &'a str
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.fill_next_line()?;
+        Some(&self.buffer)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let start = self.fill_next_line()?;
+        Some((&self.buffer, start))
+    }
+
+    /// A reader in general cannot go back, so instead of carrying on from the current
+    /// line this ends the stream with an ErrorKind::Unsupported error (see error()).
+    /// Use rewind when R: Seek; it also clears the error.
+    fn reset_position(&mut self) -> &mut Self {
+        self.buffer.clear();
+        self.peeked = None;
+        self.error = Some(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "ReaderStream cannot reset; call rewind on a seekable reader",
+        ));
+        self
+    }
+}
+
+// Lending streams whose reads can fail
+// -- Stream::next can only say "item" or "done", so ReaderStream has to stash an I/O
+//    error and end early. TryStream puts the error in the signature instead:
+//    Ok(Some(item)), Ok(None) at the end, or Err(e). Ok<'a> is the lending GAT, while
+//    Error is a plain associated type because errors are owned and outlive the call.
+pub trait TryStream {
+    type Ok<'a>
+    where
+        Self: 'a;
+    type Error;
+
+    fn try_next<'a>(&'a mut self) -> Result<Option<Self::Ok<'a>>, Self::Error>;
+}
+
+// Any Stream viewed as a TryStream that never fails
+pub struct IntoTryStream<S> {
+    stream: S,
+}
+
+impl<S> IntoTryStream<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Stream> TryStream for IntoTryStream<S> {
+    type Ok<'a> = S::Item<'a>
+    where
+        Self: 'a;
+    type Error = std::convert::Infallible;
+
+    fn try_next<'a>(&'a mut self) -> Result<Option<Self::Ok<'a>>, Self::Error> {
+        Ok(self.stream.next())
+    }
+}
+
+// Line-by-line reader that reports I/O errors from the call that hit them
+// -- Lines are lent from an internal buffer as in ReaderStream, with "\n" / "\r\n"
+//    stripped. An error does not end the stream; whether retrying makes sense is up to
+//    the caller and the reader.
+pub struct FallibleLineStream<R> {
+    reader: R,
+    buffer: String,
+}
+
+impl<R: std::io::BufRead> FallibleLineStream<R> {
+    pub fn new(reader: R) -> Self {
+        FallibleLineStream { reader, buffer: String::new() }
+    }
+}
+
+impl<R: std::io::BufRead> TryStream for FallibleLineStream<R> {
+    type Ok<'a> = &'a str
+    where
+        Self: 'a;
+    type Error = std::io::Error;
+
+    fn try_next<'a>(&'a mut self) -> Result<Option<Self::Ok<'a>>, Self::Error> {
+        self.buffer.clear();
+        if self.reader.read_line(&mut self.buffer)? == 0 {
+            return Ok(None);
+        }
+        strip_line_ending(&mut self.buffer);
+        Ok(Some(&self.buffer))
+    }
+}
+
+// Streams that know exactly how many items are left
+// -- A marker in the spirit of ExactSizeIterator: implementors promise that size_hint
+//    returns (n, Some(n)), which len() then reports. Slice- and array-backed streams
+//    qualify because the remaining count is just len - position.
+pub trait ExactSizeStream: Stream {
+    fn len(&self) -> usize {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower));
+        lower
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ExactSizeStream for IntStream {}
+impl ExactSizeStream for RangeStream {}
+impl<'s, T> ExactSizeStream for SliceStream<'s, T> {}
+impl<'s, T> ExactSizeStream for SliceMutStream<'s, T> {}
+impl<'s, T, const N: usize> ExactSizeStream for ArrayStream<'s, T, N> {}
+impl<'s, const N: usize> ExactSizeStream for ByteChunkStream<'s, N> {}
+
+// Streams that can show their next item without consuming it
+// -- peek returns exactly what the following next() will yield, and repeated peeks
+//    leave the position untouched.
+// -- filter and skip_while judge items through peek, so they need this. The base
+//    streams below implement it, and so do the pass-through adapters (Take, Skip, Map,
+//    MapStream, Enumerate, Fuse, InspectStream, Filter, SkipWhile) over a peekable stream.
+pub trait PeekableStream: Stream {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+impl PeekableStream for StringStream {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let (start, end, _) = self.next_token_bounds()?;
+        Some(&self.data[start..end])
+    }
+}
+
+impl PeekableStream for IntStream {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.data.get(self.position)
+    }
+}
+
+impl<'s, T> PeekableStream for SliceStream<'s, T> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.data.get(self.position)
+    }
+}
+
+impl PeekableStream for CharStream {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.data.get(self.position..)?.chars().next()
+    }
+}
+
+impl<'s> PeekableStream for LineStream<'s> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let position = self.position;
+        let line = self.advance().map(|(line, _)| line);
+        self.position = position;
+        line
+    }
+}
+
+// Reads the next line into the buffer ahead of time; next then lends it without reading
+impl<R: std::io::BufRead> PeekableStream for ReaderStream<R> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.fill_next_line()?);
+        }
+        Some(&self.buffer)
+    }
+}
+
+// Why a seek was refused; the stream position is left unchanged
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeekError {
+    OutOfBounds { position: usize, len: usize },
+    NotCharBoundary(usize),
+    BeforeStart { position: usize, back: usize },
+}
+
+impl fmt::Display for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeekError::OutOfBounds { position, len } => {
+                write!(f, "position {} is past the end ({})", position, len)
+            }
+            SeekError::NotCharBoundary(position) => {
+                write!(f, "position {} is inside a UTF-8 character", position)
+            }
+            SeekError::BeforeStart { position, back } => {
+                write!(f, "cannot rewind {} from position {}", back, position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeekError {}
+
+// Streams with random access to their cursor
+// -- Positions use the same units as next_with_position (byte offsets for text
+//    streams, indices for slice-backed streams), so a position captured from an
+//    item can be passed straight back to seek. Seeking to the end is allowed.
+pub trait SeekableStream: Stream {
+    fn position(&self) -> usize;
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError>;
+
+    /// Move the cursor back by `n` position units
+    fn rewind(&mut self, n: usize) -> Result<(), SeekError> {
+        let current = self.position();
+        let target = current
+            .checked_sub(n)
+            .ok_or(SeekError::BeforeStart { position: current, back: n })?;
+        self.seek(target)
+    }
+}
+
+// Shared bounds check for streams over text
+fn check_text_position(data: &str, position: usize) -> Result<(), SeekError> {
+    if position > data.len() {
+        return Err(SeekError::OutOfBounds { position, len: data.len() });
+    }
+    if !data.is_char_boundary(position) {
+        return Err(SeekError::NotCharBoundary(position));
+    }
+    Ok(())
+}
+
+// Shared bounds check for streams over indexed items
+fn check_index_position(len: usize, position: usize) -> Result<(), SeekError> {
+    if position > len {
+        return Err(SeekError::OutOfBounds { position, len });
+    }
+    Ok(())
+}
+
+impl SeekableStream for StringStream {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_text_position(&self.data, position)?;
+        self.position = position;
+        self.finished = false;
+        Ok(())
+    }
+}
+
+impl SeekableStream for CharStream {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_text_position(&self.data, position)?;
+        self.position = position;
+        Ok(())
+    }
+}
+
+impl<'s> SeekableStream for StrStream<'s> {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_text_position(self.data, position)?;
+        self.position = position;
+        self.finished = false;
+        Ok(())
+    }
+}
+
+impl SeekableStream for IntStream {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_index_position(self.data.len(), position)?;
+        self.position = position;
+        Ok(())
+    }
+}
+
+impl<'s, T> SeekableStream for SliceStream<'s, T> {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_index_position(self.data.len(), position)?;
+        self.position = position;
+        Ok(())
+    }
+}
+
+impl<'s> SeekableStream for LineStream<'s> {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_text_position(self.data, position)?;
+        self.position = position;
+        Ok(())
+    }
+}
+
+impl<'s> SeekableStream for KeyValueStream<'s> {
+    fn position(&self) -> usize {
+        self.lines.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        self.lines.seek(position)
+    }
+}
+
+impl<'s> SeekableStream for CsvStream<'s> {
+    fn position(&self) -> usize {
+        self.lines.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        self.lines.seek(position)
+    }
+}
+
+// Saved cursor state for backtracking
+// -- Unlike a bare position from SeekableStream, a checkpoint captures everything
+//    needed to resume (e.g. StringStream's keep_empty end-of-input flag) and remembers
+//    which data it was taken from. That source is identified by the address and length
+//    of the stream's data, so restore rejects checkpoints from other streams, from
+//    clones (which own a copy of the data), and from before the data was modified.
+//    Cursors borrowing the same slice share a source, so they can swap checkpoints.
+pub trait CheckpointStream: Stream {
+    type Checkpoint: Clone;
+
+    fn checkpoint(&self) -> Self::Checkpoint;
+
+    fn restore(&mut self, checkpoint: Self::Checkpoint) -> Result<(), CheckpointError>;
+}
+
+// Why a checkpoint was refused; the stream is left unchanged
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckpointError {
+    ForeignStream,
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::ForeignStream => write!(f, "checkpoint was taken from a different stream"),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SourceId {
+    address: usize,
+    len: usize,
+}
+
+impl SourceId {
+    fn of<T>(data: &[T]) -> Self {
+        SourceId { address: data.as_ptr() as usize, len: data.len() }
+    }
+}
+
+// Checkpoint of a StringStream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextCheckpoint {
+    source: SourceId,
+    position: usize,
+    finished: bool,
+}
+
+impl TextCheckpoint {
+    /// Byte offset the stream resumes from
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+// Checkpoint of an index-based stream (IntStream, SliceStream)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexCheckpoint {
+    source: SourceId,
+    position: usize,
+}
+
+impl IndexCheckpoint {
+    /// Index of the next item the stream yields
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl CheckpointStream for StringStream {
+    type Checkpoint = TextCheckpoint;
+
+    fn checkpoint(&self) -> TextCheckpoint {
+        TextCheckpoint {
+            source: SourceId::of(self.data.as_bytes()),
+            position: self.position,
+            finished: self.finished,
+        }
+    }
+
+    fn restore(&mut self, checkpoint: TextCheckpoint) -> Result<(), CheckpointError> {
+        if checkpoint.source != SourceId::of(self.data.as_bytes()) {
+            return Err(CheckpointError::ForeignStream);
+        }
+        self.position = checkpoint.position;
+        self.finished = checkpoint.finished;
+        Ok(())
+    }
+}
+
+impl CheckpointStream for IntStream {
+    type Checkpoint = IndexCheckpoint;
+
+    fn checkpoint(&self) -> IndexCheckpoint {
+        IndexCheckpoint { source: SourceId::of(&self.data), position: self.position }
+    }
+
+    fn restore(&mut self, checkpoint: IndexCheckpoint) -> Result<(), CheckpointError> {
+        if checkpoint.source != SourceId::of(&self.data) {
+            return Err(CheckpointError::ForeignStream);
+        }
+        self.position = checkpoint.position;
+        Ok(())
+    }
+}
+
+impl<'s, T> CheckpointStream for SliceStream<'s, T> {
+    type Checkpoint = IndexCheckpoint;
+
+    fn checkpoint(&self) -> IndexCheckpoint {
+        IndexCheckpoint { source: SourceId::of(self.data), position: self.position }
+    }
+
+    fn restore(&mut self, checkpoint: IndexCheckpoint) -> Result<(), CheckpointError> {
+        if checkpoint.source != SourceId::of(self.data) {
+            return Err(CheckpointError::ForeignStream);
+        }
+        self.position = checkpoint.position;
+        Ok(())
+    }
+}
+
+// Streams that can also lend their items mutably
+// -- ItemMut<'a> is a second GAT tied to the same `&'a mut self` borrow, so the borrow
+//    checker guarantees at most one mutable item is live at a time: calling next_mut
+//    again requires the previous item to be gone. Both next and next_mut advance the
+//    same cursor.
+pub trait MutStream: Stream {
+    type ItemMut<'a>
+    where
+        Self: 'a;
+
+    fn next_mut<'a>(&'a mut self) -> Option<Self::ItemMut<'a>>;
+}
+
+impl MutStream for IntStream {
+    type ItemMut<'a> = &'a mut i32
+    where
+        Self: 'a;
+
+    fn next_mut<'a>(&'a mut self) -> Option<Self::ItemMut<'a>> {
+        let result = self.data.get_mut(self.position)?;
+        self.position += 1;
+        Some(result)
+    }
+}
+
+// Slice stream with exclusive access, so items can be modified in place
+#[derive(Debug)]
+pub struct SliceMutStream<'s, T> {
+    pub data: &'s mut [T],
+    pub position: usize,
+}
+
+impl<'s, T> SliceMutStream<'s, T> {
+    pub fn from_slice(data: &'s mut [T]) -> Self {
+        SliceMutStream { data, position: 0 }
+    }
+}
+
+impl<'s, T> Stream for SliceMutStream<'s, T> {
+    type Item<'a> = &'a T
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let result = self.data.get(self.position)?;
+        self.position += 1;
+        Some(result)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let result = self.data.get(self.position)?;
+        let position = self.position;
+        self.position += 1;
+        Some((result, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.len().saturating_sub(self.position);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'s, T> MutStream for SliceMutStream<'s, T> {
+    type ItemMut<'a> = &'a mut T
+    where
+        Self: 'a;
+
+    fn next_mut<'a>(&'a mut self) -> Option<Self::ItemMut<'a>> {
+        let result = self.data.get_mut(self.position)?;
+        self.position += 1;
+        Some(result)
+    }
+}
+
+impl<'s, T> SeekableStream for SliceMutStream<'s, T> {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), SeekError> {
+        check_index_position(self.data.len(), position)?;
+        self.position = position;
+        Ok(())
+    }
+}
+
+// Streams whose cursor can also step backwards
+// -- prev moves the cursor back one item and yields the item it moved over, so
+//    next, prev, next yields the same item three times (like a text cursor).
+//    Positions are the indices reported by next_with_position. StringStream is left
+//    out: finding the previous token would mean re-scanning word boundaries.
+pub trait BidiStream: Stream {
+    fn prev<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+
+    fn prev_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized;
+
+    /// Move the cursor past the last item, so the next prev() yields it
+    fn seek_end(&mut self) -> &mut Self;
+}
+
+impl BidiStream for IntStream {
+    fn prev<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.prev_with_position().map(|(item, _)| item)
+    }
+
+    fn prev_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let position = self.position.checked_sub(1)?;
+        let result = self.data.get(position)?;
+        self.position = position;
+        Some((result, position))
+    }
+
+    fn seek_end(&mut self) -> &mut Self {
+        self.position = self.data.len();
+        self
+    }
+}
+
+impl<'s, T> BidiStream for SliceStream<'s, T> {
+    fn prev<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.prev_with_position().map(|(item, _)| item)
+    }
+
+    fn prev_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let position = self.position.checked_sub(1)?;
+        let result = self.data.get(position)?;
+        self.position = position;
+        Some((result, position))
+    }
+
+    fn seek_end(&mut self) -> &mut Self {
+        self.position = self.data.len();
+        self
+    }
+}
+
+// Streams over shared, immutable data that can hand out more than one cursor
+// -- split_at cuts the remaining items into two streams over disjoint regions of the
+//    data: the first yields the next n items, the second everything after them. The
+//    second stream's positions are relative to its own region, so they start at 0.
+// -- tee copies the cursor; both copies borrow the same data and advance separately.
+pub trait SplittableStream: Stream + Sized {
+    fn split_at(self, n: usize) -> (Self, Self);
+
+    fn tee(&self) -> (Self, Self);
+}
+
+impl<'s, T> SplittableStream for SliceStream<'s, T> {
+    fn split_at(self, n: usize) -> (Self, Self) {
+        let start = self.position.min(self.data.len());
+        let mid = start.saturating_add(n).min(self.data.len());
+        let (head, tail) = self.data.split_at(mid);
+        (
+            SliceStream { data: head, position: start },
+            SliceStream::from_slice(tail),
+        )
+    }
+
+    fn tee(&self) -> (Self, Self) {
+        // Built field by field: the derived Clone would needlessly require T: Clone
+        let cursor = || SliceStream { data: self.data, position: self.position };
+        (cursor(), cursor())
+    }
+}
+
+impl<'s> SplittableStream for StrStream<'s> {
+    fn split_at(self, n: usize) -> (Self, Self) {
+        // Find where the n-th token ends (head) and where the next one may start (tail)
+        let mut probe = self.clone();
+        let (mut head_end, mut tail_start) = (self.position.min(self.data.len()), self.position);
+        for _ in 0..n {
+            match probe.next_token_bounds() {
+                Some((_, end, resume)) => {
+                    head_end = end;
+                    tail_start = resume;
+                    probe.finished = resume > probe.data.len();
+                    probe.position = resume.min(probe.data.len());
+                }
+                None => {
+                    head_end = self.data.len();
+                    tail_start = self.data.len();
+                    break;
+                }
+            }
+        }
+        let tail_start = tail_start.min(self.data.len());
+
+        let head = StrStream {
+            data: &self.data[..head_end],
+            // With n == 0 the head is empty; in keep_empty mode it must not yield ""
+            finished: self.finished || n == 0,
+            ..self.clone()
+        };
+        let tail = StrStream {
+            data: &self.data[tail_start..],
+            position: 0,
+            // Once the probe has yielded the last token the (empty) tail must not yield ""
+            finished: probe.finished,
+            ..self
+        };
+        (head, tail)
+    }
+
+    fn tee(&self) -> (Self, Self) {
+        (self.clone(), self.clone())
+    }
+}
+
+// Callable used by Map: Output is a separate associated type so it can depend on
+// the lending lifetime of the item (e.g. str::trim maps &'a str to &'a str)
+pub trait StreamMapFn<Arg> {
+    type Output;
+
+    fn call_item(&mut self, arg: Arg) -> Self::Output;
+}
+
+impl<Arg, O, F> StreamMapFn<Arg> for F
+where
+    F: FnMut(Arg) -> O,
+{
+    type Output = O;
+
+    fn call_item(&mut self, arg: Arg) -> Self::Output {
+        self(arg)
+    }
+}
+
+// Conversion from a lent item to a value that no longer borrows the stream
+// -- Implemented for every reference whose target is ToOwned, so &str becomes String
+//    and &i32 becomes i32.
+pub trait IntoOwnedItem {
+    type Owned;
+
+    fn into_owned_item(self) -> Self::Owned;
+}
+
+impl<T: ToOwned + ?Sized> IntoOwnedItem for &T {
+    type Owned = T::Owned;
+
+    fn into_owned_item(self) -> Self::Owned {
+        self.to_owned()
+    }
+}
+
+// Owned copy of a lent item, taken by reference so the item can still be returned
+// -- HistoryStream needs this: IntoOwnedItem consumes the item, which would leave
+//    nothing to hand back to the caller.
+pub trait ToOwnedItem {
+    type Owned;
+
+    fn to_owned_item(&self) -> Self::Owned;
+}
+
+impl<T: ToOwned + ?Sized> ToOwnedItem for &T {
+    type Owned = T::Owned;
+
+    fn to_owned_item(&self) -> Self::Owned {
+        (**self).to_owned()
+    }
+}
+
+// Adapter combinators for any Stream
+// -- Every adapter is itself a Stream, so they chain like Iterator adapters
+//    while still lending items that borrow from the underlying stream.
+pub trait StreamExt: Stream + Sized {
+    fn map<F>(self, f: F) -> Map<Self, F>
+    where
+        F: for<'a> StreamMapFn<Self::Item<'a>>,
+    {
+        Map { stream: self, f }
+    }
+
+    /// Map every item to an owned value that no longer borrows the stream
+    fn map_item<U, F>(self, f: F) -> MapStream<Self, F>
+    where
+        F: for<'a> FnMut(Self::Item<'a>) -> U,
+    {
+        MapStream { stream: self, f }
+    }
+
+    /// Map every `&I` item to a `&T` that borrows for the same lifetime, e.g. a word to
+    /// a trimmed sub-slice of it
+    fn map_borrow<I, T, F>(self, f: F) -> MapBorrowStream<Self, F, T>
+    where
+        I: ?Sized + 'static,
+        T: ?Sized,
+        Self: for<'a> Stream<Item<'a> = &'a I> + 'static,
+        F: for<'a> FnMut(&'a I) -> &'a T,
+    {
+        MapBorrowStream { stream: self, f, _output: PhantomData }
+    }
+
+    /// Yield only the items matching `predicate`. Rejected items are looked at with peek
+    /// before being consumed, so the stream has to be a PeekableStream.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: PeekableStream,
+        P: for<'a> FnMut(&Self::Item<'a>) -> bool,
+    {
+        Filter { stream: self, predicate }
+    }
+
+    /// Call `f` on every item as it passes through, e.g. to debug a pipeline
+    fn inspect<F>(self, f: F) -> InspectStream<Self, F>
+    where
+        F: for<'a> FnMut(&Self::Item<'a>),
+    {
+        InspectStream { stream: self, f }
+    }
+
+    fn take(self, n: usize) -> Take<Self> {
+        Take { stream: self, n, remaining: n }
+    }
+
+    fn skip(self, n: usize) -> Skip<Self> {
+        Skip { stream: self, n, skipped: false }
+    }
+
+    /// Yield the first item, then every `n`-th item after it. Fails when `n` is 0.
+    fn step_by(self, n: usize) -> Result<StepBy<Self>, &'static str> {
+        if n == 0 {
+            return Err("Step must be non-zero");
+        }
+        Ok(StepBy { stream: self, step: n, first_taken: false })
+    }
+
+    /// Yield items until the first one failing `predicate`; that item is consumed
+    fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P>
+    where
+        P: for<'a> FnMut(&Self::Item<'a>) -> bool,
+    {
+        TakeWhile { stream: self, predicate, done: false }
+    }
+
+    /// Drop the leading run of items matching `predicate`, then yield everything.
+    /// Like filter, it judges items through peek, so the stream has to be a PeekableStream.
+    fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P>
+    where
+        Self: PeekableStream,
+        P: for<'a> FnMut(&Self::Item<'a>) -> bool,
+    {
+        SkipWhile { stream: self, predicate, skipping: true }
+    }
+
+    /// Thread `initial` state through `f`, yielding its outputs until it returns None
+    fn scan<St, O, F>(self, initial: St, f: F) -> Scan<Self, St, F, O>
+    where
+        St: Clone,
+        F: for<'a> FnMut(&mut St, Self::Item<'a>) -> Option<O>,
+    {
+        Scan { stream: self, state: initial.clone(), initial, f, done: false, _output: PhantomData }
+    }
+
+    /// Once the stream returns None, keep returning None until reset_position
+    fn fuse(self) -> Fuse<Self> {
+        Fuse { stream: self, exhausted: false }
+    }
+
+    /// Convert every lent item with `f`, giving an object-safe DynStream<T>
+    fn owned<T, F>(self, f: F) -> Owned<Self, F>
+    where
+        F: for<'a> FnMut(Self::Item<'a>) -> T,
+    {
+        Owned { stream: self, f }
+    }
+
+    /// Bridge to a plain Iterator by converting every lent item with `f`
+    fn iter_owned<T, F>(self, f: F) -> IntoIter<Self, F>
+    where
+        F: for<'a> FnMut(Self::Item<'a>) -> T,
+    {
+        IntoIter { stream: self, f }
+    }
+
+    /// Bridge to a plain Iterator for streams lending `&T` with T: Copy
+    fn copied<T>(self) -> Copied<Self>
+    where
+        T: Copy,
+        for<'a> Self::Item<'a>: IntoOwnedItem<Owned = T>,
+    {
+        Copied { stream: self }
+    }
+
+    /// Collapse runs of equal consecutive items into owned `(item, count)` pairs
+    fn rle<O>(self) -> Rle<Self, O>
+    where
+        O: PartialEq,
+        for<'a> Self::Item<'a>: ToOwnedItem<Owned = O>,
+    {
+        Rle { stream: self, pending: None }
+    }
+
+    /// Pair every item with its index, counting items (not positions) from 0
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate { stream: self, index: 0 }
+    }
+
+    /// Walk a bidirectional stream from its last item back to its first
+    fn rev(mut self) -> Rev<Self>
+    where
+        Self: BidiStream,
+    {
+        self.seek_end();
+        Rev { stream: self }
+    }
+
+    /// Pair items from two streams, ending as soon as either one ends
+    fn zip<B: Stream>(self, other: B) -> Zip<Self, B> {
+        Zip { first: self, second: other }
+    }
+
+    /// Yield every item of this stream, then every item of `other`
+    fn chain(self, other: Self) -> Chain<Self> {
+        Chain { first: self, second: other, first_done: false }
+    }
+
+    /// View this stream as a TryStream whose error can never occur
+    fn into_try_stream(self) -> IntoTryStream<Self> {
+        IntoTryStream { stream: self }
+    }
+
+    /// Alternate between this stream and `other`, starting with this one. Once either
+    /// runs out, the rest of the other follows.
+    fn interleave(self, other: Self) -> Interleave<Self> {
+        Interleave { first: self, second: other, second_turn: false }
+    }
+
+    /// Group items into non-overlapping chunks of `n`, converting each item with `f`.
+    /// The final partial chunk is yielded unless disabled with `keep_partial(false)`.
+    /// Fails when `n` is 0.
+    fn chunks<O, F>(self, n: usize, f: F) -> Result<Chunks<Self, F, O>, &'static str>
+    where
+        F: for<'a> FnMut(Self::Item<'a>) -> O,
+    {
+        if n == 0 {
+            return Err("Chunk size must be non-zero");
+        }
+        Ok(Chunks { stream: self, f, n, keep_partial: true, buffer: Vec::with_capacity(n) })
+    }
+
+    /// Overlapping windows of `n` consecutive items, converting each item with `f`.
+    /// A stream shorter than `n` yields no window. Fails when `n` is 0.
+    fn windows<O, F>(self, n: usize, f: F) -> Result<Windows<Self, F, O>, &'static str>
+    where
+        F: for<'a> FnMut(Self::Item<'a>) -> O,
+    {
+        if n == 0 {
+            return Err("Window size must be non-zero");
+        }
+        Ok(Windows {
+            stream: self,
+            f,
+            n,
+            buffer: VecDeque::with_capacity(n),
+            positions: VecDeque::with_capacity(n),
+        })
+    }
+
+    /// Advance until an item matches `predicate` and return an owned copy of it.
+    /// Without a match the stream is left exhausted.
+    fn find<O, P>(&mut self, mut predicate: P) -> Option<O>
+    where
+        P: for<'a> FnMut(&Self::Item<'a>) -> bool,
+        for<'a> Self::Item<'a>: IntoOwnedItem<Owned = O>,
+    {
+        while let Some(item) = self.next() {
+            if predicate(&item) {
+                return Some(item.into_owned_item());
+            }
+        }
+        None
+    }
+
+    /// Advance until an item matches `predicate` and return how many items were read
+    /// before it (counting from the current position, like Iterator::position).
+    /// Qualify the call (StreamExt::position) when SeekableStream is also in scope.
+    fn position<P>(&mut self, mut predicate: P) -> Option<usize>
+    where
+        P: for<'a> FnMut(&Self::Item<'a>) -> bool,
+    {
+        let mut index = 0;
+        while let Some(item) = self.next() {
+            if predicate(&item) {
+                return Some(index);
+            }
+            index += 1;
+        }
+        None
+    }
+
+    // Terminal operations
+    // -- A lending stream cannot implement Iterator, so these drive it to completion
+    //    directly. Each borrowed item must be turned into an owned value (or dropped)
+    //    before the next one is requested.
+    // -- Like map and filter, the `for<'a>` closure bounds currently imply Self: 'static,
+    //    so streams over borrowed data (SliceStream, LineStream) have to be driven by hand.
+
+    /// Convert every item with `f` and collect the owned results.
+    /// The buffer is preallocated from the lower bound of size_hint.
+    fn collect_owned<C, O, F>(mut self, mut f: F) -> C
+    where
+        C: FromIterator<O>,
+        F: for<'a> FnMut(Self::Item<'a>) -> O,
+    {
+        let mut owned = Vec::with_capacity(self.size_hint().0);
+        while let Some(item) = self.next() {
+            owned.push(f(item));
+        }
+        owned.into_iter().collect()
+    }
+
+    /// Turn every item into a key/value pair with `f` and collect them into a HashMap;
+    /// for duplicate keys the last one wins
+    /// (KeyValueStream's inherent collect_map takes precedence for that stream)
+    fn collect_map<K, V, F>(mut self, mut f: F) -> HashMap<K, V>
+    where
+        K: Eq + Hash,
+        F: for<'a> FnMut(Self::Item<'a>) -> (K, V),
+    {
+        let mut map = HashMap::with_capacity(self.size_hint().0);
+        while let Some(item) = self.next() {
+            let (key, value) = f(item);
+            map.insert(key, value);
+        }
+        map
+    }
+
+    /// Number of remaining items, consuming the stream
+    fn count(mut self) -> usize {
+        let mut count = 0;
+        while self.next().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Call `f` on every remaining item
+    fn for_each<F>(mut self, mut f: F)
+    where
+        F: for<'a> FnMut(Self::Item<'a>),
+    {
+        while let Some(item) = self.next() {
+            f(item);
+        }
+    }
+
+    /// Fold every remaining item into an accumulator
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: for<'a> FnMut(B, Self::Item<'a>) -> B,
+    {
+        let mut accumulator = init;
+        while let Some(item) = self.next() {
+            accumulator = f(accumulator, item);
+        }
+        accumulator
+    }
+}
+
+impl<S: Stream> StreamExt for S {}
+
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Map<S, F> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F> Stream for Map<S, F>
+where
+    S: Stream,
+    F: for<'a> StreamMapFn<S::Item<'a>>,
+{
+    type Item<'a> = <F as StreamMapFn<S::Item<'a>>>::Output
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let f = &mut self.f;
+        self.stream.next().map(|item| f.call_item(item))
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let f = &mut self.f;
+        self.stream.next_with_position().map(|(item, position)| (f.call_item(item), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+// The peeked item is mapped too, so f runs on every peek as well as on next
+impl<S, F> PeekableStream for Map<S, F>
+where
+    S: PeekableStream,
+    F: for<'a> StreamMapFn<S::Item<'a>>,
+{
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let f = &mut self.f;
+        self.stream.peek().map(|item| f.call_item(item))
+    }
+}
+
+// Map with an owned output
+// -- The closure's return type U is one type for every lending lifetime 'a, so it
+//    cannot mention 'a: the output must stop borrowing the stream (lengths, Strings).
+//    Item<'a> = U simply ignores the lifetime.
+pub struct MapStream<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> MapStream<S, F> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F, U> Stream for MapStream<S, F>
+where
+    S: Stream,
+    F: for<'a> FnMut(S::Item<'a>) -> U,
+{
+    type Item<'a> = U
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.stream.next().map(&mut self.f)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let f = &mut self.f;
+        self.stream.next_with_position().map(|(item, position)| (f(item), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+// As with Map, f also runs on every peek
+impl<S, F, U> PeekableStream for MapStream<S, F>
+where
+    S: PeekableStream,
+    F: for<'a> FnMut(S::Item<'a>) -> U,
+{
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.stream.peek().map(&mut self.f)
+    }
+}
+
+// Map whose output borrows for the lending lifetime
+// -- MapStream's U is one type for every 'a, so it cannot be a borrow of the item.
+//    Writing `for<'a> FnMut(S::Item<'a>) -> &'a T` does not work either: the compiler
+//    rejects an output lifetime that only appears inside a projection (E0582).
+// -- So the input is pinned to the concrete shape `&'a I` (S: for<'a> Stream<Item<'a> =
+//    &'a I>), which puts 'a back in the closure's argument. That bound also lets a
+//    closure like `|w| w.trim()` have its signature inferred. Being an HRTB over a GAT,
+//    it implies S: 'static, so the bounds spell that out.
+// -- The adapter's own GAT then rebuilds the lifetime: Item<'a> = &'a T. T appears in
+//    the type (via PhantomData) so that `Self: 'a` implies T: 'a.
+pub struct MapBorrowStream<S, F, T: ?Sized> {
+    stream: S,
+    f: F,
+    _output: PhantomData<fn() -> *const T>,
+}
+
+impl<S, F, T: ?Sized> MapBorrowStream<S, F, T> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F, I, T> Stream for MapBorrowStream<S, F, T>
+where
+    S: for<'a> Stream<Item<'a> = &'a I> + 'static,
+    I: ?Sized + 'static,
+    T: ?Sized,
+    F: for<'a> FnMut(&'a I) -> &'a T,
+{
+    type Item<'a> = &'a T
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.stream.next().map(&mut self.f)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let f = &mut self.f;
+        self.stream.next_with_position().map(|(item, position)| (f(item), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pub struct Filter<S, P> {
+    stream: S,
+    predicate: P,
+}
+
+impl<S, P> Filter<S, P> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+// Each item is judged through peek and only consumed by next once it has been accepted,
+// so the returned item is the only borrow of the stream that outlives the call.
+// (Returning the item from a next() inside a loop that may also drop it does not pass
+// the current borrow checker.)
+impl<S, P> Filter<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    // Consume items until the next one matches the predicate or the stream ends
+    fn skip_rejected(&mut self) {
+        loop {
+            let accepted = match self.stream.peek() {
+                Some(item) => (self.predicate)(&item),
+                None => return,
+            };
+            if accepted {
+                return;
+            }
+            self.stream.next();
+        }
+    }
+}
+
+impl<S, P> Stream for Filter<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_rejected();
+        self.stream.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.skip_rejected();
+        self.stream.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self
+    }
+}
+
+impl<S, P> PeekableStream for Filter<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_rejected();
+        self.stream.peek()
+    }
+}
+
+// Pass-through adapter that shows every item to a closure, like Iterator::inspect
+pub struct InspectStream<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> InspectStream<S, F> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F> Stream for InspectStream<S, F>
+where
+    S: Stream,
+    F: for<'a> FnMut(&S::Item<'a>),
+{
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let item = self.stream.next()?;
+        (self.f)(&item);
+        Some(item)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (item, position) = self.stream.next_with_position()?;
+        (self.f)(&item);
+        Some((item, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+// Peeking does not call f; the item is shown to f once, when next consumes it
+impl<S, F> PeekableStream for InspectStream<S, F>
+where
+    S: PeekableStream,
+    F: for<'a> FnMut(&S::Item<'a>),
+{
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.stream.peek()
+    }
+}
+
+pub struct Take<S> {
+    stream: S,
+    n: usize,
+    remaining: usize,
+}
+
+impl<S> Take<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Stream> Stream for Take<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.stream.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.stream.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.remaining = self.n;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        let upper = upper.map_or(self.remaining, |upper| upper.min(self.remaining));
+        (lower.min(self.remaining), Some(upper))
+    }
+}
+
+impl<S: PeekableStream> PeekableStream for Take<S> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.stream.peek()
+    }
+}
+
+pub struct Skip<S> {
+    stream: S,
+    n: usize,
+    skipped: bool,
+}
+
+impl<S: Stream> Skip<S> {
+    fn skip_prefix(&mut self) {
+        if !self.skipped {
+            self.skipped = true;
+            for _ in 0..self.n {
+                if self.stream.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<S> Skip<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Stream> Stream for Skip<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_prefix();
+        self.stream.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.skip_prefix();
+        self.stream.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.skipped = false;
+        self
+    }
+}
+
+impl<S: PeekableStream> PeekableStream for Skip<S> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_prefix();
+        self.stream.peek()
+    }
+}
+
+// Sampling adapter: the first item, then every n-th one
+// -- The n - 1 items in between are pulled from the underlying stream and dropped
+//    right away, so positions are those of the sampled items.
+pub struct StepBy<S> {
+    stream: S,
+    step: usize,
+    first_taken: bool,
+}
+
+impl<S: Stream> StepBy<S> {
+    fn skip_gap(&mut self) {
+        if !self.first_taken {
+            self.first_taken = true;
+            return;
+        }
+        for _ in 1..self.step {
+            if self.stream.next().is_none() {
+                break;
+            }
+        }
+    }
+}
+
+impl<S> StepBy<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Stream> Stream for StepBy<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_gap();
+        self.stream.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.skip_gap();
+        self.stream.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.first_taken = false;
+        self
+    }
+}
+
+pub struct TakeWhile<S, P> {
+    stream: S,
+    predicate: P,
+    done: bool,
+}
+
+impl<S, P> TakeWhile<S, P> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, P> Stream for TakeWhile<S, P>
+where
+    S: Stream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(item, _)| item)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if self.done {
+            return None;
+        }
+        let (item, position) = self.stream.next_with_position()?;
+        if (self.predicate)(&item) {
+            Some((item, position))
+        } else {
+            self.done = true;
+            None
+        }
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.done = false;
+        self
+    }
+}
+
+pub struct SkipWhile<S, P> {
+    stream: S,
+    predicate: P,
+    skipping: bool,
+}
+
+impl<S, P> SkipWhile<S, P> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+// Leading items are judged through peek, the same way Filter does it
+impl<S, P> SkipWhile<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    // Consume the leading run of matching items; afterwards this does nothing
+    fn skip_leading(&mut self) {
+        while self.skipping {
+            let matches = match self.stream.peek() {
+                Some(item) => (self.predicate)(&item),
+                None => return,
+            };
+            if !matches {
+                self.skipping = false;
+                return;
+            }
+            self.stream.next();
+        }
+    }
+}
+
+impl<S, P> Stream for SkipWhile<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_leading();
+        self.stream.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.skip_leading();
+        self.stream.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.skipping = true;
+        self
+    }
+}
+
+impl<S, P> PeekableStream for SkipWhile<S, P>
+where
+    S: PeekableStream,
+    P: for<'a> FnMut(&S::Item<'a>) -> bool,
+{
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.skip_leading();
+        self.stream.peek()
+    }
+}
+
+// Stateful map with early termination
+// -- Outputs are owned (O), while the state lives in the adapter and is handed to
+//    f by &mut on every item. reset_position restores a clone of the initial state.
+pub struct Scan<S, St, F, O> {
+    stream: S,
+    initial: St,
+    state: St,
+    f: F,
+    done: bool,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<S, St, F, O> Scan<S, St, F, O> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// The current state
+    pub fn state(&self) -> &St {
+        &self.state
+    }
+}
+
+impl<S, St, F, O> Stream for Scan<S, St, F, O>
+where
+    S: Stream,
+    St: Clone,
+    F: for<'a> FnMut(&mut St, S::Item<'a>) -> Option<O>,
+{
+    type Item<'a> = O
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(output, _)| output)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if self.done {
+            return None;
+        }
+        let (item, position) = self.stream.next_with_position()?;
+        match (self.f)(&mut self.state, item) {
+            Some(output) => Some((output, position)),
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.state = self.initial.clone();
+        self.done = false;
+        self
+    }
+}
+
+// Exhaustion guard
+// -- StringStream and IntStream already keep returning None past the end, but a stream
+//    whose source grows (e.g. IntStream.data pushed to after exhaustion) would resume.
+//    Fuse makes "None means done" a guarantee for any stream.
+pub struct Fuse<S> {
+    stream: S,
+    exhausted: bool,
+}
+
+impl<S> Fuse<S> {
+    /// Whether the underlying stream has returned None
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Mutable access to the underlying stream; fusing still applies afterwards
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: Stream> Stream for Fuse<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if self.exhausted {
+            return None;
+        }
+        let item = self.stream.next();
+        self.exhausted = item.is_none();
+        item
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if self.exhausted {
+            return None;
+        }
+        let item = self.stream.next_with_position();
+        self.exhausted = item.is_none();
+        item
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.exhausted = false;
+        self
+    }
+}
+
+// Peeking at the end fuses too, so a later next() cannot disagree with it
+impl<S: PeekableStream> PeekableStream for Fuse<S> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if self.exhausted {
+            return None;
+        }
+        let item = self.stream.peek();
+        self.exhausted = item.is_none();
+        item
+    }
+}
+
+// Look-behind buffer over the last K yielded items
+// -- Lent items cannot outlive the next call, so the adapter keeps owned copies (O) in a
+//    buffer whose capacity is fixed by the const parameter K. Oldest items are evicted
+//    first, and history() lists the survivors from oldest to newest.
+// -- Like Copied, the `for<'a>` bound currently needs streams that own their data
+//    (S: 'static).
+pub struct HistoryStream<S, O, const K: usize> {
+    stream: S,
+    history: Vec<O>,
+}
+
+impl<S, O, const K: usize> HistoryStream<S, O, K> {
+    pub fn new(stream: S) -> Self {
+        HistoryStream { stream, history: Vec::with_capacity(K) }
+    }
+
+    /// Owned copies of the last (up to) K items, oldest first
+    pub fn history(&self) -> &[O] {
+        &self.history
+    }
+
+    /// Owned copy of the most recently yielded item
+    pub fn last(&self) -> Option<&O> {
+        self.history.last()
+    }
+
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    // Takes the buffer rather than &mut self, so it can run while an item still
+    // borrows self.stream
+    fn remember(history: &mut Vec<O>, item: O) {
+        if K == 0 {
+            return;
+        }
+        if history.len() == K {
+            history.remove(0);
+        }
+        history.push(item);
+    }
+}
+
+impl<S, O, const K: usize> Stream for HistoryStream<S, O, K>
+where
+    S: Stream,
+    for<'a> S::Item<'a>: ToOwnedItem<Owned = O>,
+{
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let item = self.stream.next()?;
+        Self::remember(&mut self.history, item.to_owned_item());
+        Some(item)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (item, position) = self.stream.next_with_position()?;
+        Self::remember(&mut self.history, item.to_owned_item());
+        Some((item, position))
+    }
+
+    /// Restart the underlying stream and forget the history
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.history.clear();
+        self
+    }
+}
+
+// Run-length encoding: each run of equal consecutive items becomes (item, count)
+// -- A run only ends once the first item of the next run has been read, and that item
+//    cannot be lent across next() calls, so an owned copy of it (with its position) is
+//    buffered in `pending`. Items are compared through their owned copies.
+// -- Positions are those of the first item of each run. Like HistoryStream, the
+//    `for<'a>` bound currently needs streams that own their data (S: 'static).
+pub struct Rle<S, O> {
+    stream: S,
+    pending: Option<(O, usize)>,
+}
+
+impl<S, O> Rle<S, O> {
+    /// The underlying stream; it is one item ahead while a look-ahead item is buffered
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, O> Rle<S, O>
+where
+    S: Stream,
+    O: PartialEq,
+    for<'a> S::Item<'a>: ToOwnedItem<Owned = O>,
+{
+    fn next_run(&mut self) -> Option<((O, usize), usize)> {
+        let (current, position) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let (item, position) = self.stream.next_with_position()?;
+                (item.to_owned_item(), position)
+            }
+        };
+        let mut count = 1;
+        while let Some((item, next_position)) = self.stream.next_with_position() {
+            let item = item.to_owned_item();
+            if item != current {
+                self.pending = Some((item, next_position));
+                break;
+            }
+            count += 1;
+        }
+        Some(((current, count), position))
+    }
+}
+
+impl<S, O> Stream for Rle<S, O>
+where
+    S: Stream,
+    O: PartialEq,
+    for<'a> S::Item<'a>: ToOwnedItem<Owned = O>,
+{
+    type Item<'a> = (O, usize)
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_run().map(|(run, _)| run)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.next_run()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.pending = None;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        let buffered = usize::from(self.pending.is_some());
+        let lower = usize::from(lower + buffered > 0);
+        (lower, upper.and_then(|upper| upper.checked_add(buffered)))
+    }
+}
+
+// Item counter; positions still come from the underlying stream
+pub struct Enumerate<S> {
+    stream: S,
+    index: usize,
+}
+
+impl<S> Enumerate<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Stream> Stream for Enumerate<S> {
+    type Item<'a> = (usize, S::Item<'a>)
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let item = self.stream.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some((index, item))
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (item, position) = self.stream.next_with_position()?;
+        let index = self.index;
+        self.index += 1;
+        Some(((index, item), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.index = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S: PeekableStream> PeekableStream for Enumerate<S> {
+    fn peek<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let index = self.index;
+        self.stream.peek().map(|item| (index, item))
+    }
+}
+
+// Reversed view of a BidiStream
+// -- next delegates to prev, and positions are the original indices, so they count
+//    down. reset_position moves back to the end rather than to index 0.
+pub struct Rev<S> {
+    stream: S,
+}
+
+impl<S> Rev<S> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: BidiStream> Stream for Rev<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.stream.prev()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.stream.prev_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.seek_end();
+        self
+    }
+}
+
+// Both items are lent for the same 'a: the adapter borrows each inner stream mutably
+// through disjoint fields, so the pair can hold two live borrows at once.
+// -- Positions come from the first stream. If the first stream yields but the second
+//    is exhausted, that first item is consumed and dropped (as with Iterator::zip).
+pub struct Zip<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Zip<A, B> {
+    /// Both underlying streams, e.g. to inspect their positions
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+}
+
+impl<A: Stream, B: Stream> Stream for Zip<A, B> {
+    type Item<'a> = (A::Item<'a>, B::Item<'a>)
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let first = self.first.next()?;
+        let second = self.second.next()?;
+        Some((first, second))
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (first, position) = self.first.next_with_position()?;
+        let second = self.second.next()?;
+        Some(((first, second), position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.first.reset_position();
+        self.second.reset_position();
+        self
+    }
+}
+
+// Exhausts the first stream, then continues with the second
+// -- Both halves are the same stream type, which is what makes their Item GATs equal for
+//    every 'a. The general bound `B: for<'a> Stream<Item<'a> = A::Item<'a>>` would force
+//    B: 'static (a current limitation of HRTBs over GATs), ruling out borrowed streams.
+// -- Positions are reported as-is by whichever stream produced the item, so they
+//    restart from the second stream's own origin once the first one is exhausted.
+pub struct Chain<S> {
+    first: S,
+    second: S,
+    first_done: bool,
+}
+
+impl<S> Chain<S> {
+    /// Both underlying streams, e.g. to inspect their positions
+    pub fn get_ref(&self) -> (&S, &S) {
+        (&self.first, &self.second)
+    }
+}
+
+impl<S: Stream> Stream for Chain<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        if !self.first_done {
+            if let Some(item) = self.first.next() {
+                return Some(item);
+            }
+            self.first_done = true;
+        }
+        self.second.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        if !self.first_done {
+            if let Some(item) = self.first.next_with_position() {
+                return Some(item);
+            }
+            self.first_done = true;
+        }
+        self.second.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.first.reset_position();
+        self.second.reset_position();
+        self.first_done = false;
+        self
+    }
+}
+
+// Sum of two size hints, saturating and giving up on the upper bound on overflow
+fn add_size_hints(a: (usize, Option<usize>), b: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let upper = match (a.1, b.1) {
+        (Some(x), Some(y)) => x.checked_add(y),
+        _ => None,
+    };
+    (a.0.saturating_add(b.0), upper)
+}
+
+// Alternates between two streams of the same type (see Chain for why the type is shared)
+// -- The turn flips on every call. When the stream whose turn it is has run out, the
+//    other one is asked instead, so the longer stream's tail comes out in order.
+pub struct Interleave<S> {
+    first: S,
+    second: S,
+    second_turn: bool,
+}
+
+impl<S> Interleave<S> {
+    /// Both underlying streams, e.g. to inspect their positions
+    pub fn get_ref(&self) -> (&S, &S) {
+        (&self.first, &self.second)
+    }
+
+    // Flip the turn and return the streams in the order to ask them. The two
+    // references are disjoint fields, so an item lent by one does not lock the other.
+    fn take_turn(&mut self) -> (&mut S, &mut S) {
+        self.second_turn = !self.second_turn;
+        if self.second_turn {
+            (&mut self.first, &mut self.second)
+        } else {
+            (&mut self.second, &mut self.first)
+        }
+    }
+}
+
+impl<S: Stream> Stream for Interleave<S> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let (current, other) = self.take_turn();
+        if let Some(item) = current.next() {
+            return Some(item);
+        }
+        other.next()
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (current, other) = self.take_turn();
+        if let Some(item) = current.next_with_position() {
+            return Some(item);
+        }
+        other.next_with_position()
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.first.reset_position();
+        self.second.reset_position();
+        self.second_turn = false;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        add_size_hints(self.first.size_hint(), self.second.size_hint())
+    }
+}
+
+// Round-robin merge of K streams held in an array
+// -- Each call starts with the stream after the one that yielded last and moves on past
+//    exhausted members, so a full cycle of None means every stream is done.
+// -- Splitting the array with split_at_mut hands out one &mut per element, which lets
+//    an item be returned from inside the loop; each stream is only borrowed once.
+pub struct RoundRobin<S, const K: usize> {
+    streams: [S; K],
+    turn: usize,
+}
+
+impl<S, const K: usize> RoundRobin<S, K> {
+    pub fn new(streams: [S; K]) -> Self {
+        RoundRobin { streams, turn: 0 }
+    }
+
+    /// The underlying streams, e.g. to inspect their positions
+    pub fn get_ref(&self) -> &[S; K] {
+        &self.streams
+    }
+}
+
+impl<S: Stream, const K: usize> Stream for RoundRobin<S, K> {
+    type Item<'a> = S::Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(item, _)| item)
+    }
+
+    /// Positions come from whichever member produced the item
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let (before, after) = self.streams.split_at_mut(self.turn);
+        let order = after.iter_mut().zip(self.turn..).chain(before.iter_mut().zip(0..));
+        for (stream, index) in order {
+            if let Some(item) = stream.next_with_position() {
+                self.turn = (index + 1) % K;
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        for stream in self.streams.iter_mut() {
+            stream.reset_position();
+        }
+        self.turn = 0;
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.streams.iter().map(Stream::size_hint).fold((0, Some(0)), add_size_hints)
+    }
+}
+
+// Non-overlapping groups, lent as a slice of the adapter's own buffer
+// -- A lending stream's items cannot be held across next() calls, so each one is
+//    converted to an owned O first. Item<'a> = &'a [O] then borrows the buffer,
+//    which is refilled on the following call. Positions are those of the first item.
+pub struct Chunks<S, F, O> {
+    stream: S,
+    f: F,
+    n: usize,
+    keep_partial: bool,
+    buffer: Vec<O>,
+}
+
+impl<S, F, O> Chunks<S, F, O> {
+    /// Yield (true, the default) or drop (false) a final chunk shorter than n
+    pub fn keep_partial(mut self, keep_partial: bool) -> Self {
+        self.keep_partial = keep_partial;
+        self
+    }
+
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F, O> Stream for Chunks<S, F, O>
+where
+    S: Stream,
+    F: for<'a> FnMut(S::Item<'a>) -> O,
+{
+    type Item<'a> = &'a [O]
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(chunk, _)| chunk)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        self.buffer.clear();
+        let mut start = None;
+        while self.buffer.len() < self.n {
+            let Some((item, position)) = self.stream.next_with_position() else {
+                break;
+            };
+            start.get_or_insert(position);
+            self.buffer.push((self.f)(item));
+        }
+        let partial = self.buffer.len() < self.n;
+        if partial && !self.keep_partial {
+            return None;
+        }
+        start.map(|start| (self.buffer.as_slice(), start))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.buffer.clear();
+        self
+    }
+}
+
+// Overlapping windows, lent as a slice of a sliding buffer
+// -- Each call reads one new item, drops the oldest, and lends the n most recent
+//    items in order. Positions are those of the first item in the window.
+pub struct Windows<S, F, O> {
+    stream: S,
+    f: F,
+    n: usize,
+    buffer: VecDeque<O>,
+    positions: VecDeque<usize>,
+}
+
+impl<S, F, O> Windows<S, F, O> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F, O> Stream for Windows<S, F, O>
+where
+    S: Stream,
+    F: for<'a> FnMut(S::Item<'a>) -> O,
+{
+    type Item<'a> = &'a [O]
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(window, _)| window)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        // The first window needs n items, every later one needs a single new item
+        let needed = if self.buffer.len() < self.n { self.n - self.buffer.len() } else { 1 };
+        for _ in 0..needed {
+            let (item, position) = self.stream.next_with_position()?;
+            if self.buffer.len() == self.n {
+                self.buffer.pop_front();
+                self.positions.pop_front();
+            }
+            self.buffer.push_back((self.f)(item));
+            self.positions.push_back(position);
+        }
+        let start = *self.positions.front()?;
+        Some((self.buffer.make_contiguous(), start))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.stream.reset_position();
+        self.buffer.clear();
+        self.positions.clear();
+        self
+    }
+}
+
+// Object-safe companion to Stream
+// -- A trait with a generic associated type cannot be made into a trait object, so
+//    Box<dyn Stream> does not exist. DynStream fixes the item type to an owned T,
+//    which lets different stream types share one Vec<Box<dyn DynStream<T>>>.
+pub trait DynStream<T> {
+    fn next_owned(&mut self) -> Option<T>;
+}
+
+// Adapter turning any Stream into a DynStream<T> by converting each lent item
+pub struct Owned<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Owned<S, F> {
+    /// The underlying stream, e.g. to inspect its position
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S, F, T> DynStream<T> for Owned<S, F>
+where
+    S: Stream,
+    F: for<'a> FnMut(S::Item<'a>) -> T,
+{
+    fn next_owned(&mut self) -> Option<T> {
+        let f = &mut self.f;
+        self.stream.next().map(f)
+    }
+}
+
+// Stream-to-Iterator bridges
+// -- An Iterator's items may all be alive at once, so each lent item has to stop
+//    borrowing the stream first: IntoIter converts it with a closure, Copied copies
+//    the referenced value. Like the other closure-based adapters, the `for<'a>`
+//    bounds currently need streams that own their data (Self: 'static).
+pub struct IntoIter<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F, T> Iterator for IntoIter<S, F>
+where
+    S: Stream,
+    F: for<'a> FnMut(S::Item<'a>) -> T,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let f = &mut self.f;
+        self.stream.next().map(f)
+    }
+}
+
+pub struct Copied<S> {
+    stream: S,
+}
+
+impl<S, T> Iterator for Copied<S>
+where
+    S: Stream,
+    T: Copy,
+    for<'a> S::Item<'a>: IntoOwnedItem<Owned = T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // For &T with T: Copy, to_owned is a plain copy
+        self.stream.next().map(IntoOwnedItem::into_owned_item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_stream(data: &str) -> StringStream {
+        StringStream::new(data)
+    }
+
+    #[test]
+    fn test_skip_take_map_chain() {
+        let mut chained = string_stream("a bb ccc dddd").skip(1).take(2).map(str::len);
+        let mut lengths = Vec::new();
+        while let Some(len) = chained.next() {
+            lengths.push(len);
+        }
+
+        assert_eq!(lengths, vec![2, 3]);
+        assert_eq!(chained.next(), None);
+        // "a " skipped, "bb " and "ccc " consumed, "dddd" still pending
+        let inner = chained.get_ref().get_ref().get_ref();
+        assert_eq!(inner.position, 9);
+        assert_eq!(&inner.data[inner.position..], "dddd");
+    }
+
+    #[test]
+    fn test_map_preserves_borrowed_items() {
+        // A fn item (unlike a closure) can return a borrow of its argument for every lifetime
+        fn strip_comma(word: &str) -> &str {
+            word.trim_end_matches(',')
+        }
+
+        let mut trimmed = string_stream("x, y, z").map(strip_comma);
+        let mut words = Vec::new();
+        while let Some(word) = trimmed.next() {
+            words.push(word.to_string());
+        }
+        assert_eq!(words, vec!["x", "y", "z"]);
+    }
+
+    #[test]
+    fn test_filter_and_positions() {
+        let mut long_words = string_stream("a bb c dd").filter(|word: &&str| word.len() > 1);
+        assert_eq!(long_words.next_with_position(), Some(("bb", 2)));
+        assert_eq!(long_words.next_with_position(), Some(("dd", 7)));
+        assert_eq!(long_words.next_with_position(), None);
+
+        long_words.reset_position();
+        assert_eq!(long_words.next(), Some("bb"));
+    }
+
+    #[test]
+    fn test_filter_peek_agrees_with_next() {
+        let mut odd = IntStream { data: vec![2, 4, 5, 6, 7], position: 0 }.filter(|n: &&i32| **n % 2 == 1);
+        assert_eq!(odd.peek(), Some(&5));
+        assert_eq!(odd.peek(), Some(&5));
+        assert_eq!(odd.next_with_position(), Some((&5, 2)));
+        assert_eq!(odd.next(), Some(&7));
+        assert_eq!(odd.peek(), None);
+        assert_eq!(odd.get_ref().position, 5);
+
+        // Filters stack, and filtering works behind other peekable adapters
+        let mut words = string_stream("skip me a bb ccc dddd")
+            .skip(2)
+            .filter(|word: &&str| word.len() > 1)
+            .filter(|word: &&str| word.len() < 4);
+        assert_eq!(words.next(), Some("bb"));
+        assert_eq!(words.next(), Some("ccc"));
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn test_filter_after_take_and_map() {
+        // take counts the rejected items too: only the first four are considered
+        let mut evens = IntStream { data: vec![1, 2, 3, 4, 5, 6], position: 0 }
+            .take(4)
+            .filter(|n: &&i32| **n % 2 == 0);
+        assert_eq!(evens.next_with_position(), Some((&2, 1)));
+        assert_eq!(evens.next(), Some(&4));
+        assert_eq!(evens.next(), None);
+
+        let mut lengths = string_stream("a bb ccc dd")
+            .map_item(|word: &str| word.len())
+            .filter(|len: &usize| *len > 1);
+        assert_eq!(lengths.peek(), Some(2));
+        assert_eq!(lengths.next(), Some(2));
+        assert_eq!(lengths.next(), Some(3));
+        assert_eq!(lengths.next(), Some(2));
+        assert_eq!(lengths.next(), None);
+
+        let mut tens = IntStream { data: vec![1, 2, 3], position: 0 }
+            .map(|n: &i32| n * 10)
+            .enumerate()
+            .filter(|(index, _): &(usize, i32)| *index != 1)
+            .fuse();
+        assert_eq!(tens.next(), Some((0, 10)));
+        assert_eq!(tens.next(), Some((2, 30)));
+        assert_eq!(tens.peek(), None);
+        assert_eq!(tens.next(), None);
+    }
+
+    #[test]
+    fn test_filter_text_streams() {
+        let mut vowels = CharStream::new("héllo").filter(|c: &char| "aeioué".contains(*c));
+        assert_eq!(vowels.next_with_position(), Some(('é', 1)));
+        assert_eq!(vowels.next_with_position(), Some(('o', 5)));
+        assert_eq!(vowels.next(), None);
+
+        let mut comments = LineStream::new("# a\ncode\n# b").filter(|line: &&str| line.starts_with('#'));
+        assert_eq!(comments.next_with_position(), Some(("# a", 0)));
+        assert_eq!(comments.next_with_position(), Some(("# b", 9)));
+        assert_eq!(comments.next(), None);
+
+        let mut reader = ReaderStream::new(&b"keep\ndrop\nkeep too\n"[..]);
+        assert_eq!(reader.peek(), Some("keep"));
+        let mut kept = reader.filter(|line: &&str| line.starts_with("keep"));
+        assert_eq!(kept.next_with_position(), Some(("keep", 0)));
+        assert_eq!(kept.next_with_position(), Some(("keep too", 10)));
+        assert_eq!(kept.next(), None);
+        assert!(kept.get_ref().error().is_none());
+    }
+
+    #[test]
+    fn test_adapters_over_int_stream() {
+        let mut evens = IntStream { data: vec![1, 2, 3, 4, 5, 6], position: 0 }
+            .filter(|n: &&i32| **n % 2 == 0)
+            .map(|n: &i32| n * 10)
+            .take(2);
+        assert_eq!(evens.next(), Some(20));
+        assert_eq!(evens.next(), Some(40));
+        assert_eq!(evens.next(), None);
+
+        evens.reset_position();
+        assert_eq!(evens.next(), Some(20));
+    }
+
+    #[test]
+    fn test_string_stream_peek_interleaved() {
+        let mut stream = string_stream("let x = 1");
+        assert_eq!(stream.peek(), Some("let"));
+        assert_eq!(stream.peek(), Some("let"));
+        assert_eq!(stream.position, 0);
+
+        assert_eq!(stream.next(), Some("let"));
+        assert_eq!(stream.peek(), Some("x"));
+        assert_eq!(stream.position, 4);
+        assert_eq!(stream.next_with_position(), Some(("x", 4)));
+        assert_eq!(stream.next(), Some("="));
+
+        assert_eq!(stream.peek(), Some("1"));
+        assert_eq!(stream.next(), Some("1"));
+        assert_eq!(stream.peek(), None);
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.peek(), None);
+    }
+
+    #[test]
+    fn test_int_stream_peek_interleaved() {
+        let mut stream = IntStream { data: vec![7, 8], position: 0 };
+        assert_eq!(stream.peek(), Some(&7));
+        assert_eq!(stream.next(), Some(&7));
+        assert_eq!(stream.peek(), Some(&8));
+        assert_eq!(stream.peek(), Some(&8));
+        assert_eq!(stream.position, 1);
+        assert_eq!(stream.next(), Some(&8));
+        assert_eq!(stream.peek(), None);
+        assert_eq!(stream.position, 2);
+
+        stream.reset_position();
+        assert_eq!(stream.peek(), Some(&7));
+    }
+
+    #[test]
+    fn test_slice_stream_over_i32() {
+        let numbers = [10, 20, 30];
+        let mut stream = SliceStream::from_slice(&numbers);
+        assert_eq!(stream.remaining(), 3);
+        assert_eq!(stream.next(), Some(&10));
+        assert_eq!(stream.next_with_position(), Some((&20, 1)));
+        assert_eq!(stream.remaining(), 1);
+        assert_eq!(stream.next(), Some(&30));
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.remaining(), 0);
+
+        stream.reset_position();
+        assert_eq!(stream.remaining(), 3);
+    }
+
+    #[test]
+    fn test_slice_stream_over_strings() {
+        let words = vec!["alpha".to_string(), "beta".to_string()];
+        let mut stream = SliceStream::from_slice(&words);
+        let mut lengths = Vec::new();
+        while let Some(word) = stream.next() {
+            lengths.push(word.len());
+        }
+        assert_eq!(lengths, vec![5, 4]);
+    }
+
+    #[test]
+    fn test_slice_stream_empty() {
+        let empty: [u8; 0] = [];
+        let mut stream = SliceStream::from_slice(&empty);
+        assert_eq!(stream.remaining(), 0);
+        assert_eq!(stream.peek(), None);
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_slice_stream_recreated_after_borrow_ends() {
+        let mut data = vec![1, 2, 3];
+        {
+            let mut stream = SliceStream::from_slice(&data);
+            assert_eq!(stream.next(), Some(&1));
+        }
+        // The first borrow has ended, so the data can be mutated and streamed again
+        data.push(4);
+        let mut stream = SliceStream::from_slice(&data);
+        let mut total = 0;
+        while let Some(n) = stream.next() {
+            total += n;
+        }
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn test_line_stream_multi_line_and_crlf() {
+        let text = "first\r\nsecond\n\r\nfourth";
+        let mut lines = LineStream::new(text);
+        assert_eq!(lines.next_with_position(), Some(("first", 0)));
+        assert_eq!(lines.next_with_position(), Some(("second", 7)));
+        assert_eq!(lines.next_with_position(), Some(("", 14)));
+        assert_eq!(lines.next_with_position(), Some(("fourth", 16)));
+        assert_eq!(lines.next(), None);
+
+        // Positions are byte offsets into the source text
+        assert_eq!(&text[16..], "fourth");
+    }
+
+    #[test]
+    fn test_line_stream_skip_empty() {
+        let mut lines = LineStream::new("a\n\n\nb\n").with_skip_empty(true);
+        assert_eq!(lines.next_with_position(), Some(("a", 0)));
+        assert_eq!(lines.next_with_position(), Some(("b", 4)));
+        assert_eq!(lines.next(), None);
+
+        let mut kept = LineStream::new("a\n\nb\n");
+        let first = kept.next();
+        let second = kept.next();
+        // Items borrow the source, so they outlive later calls to next()
+        assert_eq!((first, second, kept.next(), kept.next()), (Some("a"), Some(""), Some("b"), None));
+    }
+
+    #[test]
+    fn test_reader_stream_over_cursor() {
+        let source = "héllo\r\nworld\n\nend";
+        let mut stream = ReaderStream::new(std::io::Cursor::new(source));
+
+        let mut seen = Vec::new();
+        while let Some((line, offset)) = stream.next_with_position() {
+            seen.push((line.to_string(), offset));
+        }
+        assert_eq!(
+            seen,
+            vec![
+                ("héllo".to_string(), 0),
+                ("world".to_string(), 8),
+                ("".to_string(), 14),
+                ("end".to_string(), 15),
+            ]
+        );
+        for (line, offset) in &seen {
+            assert!(source[*offset..].starts_with(line.as_str()));
+        }
+        assert!(stream.error().is_none());
+
+        stream.rewind().unwrap();
+        assert_eq!(stream.next_with_position(), Some(("héllo", 0)));
+    }
+
+    #[test]
+    fn test_reader_stream_without_seek() {
+        // &[u8] is BufRead but not Seek, like stdin or a socket
+        let mut stream = ReaderStream::new(&b"first
+second
+"[..]);
+        assert_eq!(stream.next_with_position(), Some(("first", 0)));
+
+        assert_eq!(stream.next_with_position(), Some(("second", 6)));
+        assert_eq!(stream.next(), None);
+        assert!(stream.error().is_none());
+    }
+
+    #[test]
+    fn test_reader_stream_reset_position_ends_with_error() {
+        let mut stream = ReaderStream::new(std::io::Cursor::new("first\nsecond\n"));
+        assert_eq!(stream.next(), Some("first"));
+
+        // Resetting cannot rewind through Stream, so the stream ends instead of going on
+        stream.reset_position();
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.error().map(|e| e.kind()), Some(std::io::ErrorKind::Unsupported));
+
+        stream.rewind().unwrap();
+        assert!(stream.error().is_none());
+        assert_eq!(stream.next_with_position(), Some(("first", 0)));
+    }
+
+    #[test]
+    fn test_reader_stream_invalid_utf8_ends_with_error() {
+        let bytes: &[u8] = b"ok\n\xff\xfe\n";
+        let mut stream = ReaderStream::new(std::io::Cursor::new(bytes));
+        assert_eq!(stream.next(), Some("ok"));
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.error().map(|e| e.kind()), Some(std::io::ErrorKind::InvalidData));
+    }
+
+    fn collect_tokens(stream: &mut StringStream) -> Vec<(String, usize)> {
+        let mut tokens = Vec::new();
+        while let Some((token, position)) = stream.next_with_position() {
+            tokens.push((token.to_string(), position));
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_string_stream_korean_text() {
+        let mut stream = string_stream("안녕 world 하세요");
+        assert_eq!(
+            collect_tokens(&mut stream),
+            vec![("안녕".to_string(), 0), ("world".to_string(), 7), ("하세요".to_string(), 13)]
+        );
+        assert_eq!(stream.position, stream.data.len());
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_string_stream_whitespace_runs() {
+        let mut stream = string_stream("  leading   multiple\tspaces\n\ttrailing   ");
+        assert_eq!(
+            collect_tokens(&mut stream),
+            vec![
+                ("leading".to_string(), 2),
+                ("multiple".to_string(), 12),
+                ("spaces".to_string(), 21),
+                ("trailing".to_string(), 29),
+            ]
+        );
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_string_stream_empty_and_blank() {
+        assert_eq!(string_stream("").next(), None);
+        assert_eq!(string_stream(" \t\n ").next(), None);
+        assert_eq!(string_stream(" \t\n ").peek(), None);
+    }
+
+    #[test]
+    fn test_string_stream_never_panics_on_bad_position() {
+        let mut stream = string_stream("안녕");
+        stream.position = 1; // inside the first character
+        assert_eq!(stream.next(), None);
+        stream.position = 100; // past the end
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_string_stream_comma_delimited() {
+        let mut stream = StringStream::with_delimiter("name,age,,city", ',');
+        assert_eq!(
+            collect_tokens(&mut stream),
+            vec![("name".to_string(), 0), ("age".to_string(), 5), ("city".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn test_string_stream_split_fn_on_punctuation() {
+        let mut stream = StringStream::with_split_fn("usr/local;bin:rust.rs", |c| c.is_ascii_punctuation());
+        let tokens: Vec<String> = collect_tokens(&mut stream).into_iter().map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec!["usr", "local", "bin", "rust", "rs"]);
+    }
+
+    #[test]
+    fn test_string_stream_keep_empty_tokens() {
+        let mut stream = StringStream::with_delimiter(",a,,b,", ',').keep_empty(true);
+        assert_eq!(
+            collect_tokens(&mut stream),
+            vec![
+                ("".to_string(), 0),
+                ("a".to_string(), 1),
+                ("".to_string(), 3),
+                ("b".to_string(), 4),
+                ("".to_string(), 6),
+            ]
+        );
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.peek(), None);
+
+        // Matches str::split exactly, and reset starts over
+        stream.reset_position();
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next() {
+            tokens.push(token.to_string());
+        }
+        assert_eq!(tokens, ",a,,b,".split(',').collect::<Vec<_>>());
+
+        let mut empty = StringStream::new("").keep_empty(true);
+        assert_eq!(empty.next_with_position(), Some(("", 0)));
+        assert_eq!(empty.next(), None);
+        assert_eq!(StringStream::new("").next(), None);
+    }
+
+    #[test]
+    fn test_csv_stream_quoted_fields_with_commas() {
+        let mut stream = CsvStream::new("name,quote\nAda,\"Hello, world\"\nBob,\"say \"\"hi\"\"\"\n");
+
+        let header = stream.next().unwrap();
+        assert_eq!(header.fields().collect::<Vec<_>>(), vec!["name", "quote"]);
+
+        let (ada, position) = stream.next_with_position().unwrap();
+        assert_eq!(position, 11);
+        assert_eq!(ada.field(1), Some("Hello, world"));
+        assert_eq!(ada.len(), 2);
+
+        let bob = stream.next().unwrap();
+        assert_eq!(bob.field(1), Some("say \"\"hi\"\""));
+        assert_eq!(bob.unescaped(1).as_deref(), Some("say \"hi\""));
+        assert!(matches!(ada.unescaped(0), Some(Cow::Borrowed("Ada"))));
+
+        // Trailing newline: no extra empty record
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_csv_stream_empty_fields() {
+        let mut stream = CsvStream::new("a,,c\n,\n\"\",x");
+        let rows: Vec<Vec<&str>> = std::iter::from_fn(|| stream.next())
+            .map(|record| record.fields().collect())
+            .collect();
+        assert_eq!(rows, vec![vec!["a", "", "c"], vec!["", ""], vec!["", "x"]]);
+    }
+
+    #[test]
+    fn test_csv_stream_unterminated_quote_takes_rest_of_line() {
+        let mut stream = CsvStream::new("1,\"open, never closed\n2,ok\r\n");
+        let broken = stream.next().unwrap();
+        assert_eq!(broken.fields().collect::<Vec<_>>(), vec!["1", "open, never closed"]);
+
+        // The next line is parsed normally, with CRLF stripped
+        let (ok, position) = stream.next_with_position().unwrap();
+        assert_eq!(ok.fields().collect::<Vec<_>>(), vec!["2", "ok"]);
+        assert_eq!(position, 22);
+        assert_eq!(stream.next(), None);
+
+        stream.reset_position();
+        assert_eq!(stream.position(), 0);
+        assert_eq!(stream.next().unwrap().field(0), Some("1"));
+    }
+
+    #[test]
+    fn test_csv_records_outlive_stream_borrow() {
+        let data = String::from("k,v\nx,1");
+        let mut stream = CsvStream::new(&data);
+        let first = stream.next().unwrap();
+        let second = stream.next().unwrap();
+        assert_eq!((first.field(0), second.field(1)), (Some("k"), Some("1")));
+        assert_eq!(second.field(2), None);
+    }
+
+    #[test]
+    fn test_zip_string_stream_with_int_indexes() {
+        let indexes = IntStream { data: vec![0, 1, 2], position: 0 };
+        let mut zipped = string_stream("alpha beta gamma").zip(indexes);
+
+        let mut pairs = Vec::new();
+        while let Some(((word, index), position)) = zipped.next_with_position() {
+            pairs.push((word.to_string(), *index, position));
+        }
+        assert_eq!(
+            pairs,
+            vec![
+                ("alpha".to_string(), 0, 0),
+                ("beta".to_string(), 1, 6),
+                ("gamma".to_string(), 2, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zip_unequal_lengths_truncates() {
+        let numbers = [10, 20];
+        let mut zipped = string_stream("a b c d").zip(SliceStream::from_slice(&numbers));
+        let mut pairs = Vec::new();
+        while let Some((word, number)) = zipped.next() {
+            pairs.push((word.to_string(), *number));
+        }
+        assert_eq!(pairs, vec![("a".to_string(), 10), ("b".to_string(), 20)]);
+
+        // The shorter side ends the zip; reset rewinds both sides
+        zipped.reset_position();
+        assert_eq!(zipped.next(), Some(("a", &10)));
+    }
+
+    #[test]
+    fn test_chain_string_streams_positions_restart() {
+        let mut chained = string_stream("one two").chain(string_stream("three four"));
+        let mut tokens = Vec::new();
+        while let Some((token, position)) = chained.next_with_position() {
+            tokens.push((token.to_string(), position));
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                ("one".to_string(), 0),
+                ("two".to_string(), 4),
+                ("three".to_string(), 0),
+                ("four".to_string(), 6),
+            ]
+        );
+        assert_eq!(chained.next(), None);
+
+        chained.reset_position();
+        assert_eq!(chained.next(), Some("one"));
+    }
+
+    #[test]
+    fn test_chain_borrowed_slice_streams() {
+        let (left, right) = (vec![1, 2], vec![3]);
+        let mut chained = SliceStream::from_slice(&left).chain(SliceStream::from_slice(&right));
+        let mut values = Vec::new();
+        while let Some(value) = chained.next() {
+            values.push(*value);
+        }
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_string_stream_delimiter_runs_and_trailing() {
+        // Repeated delimiters never yield empty tokens in the default mode
+        let mut stream = StringStream::with_delimiter(";;a;;;b;", ';');
+        assert_eq!(collect_tokens(&mut stream), vec![("a".to_string(), 2), ("b".to_string(), 6)]);
+        assert_eq!(stream.position, stream.data.len());
+
+        // The default constructor still splits on spaces, including runs of them
+        let mut spaces = StringStream::new("x   y ");
+        assert_eq!(collect_tokens(&mut spaces), vec![("x".to_string(), 0), ("y".to_string(), 4)]);
+    }
+
+    #[test]
+    fn test_collect_owned_string_stream() {
+        let words: Vec<String> = string_stream("lend me your words").collect_owned(str::to_string);
+        assert_eq!(words, vec!["lend", "me", "your", "words"]);
+
+        let lengths: std::collections::BTreeSet<usize> =
+            string_stream("aa b cc ddd").collect_owned(|word: &str| word.len());
+        assert_eq!(lengths.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_count_and_fold_exhaust_the_stream() {
+        let ints = IntStream { data: vec![1, 2, 3, 4], position: 0 };
+        assert_eq!(ints.count(), 4);
+
+        let sum = IntStream { data: vec![1, 2, 3, 4], position: 0 }.fold(0, |acc, x: &i32| acc + x);
+        assert_eq!(sum, 10);
+
+        // By-value terminal ops consume the stream; drive a get_ref-able adapter to check exhaustion
+        let mut taken = IntStream { data: vec![5, 6, 7], position: 0 }.take(5);
+        let mut total = 0;
+        while let Some(x) = taken.next() {
+            total += x;
+        }
+        assert_eq!(total, 18);
+        assert_eq!(taken.get_ref().position, 3);
+        assert_eq!(taken.next(), None);
+    }
+
+    #[test]
+    fn test_for_each_visits_every_item() {
+        let mut seen = Vec::new();
+        string_stream("x yy zzz").for_each(|word: &str| seen.push(word.len()));
+        assert_eq!(seen, vec![1, 2, 3]);
+        assert_eq!(string_stream("x yy zzz").skip(1).count(), 2);
+    }
+
+    #[test]
+    fn test_string_stream_korean_and_emoji_positions() {
+        let mut stream = string_stream("안녕 세계 🦀");
+        let mut tokens = Vec::new();
+        while let Some((token, position)) = stream.next_with_position() {
+            tokens.push((token.to_string(), position));
+            // The resting position is a char boundary too
+            assert!(stream.data.is_char_boundary(stream.position));
+        }
+        assert_eq!(
+            tokens,
+            vec![("안녕".to_string(), 0), ("세계".to_string(), 7), ("🦀".to_string(), 14)]
+        );
+    }
+
+    #[test]
+    fn test_mut_stream_doubles_int_stream() {
+        let mut stream = IntStream { data: vec![1, 2, 3], position: 0 };
+        while let Some(x) = stream.next_mut() {
+            *x *= 2;
+        }
+        assert_eq!(stream.next_mut(), None);
+
+        stream.reset_position();
+        let mut values = Vec::new();
+        while let Some(x) = stream.next() {
+            values.push(*x);
+        }
+        assert_eq!(values, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_mut_stream_over_slice_shares_cursor() {
+        let mut words = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        {
+            let mut stream = SliceMutStream::from_slice(&mut words);
+            assert_eq!(stream.next().map(String::as_str), Some("a"));
+            if let Some(word) = stream.next_mut() {
+                word.push('!');
+            }
+            assert_eq!(stream.next_with_position().map(|(w, p)| (w.as_str(), p)), Some(("c", 2)));
+        }
+        assert_eq!(words, vec!["a", "b!", "c"]);
+    }
+
+    fn seven_ints() -> IntStream {
+        IntStream { data: (1..=7).collect(), position: 0 }
+    }
+
+    #[test]
+    fn test_chunks_of_three() {
+        let mut chunks = seven_ints().chunks(3, |x: &i32| *x).unwrap();
+        let mut groups = Vec::new();
+        while let Some((chunk, position)) = chunks.next_with_position() {
+            groups.push((chunk.to_vec(), position));
+        }
+        assert_eq!(groups, vec![(vec![1, 2, 3], 0), (vec![4, 5, 6], 3), (vec![7], 6)]);
+        assert_eq!(chunks.next(), None);
+
+        let mut full_only = seven_ints().chunks(3, |x: &i32| *x).unwrap().keep_partial(false);
+        let mut groups = Vec::new();
+        while let Some(chunk) = full_only.next() {
+            groups.push(chunk.to_vec());
+        }
+        assert_eq!(groups, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(full_only.get_ref().position, 7);
+    }
+
+    #[test]
+    fn test_windows_of_three() {
+        let mut windows = seven_ints().windows(3, |x: &i32| *x).unwrap();
+        let mut groups = Vec::new();
+        while let Some((window, position)) = windows.next_with_position() {
+            groups.push((window.to_vec(), position));
+        }
+        assert_eq!(
+            groups,
+            vec![
+                (vec![1, 2, 3], 0),
+                (vec![2, 3, 4], 1),
+                (vec![3, 4, 5], 2),
+                (vec![4, 5, 6], 3),
+                (vec![5, 6, 7], 4),
+            ]
+        );
+
+        windows.reset_position();
+        assert_eq!(windows.next(), Some(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn test_chunks_and_windows_edge_cases() {
+        assert!(seven_ints().chunks(0, |x: &i32| *x).is_err());
+        assert!(seven_ints().windows(0, |x: &i32| *x).is_err());
+
+        // Shorter than the window: nothing at all
+        let mut short = string_stream("only two").windows(3, str::to_string).unwrap();
+        assert_eq!(short.next(), None);
+
+        let mut words = string_stream("a b c d e").chunks(2, str::to_string).unwrap();
+        assert_eq!(words.next(), Some(&["a".to_string(), "b".to_string()][..]));
+    }
+
+    #[test]
+    fn test_seek_back_replays_token() {
+        let mut stream = string_stream("alpha beta gamma delta");
+        stream.next();
+        let (_, beta_position) = stream.next_with_position().unwrap();
+        assert_eq!(stream.next(), Some("gamma"));
+
+        stream.seek(beta_position).unwrap();
+        assert_eq!(stream.next(), Some("beta"));
+        assert_eq!(SeekableStream::position(&stream), 11);
+
+        // rewind counts position units (bytes here): back over "beta "
+        stream.rewind(5).unwrap();
+        assert_eq!(stream.next(), Some("beta"));
+    }
+
+    #[test]
+    fn test_seek_errors_leave_position_unchanged() {
+        let mut stream = string_stream("안녕 세계");
+        stream.next();
+        let before = stream.position;
+
+        let len = stream.data.len();
+        assert_eq!(stream.seek(len + 1), Err(SeekError::OutOfBounds { position: len + 1, len }));
+        assert_eq!(stream.seek(1), Err(SeekError::NotCharBoundary(1)));
+        assert_eq!(stream.rewind(100), Err(SeekError::BeforeStart { position: before, back: 100 }));
+        assert_eq!(stream.position, before);
+
+        // Seeking to the very end is fine and simply ends the stream
+        stream.seek(len).unwrap();
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_seek_index_streams() {
+        let mut ints = IntStream { data: vec![10, 20, 30], position: 0 };
+        while ints.next().is_some() {}
+        ints.rewind(2).unwrap();
+        assert_eq!(ints.next(), Some(&20));
+        assert!(ints.seek(4).is_err());
+
+        let mut csv = CsvStream::new("a,b\nc,d\n");
+        csv.next();
+        let second = csv.next_with_position().map(|(_, position)| position).unwrap();
+        csv.seek(second).unwrap();
+        assert_eq!(csv.next().unwrap().field(1), Some("d"));
+    }
+
+    #[test]
+    fn test_take_while_short_words() {
+        let mut short = string_stream("tiny small little enormous wee").take_while(|word: &&str| word.len() < 6);
+        let mut words = Vec::new();
+        while let Some(word) = short.next() {
+            words.push(word.to_string());
+        }
+        assert_eq!(words, vec!["tiny", "small"]);
+        // "little" failed the predicate and was consumed; nothing after it is yielded
+        assert_eq!(short.next(), None);
+        assert_eq!(&short.get_ref().data[short.get_ref().position..], "enormous wee");
+    }
+
+    #[test]
+    fn test_skip_while_peek_and_reset() {
+        let mut rest = IntStream { data: vec![-1, -2, 3, -4], position: 0 }.skip_while(|x: &&i32| **x < 0);
+        assert_eq!(rest.peek(), Some(&3));
+        assert_eq!(rest.get_ref().position, 2);
+        assert_eq!(rest.next(), Some(&3));
+        // Only the leading run is skipped
+        assert_eq!(rest.next_with_position(), Some((&-4, 3)));
+        assert_eq!(rest.next(), None);
+
+        rest.reset_position();
+        assert_eq!(rest.next_with_position(), Some((&3, 2)));
+
+        let mut all_skipped = IntStream { data: vec![-1, -2], position: 0 }.skip_while(|x: &&i32| **x < 0);
+        assert_eq!(all_skipped.next(), None);
+        assert_eq!(all_skipped.peek(), None);
+    }
+
+    #[test]
+    fn test_skip_while_leading_negatives() {
+        let ints = IntStream { data: vec![-3, -1, 4, -5, 9], position: 0 };
+        let mut rest = ints.skip_while(|x: &&i32| **x < 0);
+        let mut values = Vec::new();
+        while let Some((x, position)) = rest.next_with_position() {
+            values.push((*x, position));
+        }
+        assert_eq!(values, vec![(4, 2), (-5, 3), (9, 4)]);
+
+        rest.reset_position();
+        assert_eq!(rest.next(), Some(&4));
+    }
+
+    #[test]
+    fn test_scan_running_sums() {
+        let ints = IntStream { data: vec![1, 2, 3, 4, 5], position: 0 };
+        let mut sums = ints.scan(0, |total: &mut i32, x: &i32| {
+            *total += x;
+            (*total <= 10).then_some(*total)
+        });
+        let mut values = Vec::new();
+        while let Some(sum) = sums.next() {
+            values.push(sum);
+        }
+        // 1+2+3+4 = 10, then 15 exceeds the limit and ends the scan
+        assert_eq!(values, vec![1, 3, 6, 10]);
+        assert_eq!(sums.next(), None);
+        assert_eq!(*sums.state(), 15);
+
+        sums.reset_position();
+        assert_eq!(sums.next(), Some(1));
+    }
+
+    #[test]
+    fn test_raw_streams_stay_exhausted() {
+        let mut words = string_stream("one two");
+        while words.next().is_some() {}
+        for _ in 0..5 {
+            assert_eq!(words.next(), None);
+            assert_eq!(words.next_with_position(), None);
+        }
+        assert_eq!(words.position, words.data.len());
+
+        let mut kept = StringStream::with_delimiter("a,", ',').keep_empty(true);
+        while kept.next().is_some() {}
+        for _ in 0..5 {
+            assert_eq!(kept.next(), None);
+        }
+
+        let mut ints = IntStream { data: vec![1], position: 0 };
+        while ints.next().is_some() {}
+        for _ in 0..5 {
+            assert_eq!(ints.next(), None);
+            assert_eq!(ints.next_with_position(), None);
+        }
+        assert_eq!(ints.position, 1);
+    }
+
+    #[test]
+    fn test_fuse_ignores_a_growing_source() {
+        let mut fused = IntStream { data: vec![1, 2], position: 0 }.fuse();
+        while fused.next().is_some() {}
+        assert!(fused.is_exhausted());
+
+        // The raw stream would resume here; the fused one does not
+        fused.get_mut().data.push(3);
+        for _ in 0..5 {
+            assert_eq!(fused.next(), None);
+            assert_eq!(fused.next_with_position(), None);
+        }
+
+        fused.reset_position();
+        assert!(!fused.is_exhausted());
+        let mut values = Vec::new();
+        while let Some(x) = fused.next() {
+            values.push(*x);
+        }
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_key_value_stream_with_comments() {
+        let config = "# server settings\nhost = localhost\n\n  # indented comment\nport=8080\nurl = http://x?a=b\n";
+        let mut stream = KeyValueStream::new(config);
+        let mut entries = Vec::new();
+        while let Some((entry, position)) = stream.next_with_position() {
+            entries.push((entry, position));
+        }
+        assert_eq!(
+            entries,
+            vec![(("host", "localhost"), 18), (("port", "8080"), 57), (("url", "http://x?a=b"), 67)]
+        );
+    }
+
+    #[test]
+    fn test_key_value_stream_skips_lines_without_equals() {
+        let mut stream = KeyValueStream::new("verbose\nlevel = 3\nempty =");
+        assert_eq!(stream.next(), Some(("level", "3")));
+        assert_eq!(stream.next(), Some(("empty", "")));
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_key_value_collect_map_last_wins() {
+        let map = KeyValueStream::new("mode = debug\nport = 1\nmode = release").collect_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["mode"], "release");
+        assert_eq!(map["port"], "1");
+    }
+
+    #[test]
+    fn test_for_each_visits_in_order() {
+        let mut visited = Vec::new();
+        string_stream("first second third").for_each(|word: &str| visited.push(word.to_string()));
+        assert_eq!(visited, vec!["first", "second", "third"]);
+
+        let mut positions = Vec::new();
+        IntStream { data: vec![7, 8, 9], position: 1 }.for_each(|x: &i32| positions.push(*x));
+        assert_eq!(positions, vec![8, 9]);
+    }
+
+    fn json_tokens(data: &str) -> Vec<(Token<'_>, usize)> {
+        let mut stream = JsonTokenStream::new(data);
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next_with_position() {
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_json_lexer_nested_object() {
+        let tokens: Vec<Token> = json_tokens(r#"{"a": [1, true, null], "b": {"c": false}}"#)
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBrace, Token::Str("a"), Token::Colon,
+                Token::LBracket, Token::Number("1"), Token::Comma, Token::Bool(true), Token::Comma, Token::Null, Token::RBracket,
+                Token::Comma, Token::Str("b"), Token::Colon,
+                Token::LBrace, Token::Str("c"), Token::Colon, Token::Bool(false), Token::RBrace,
+                Token::RBrace,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_lexer_escaped_quotes_and_positions() {
+        assert_eq!(
+            json_tokens(r#"["say \"hi\"", "back\\"]"#),
+            vec![
+                (Token::LBracket, 0),
+                (Token::Str(r#"say \"hi\""#), 1),
+                (Token::Comma, 13),
+                (Token::Str(r"back\\"), 15),
+                (Token::RBracket, 23),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_lexer_numbers() {
+        let tokens: Vec<Token> = json_tokens("0 -12 3.25 6.02e23 1E-9 -0.5e+2")
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            ["0", "-12", "3.25", "6.02e23", "1E-9", "-0.5e+2"].map(Token::Number).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_json_lexer_errors_at_offsets() {
+        assert_eq!(
+            json_tokens(r#"{"k": tru, "n": 01, @}"#),
+            vec![
+                (Token::LBrace, 0),
+                (Token::Str("k"), 1),
+                (Token::Colon, 4),
+                (Token::Error("tru", 6), 6),
+                (Token::Comma, 9),
+                (Token::Str("n"), 11),
+                (Token::Colon, 14),
+                (Token::Error("01", 16), 16),
+                (Token::Comma, 18),
+                (Token::Error("@", 20), 20),
+                (Token::RBrace, 21),
+            ]
+        );
+        assert_eq!(json_tokens(r#"["open"#), vec![(Token::LBracket, 0), (Token::Error(r#""open"#, 1), 1)]);
+    }
+
+    #[test]
+    fn test_find_returns_owned_items() {
+        let mut ints = IntStream { data: vec![3, 5, 8, 9, 10], position: 0 };
+        assert_eq!(ints.find(|x: &&i32| **x % 2 == 0), Some(8));
+        // The search resumes after the previous match
+        assert_eq!(ints.find(|x: &&i32| **x % 2 == 0), Some(10));
+
+        let mut words = string_stream("alpha beta gamma");
+        let found: Option<String> = words.find(|word: &&str| word.starts_with('a'));
+        assert_eq!(found.as_deref(), Some("alpha"));
+    }
+
+    #[test]
+    fn test_find_without_match_exhausts() {
+        let mut ints = IntStream { data: vec![1, 3, 5], position: 0 };
+        assert_eq!(ints.find(|x: &&i32| **x % 2 == 0), None);
+        assert_eq!(ints.position, 3);
+        assert_eq!(ints.next(), None);
+    }
+
+    #[test]
+    fn test_position_start_middle_and_missing() {
+        let mut ints = IntStream { data: vec![2, 4, 7, 8], position: 0 };
+        assert_eq!(StreamExt::position(&mut ints, |x: &&i32| **x == 2), Some(0));
+        // Counted from the current position: 4 and 7 come after the consumed 2
+        assert_eq!(StreamExt::position(&mut ints, |x: &&i32| **x == 7), Some(1));
+        assert_eq!(StreamExt::position(&mut ints, |x: &&i32| **x == 100), None);
+        assert_eq!(ints.next(), None);
+    }
+
+    #[test]
+    fn test_row_stream_over_matrix() {
+        use crate::custom_types::const_generic::Matrix;
+
+        let matrix: Matrix<i32, 2, 3> = Matrix::from_data([[1, 2, 3], [4, 5, 6]]);
+        let mut rows = matrix.rows_stream();
+        let mut seen = Vec::new();
+        while let Some((row, position)) = rows.next_with_position() {
+            let row: [i32; 3] = *row;
+            seen.push((row, position));
+        }
+        assert_eq!(seen, vec![([1, 2, 3], 0), ([4, 5, 6], 1)]);
+        assert_eq!(rows.next(), None);
+
+        rows.reset_position();
+        assert_eq!(rows.next(), Some(&[1, 2, 3]));
+        assert_eq!(rows.next(), Some(&[4, 5, 6]));
+    }
+
+    #[test]
+    fn test_array_stream_over_array() {
+        use crate::custom_types::const_generic::Array;
+
+        let array = Array::from_array(['a', 'b', 'c']);
+        let mut stream = array.stream();
+        let mut seen = Vec::new();
+        while let Some((c, position)) = stream.next_with_position() {
+            seen.push((*c, position));
+        }
+        assert_eq!(seen, vec![('a', 0), ('b', 1), ('c', 2)]);
+
+        stream.reset_position();
+        assert_eq!(stream.next(), Some(&'a'));
+    }
+
+    #[test]
+    fn test_char_stream_multi_byte() {
+        let mut stream = CharStream::new("aé🦀");
+        let mut chars = Vec::new();
+        while let Some((c, position)) = stream.next_with_position() {
+            assert!(stream.data.is_char_boundary(stream.position));
+            chars.push((c, position));
+        }
+        assert_eq!(chars, vec![('a', 0), ('é', 1), ('🦀', 3)]);
+        assert_eq!(stream.position, stream.data.len());
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_char_stream_count_matches_chars() {
+        let text = "안녕, world! 🦀🦀";
+        assert_eq!(CharStream::new(text).count(), text.chars().count());
+
+        let mut stream = CharStream::new(text);
+        assert_eq!(stream.seek(1), Err(SeekError::NotCharBoundary(1)));
+        stream.seek(3).unwrap();
+        assert_eq!(stream.next(), Some('녕'));
+    }
+
+    #[test]
+    fn test_dyn_stream_heterogeneous_vec() {
+        let mut streams: Vec<Box<dyn DynStream<String>>> = vec![
+            Box::new(string_stream("a b c").owned(str::to_string)),
+            Box::new(IntStream { data: vec![1, 2], position: 0 }.owned(|x: &i32| format!("#{}", x))),
+            Box::new(CharStream::new("xy").owned(|c: char| c.to_uppercase().collect::<String>())),
+        ];
+
+        // Round-robin until every stream is exhausted
+        let mut outputs = Vec::new();
+        loop {
+            let before = outputs.len();
+            for stream in streams.iter_mut() {
+                if let Some(item) = stream.next_owned() {
+                    outputs.push(item);
+                }
+            }
+            if outputs.len() == before {
+                break;
+            }
+        }
+        assert_eq!(outputs, vec!["a", "#1", "X", "b", "#2", "Y", "c"]);
+        assert!(streams.iter_mut().all(|stream| stream.next_owned().is_none()));
+    }
+
+    #[test]
+    fn test_iter_owned_matches_manual_loop() {
+        let text = "bridged words become an iterator";
+        let mut manual = Vec::new();
+        let mut stream = string_stream(text);
+        while let Some(word) = stream.next() {
+            manual.push(word.to_string());
+        }
+
+        let bridged: Vec<String> = string_stream(text).iter_owned(str::to_string).collect();
+        assert_eq!(bridged, manual);
+
+        let numbered: Vec<(usize, String)> = (1..).zip(string_stream("a b").iter_owned(str::to_string)).collect();
+        assert_eq!(numbered, vec![(1, "a".to_string()), (2, "b".to_string())]);
+    }
+
+    #[test]
+    fn test_copied_int_stream_with_iterator_adapters() {
+        let ints = || IntStream { data: vec![1, 2, 3, 4], position: 0 };
+
+        let mut manual = 0;
+        let mut stream = ints();
+        while let Some(x) = stream.next() {
+            manual += x;
+        }
+        assert_eq!(ints().copied().sum::<i32>(), manual);
+
+        let evens: Vec<i32> = ints().copied().filter(|x| x % 2 == 0).collect();
+        assert_eq!(evens, vec![2, 4]);
+        let scaled: Vec<i32> = ints().copied().zip(10..).map(|(x, k)| x * k).collect();
+        assert_eq!(scaled, vec![10, 22, 36, 52]);
+    }
+
+    #[test]
+    fn test_enumerate_counts_items_not_bytes() {
+        let mut enumerated = string_stream("a   bb    c").enumerate();
+        let mut seen = Vec::new();
+        while let Some(((index, word), position)) = enumerated.next_with_position() {
+            seen.push((index, word.to_string(), position));
+        }
+        assert_eq!(
+            seen,
+            vec![(0, "a".to_string(), 0), (1, "bb".to_string(), 4), (2, "c".to_string(), 10)]
+        );
+
+        enumerated.reset_position();
+        assert_eq!(enumerated.next(), Some((0, "a")));
+    }
+
+    #[test]
+    fn test_with_metadata_composes_with_take() {
+        let mut infos = string_stream("  안녕   to  you").with_metadata().take(2);
+        let first = infos.next().unwrap();
+        assert_eq!(first, TokenInfo { text: "안녕", byte_offset: 2, index: 0, len: 6 });
+        let second = infos.next().unwrap();
+        assert_eq!((second.text, second.byte_offset, second.index, second.len), ("to", 11, 1, 2));
+        let (offset, len) = (second.byte_offset, second.len);
+        assert_eq!(infos.next(), None);
+
+        let data = &infos.get_ref().get_ref().data;
+        assert_eq!(&data[offset..offset + len], "to");
+    }
+
+    #[test]
+    fn test_prev_steps_back_over_the_last_item() {
+        let mut stream = IntStream { data: vec![10, 20, 30], position: 0 };
+        let seen = vec![
+            *stream.next().unwrap(),
+            *stream.next().unwrap(),
+            *stream.prev().unwrap(),
+            *stream.next().unwrap(),
+            *stream.next().unwrap(),
+        ];
+        assert_eq!(seen, vec![10, 20, 20, 20, 30]);
+        assert_eq!(stream.prev_with_position(), Some((&30, 2)));
+    }
+
+    #[test]
+    fn test_prev_at_start_returns_none() {
+        let mut ints = IntStream { data: vec![1, 2], position: 0 };
+        assert_eq!(ints.prev(), None);
+        assert_eq!(ints.position, 0);
+
+        let names = ["a", "b"];
+        let mut slice = SliceStream::from_slice(&names);
+        assert_eq!(slice.prev(), None);
+        assert_eq!(slice.next(), Some(&"a"));
+        assert_eq!(slice.prev(), Some(&"a"));
+        assert_eq!(slice.prev(), None);
+    }
+
+    #[test]
+    fn test_rev_walks_from_the_end() {
+        let mut reversed = IntStream { data: vec![1, 2, 3, 4, 5], position: 0 }.rev();
+        let mut seen = Vec::new();
+        while let Some((x, position)) = reversed.next_with_position() {
+            seen.push((*x, position));
+        }
+        assert_eq!(seen, vec![(5, 4), (4, 3), (3, 2), (2, 1), (1, 0)]);
+        assert_eq!(reversed.next(), None);
+
+        reversed.reset_position();
+        assert_eq!(reversed.next(), Some(&5));
+    }
+
+    #[test]
+    fn test_rev_borrowed_slice() {
+        let words = ["x", "y", "z"];
+        let mut reversed = SliceStream::from_slice(&words).rev();
+        let mut seen = Vec::new();
+        while let Some(word) = reversed.next() {
+            seen.push(*word);
+        }
+        assert_eq!(seen, vec!["z", "y", "x"]);
+    }
+
+    #[test]
+    fn test_slice_split_at_disjoint_halves() {
+        let data = [1, 2, 3, 4, 5];
+        let mut stream = SliceStream::from_slice(&data);
+        stream.next();
+        let (mut head, mut tail) = stream.split_at(2);
+
+        assert_eq!(tail.next_with_position(), Some((&4, 0)));
+        assert_eq!(head.next_with_position(), Some((&2, 1)));
+        assert_eq!(head.next(), Some(&3));
+        assert_eq!(head.next(), None);
+        assert_eq!(tail.next(), Some(&5));
+        assert_eq!(tail.next(), None);
+
+        let (mut all, mut rest) = SliceStream::from_slice(&data).split_at(10);
+        assert_eq!(all.remaining(), 5);
+        assert_eq!(rest.next(), None);
+        assert_eq!(all.next(), Some(&1));
+    }
+
+    #[test]
+    fn test_slice_tee_cursors_are_independent() {
+        let data = ["a", "b", "c"];
+        let mut stream = SliceStream::from_slice(&data);
+        stream.next();
+        let (mut left, mut right) = stream.tee();
+        assert_eq!(left.next(), Some(&"b"));
+        assert_eq!(left.next(), Some(&"c"));
+        assert_eq!(right.position, 1);
+        assert_eq!(right.next(), Some(&"b"));
+        assert_eq!(stream.next(), Some(&"b"));
+    }
+
+    #[test]
+    fn test_str_stream_split_at_tokens() {
+        let text = String::from("one two  three four");
+        let (mut head, mut tail) = StrStream::new(&text).split_at(2);
+        let mut tail_words = Vec::new();
+        while let Some(word) = tail.next() {
+            tail_words.push(word.to_string());
+        }
+        let mut head_words = Vec::new();
+        while let Some(word) = head.next() {
+            head_words.push(word.to_string());
+        }
+        assert_eq!(head_words, vec!["one", "two"]);
+        assert_eq!(tail_words, vec!["three", "four"]);
+
+        let (mut none, mut everything) = StrStream::new(&text).split_at(0);
+        assert_eq!(none.next(), None);
+        assert_eq!(everything.next(), Some("one"));
+    }
+
+    #[test]
+    fn test_str_stream_split_at_keeps_empty_tokens() {
+        let stream = StringStream::with_delimiter("a,,b", ',').keep_empty(true);
+        let (mut head, mut tail) = stream.as_str_stream().split_at(1);
+        assert_eq!(head.next(), Some("a"));
+        assert_eq!(head.next(), None);
+        assert_eq!(tail.next(), Some(""));
+        assert_eq!(tail.next(), Some("b"));
+        assert_eq!(tail.next(), None);
+
+        let (mut head, mut tail) = StrStream::new("x").split_at(0);
+        assert_eq!((head.next(), tail.next()), (None, Some("x")));
+
+        // Splitting after the last token leaves nothing, not an empty token
+        let stream = StringStream::with_delimiter("a,b", ',').keep_empty(true);
+        let (_, mut tail) = stream.as_str_stream().split_at(2);
+        assert_eq!(tail.next(), None);
+        let (_, mut tail) = stream.as_str_stream().split_at(5);
+        assert_eq!(tail.next(), None);
+        let (mut head, mut tail) = stream.as_str_stream().split_at(0);
+        assert_eq!((head.next(), tail.next()), (None, Some("a")));
+
+        let empty = StringStream::new("").keep_empty(true);
+        let (mut head, mut tail) = empty.as_str_stream().split_at(1);
+        assert_eq!(head.next(), Some(""));
+        assert_eq!((head.next(), tail.next()), (None, None));
+    }
+
+    #[test]
+    fn test_str_stream_tee_from_string_stream() {
+        let mut owned = StringStream::new("shared words here");
+        owned.next();
+        let (mut left, mut right) = owned.as_str_stream().tee();
+        assert_eq!(left.next_with_position(), Some(("words", 7)));
+        assert_eq!(left.next(), Some("here"));
+        assert_eq!(right.next(), Some("words"));
+        assert_eq!(left.next(), None);
+        assert_eq!(right.position, 13);
+    }
+
+    #[test]
+    fn test_history_keeps_last_k_in_order() {
+        let mut stream: HistoryStream<_, _, 3> = HistoryStream::new(string_stream("one two three four five"));
+        assert!(stream.history().is_empty());
+        assert_eq!(stream.last(), None);
+
+        for _ in 0..5 {
+            stream.next();
+        }
+        assert_eq!(stream.history(), ["three", "four", "five"]);
+        assert_eq!(stream.last().map(String::as_str), Some("five"));
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.history().len(), 3);
+
+        stream.reset_position();
+        assert!(stream.history().is_empty());
+    }
+
+    #[test]
+    fn test_history_partial_fill_and_zero_capacity() {
+        let ints = || IntStream { data: vec![4, 5, 6], position: 0 };
+        let mut stream = HistoryStream::<_, i32, 5>::new(ints());
+        assert_eq!(stream.next_with_position(), Some((&4, 0)));
+        stream.next();
+        assert_eq!(stream.history(), [4, 5]);
+
+        let mut none = HistoryStream::<_, i32, 0>::new(ints());
+        assert_eq!(none.next(), Some(&4));
+        assert!(none.history().is_empty());
+        assert_eq!(none.last(), None);
+    }
+
+    #[test]
+    fn test_step_by_one_yields_everything() {
+        let mut all = seven_ints().step_by(1).unwrap();
+        let mut seen = Vec::new();
+        while let Some(x) = all.next() {
+            seen.push(*x);
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert!(seven_ints().step_by(0).is_err());
+    }
+
+    #[test]
+    fn test_step_by_two_samples_positions() {
+        let mut evens = seven_ints().step_by(2).unwrap();
+        let mut seen = Vec::new();
+        while let Some((x, position)) = evens.next_with_position() {
+            seen.push((*x, position));
+        }
+        assert_eq!(seen, vec![(1, 0), (3, 2), (5, 4), (7, 6)]);
+
+        evens.reset_position();
+        assert_eq!(evens.next(), Some(&1));
+    }
+
+    #[test]
+    fn test_step_by_at_the_end() {
+        // A gap that runs past the end yields nothing more
+        let mut sampled = seven_ints().step_by(4).unwrap();
+        assert_eq!(sampled.next(), Some(&1));
+        assert_eq!(sampled.next(), Some(&5));
+        assert_eq!(sampled.next(), None);
+        assert_eq!(sampled.next(), None);
+
+        let mut larger_than_stream = string_stream("only two").step_by(10).unwrap();
+        assert_eq!(larger_than_stream.next(), Some("only"));
+        assert_eq!(larger_than_stream.next(), None);
+    }
+
+    // set NAME = VALUE
+    fn parse_set(input: &str) -> Result<(&str, &str), ParseError> {
+        let mut parser = ParseStream::new(input);
+        parser.expect(|t| t == "set", "keyword `set`")?;
+        let name = parser.expect(|t| t.chars().all(|c| c.is_ascii_alphabetic()), "a name")?;
+        parser.expect(|t| t == "=", "`=`")?;
+        let value = parser.expect(|_| true, "a value")?;
+        parser.expect_end()?;
+        Ok((name, value))
+    }
+
+    #[test]
+    fn test_parse_set_statement() {
+        assert_eq!(parse_set("set WIDTH = 80"), Ok(("WIDTH", "80")));
+        assert_eq!(parse_set("  set  x   =  y "), Ok(("x", "y")));
+    }
+
+    #[test]
+    fn test_parse_wrong_keyword_reports_offset() {
+        let err = parse_set("  let x = 1").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert_eq!(err.message, "expected keyword `set`, found \"let\"");
+
+        let err = parse_set("set x := 1").unwrap_err();
+        assert_eq!(err.position, 6);
+        assert_eq!(err.to_string(), "parse error at byte 6: expected `=`, found \":=\"");
+    }
+
+    #[test]
+    fn test_parse_premature_end_and_trailing_input() {
+        let err = parse_set("set x =").unwrap_err();
+        assert_eq!(err.position, 7);
+        assert!(err.message.ends_with("found end of input"));
+
+        assert_eq!(parse_set("set x = 1 extra").unwrap_err().position, 10);
+    }
+
+    #[test]
+    fn test_try_next_matching_only_advances_on_match() {
+        let words = string_stream("-v file.txt");
+        let mut parser = ParseStream::from(words.as_str_stream());
+        assert_eq!(parser.remaining_hint(), 2);
+        assert_eq!(parser.try_next_matching(|t| t == "-q"), None);
+        assert_eq!(parser.try_next_matching(|t| t.starts_with('-')), Some("-v"));
+        assert_eq!(parser.remaining_hint(), 1);
+        assert_eq!(parser.next_with_position(), Some(("file.txt", 3)));
+        assert_eq!(parser.try_next_matching(|_| true), None);
+    }
+
+    #[test]
+    fn test_map_item_to_owned_lengths() {
+        let mut lengths = string_stream("alpha be gamma delta").map_item(|word: &str| word.len()).take(2);
+        assert_eq!(lengths.next_with_position(), Some((5, 0)));
+        assert_eq!(lengths.next(), Some(2));
+        assert_eq!(lengths.next(), None);
+
+        let mut shouted = string_stream("quiet words").map_item(str::to_uppercase);
+        assert_eq!(shouted.next().as_deref(), Some("QUIET"));
+    }
+
+    #[test]
+    fn test_map_borrow_to_trimmed_sub_slices() {
+        let fields = StringStream::with_delimiter(" left , middle ,right", ',');
+        let mut trimmed = fields.map_borrow(|field| field.trim()).take(2);
+        assert_eq!(trimmed.next(), Some("left"));
+        assert_eq!(trimmed.next_with_position(), Some(("middle", 7)));
+        assert_eq!(trimmed.next(), None);
+
+        let mut first_chars = string_stream("ab cd").map_borrow(|word| &word[..1]);
+        assert_eq!(first_chars.next(), Some("a"));
+        assert_eq!(first_chars.next(), Some("c"));
+    }
+
+    #[test]
+    fn test_byte_chunks_with_remainder() {
+        let bytes: Vec<u8> = (0..10).collect();
+        let mut chunks = ByteChunkStream::<4>::new(&bytes);
+        assert_eq!(chunks.next_with_position(), Some((&[0, 1, 2, 3], 0)));
+        assert_eq!(chunks.next_with_position(), Some((&[4, 5, 6, 7], 4)));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), [8, 9]);
+
+        chunks.reset_position();
+        assert_eq!(chunks.next().map(|chunk| u32::from_le_bytes(*chunk)), Some(0x0302_0100));
+    }
+
+    #[test]
+    fn test_byte_chunks_larger_than_buffer() {
+        let bytes = [1u8, 2, 3];
+        let mut chunks = ByteChunkStream::<8>::new(&bytes);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), bytes);
+
+        let mut exact = ByteChunkStream::<3>::new(&bytes);
+        assert_eq!(exact.next(), Some(&bytes));
+        assert!(exact.remainder().is_empty());
+    }
+
+    fn drain_range(mut range: RangeStream) -> Vec<i32> {
+        let mut values = Vec::new();
+        while let Some(value) = range.next() {
+            values.push(value);
+        }
+        values
+    }
+
+    #[test]
+    fn test_range_stream_ascending_and_positions() {
+        let mut range = RangeStream::new(0, 10, 2).unwrap();
+        assert_eq!(range.next_with_position(), Some((0, 0)));
+        assert_eq!(range.next_with_position(), Some((2, 1)));
+        range.reset_position();
+        assert_eq!(drain_range(range), vec![0, 2, 4, 6, 8]);
+        assert!(RangeStream::new(0, 10, 0).is_err());
+    }
+
+    #[test]
+    fn test_range_stream_empty_and_descending() {
+        assert!(drain_range(RangeStream::new(5, 5, 1).unwrap()).is_empty());
+        assert!(drain_range(RangeStream::new(5, 0, 1).unwrap()).is_empty());
+        assert_eq!(drain_range(RangeStream::new(5, 0, -1).unwrap()), vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_range_stream_step_past_end() {
+        assert_eq!(drain_range(RangeStream::new(0, 10, 3).unwrap()), vec![0, 3, 6, 9]);
+        assert_eq!(drain_range(RangeStream::new(10, -1, -4).unwrap()), vec![10, 6, 2]);
+        assert_eq!(drain_range(RangeStream::new(i32::MAX - 2, i32::MAX, 5).unwrap()), vec![i32::MAX - 2]);
+    }
+
+    fn sentences(text: &str) -> Vec<(&str, usize)> {
+        let mut stream = SentenceStream::new(text);
+        let mut found = Vec::new();
+        while let Some(sentence) = stream.next_with_position() {
+            found.push(sentence);
+        }
+        found
+    }
+
+    #[test]
+    fn test_sentence_stream_paragraph() {
+        let text = "Rust is fast.  Is it safe? Yes!\nIt is.";
+        assert_eq!(
+            sentences(text),
+            vec![("Rust is fast.", 0), ("Is it safe?", 15), ("Yes!", 27), ("It is.", 32)]
+        );
+        assert_eq!(&text[15..26], "Is it safe?");
+    }
+
+    #[test]
+    fn test_sentence_stream_abbreviation_and_fragment() {
+        let text = "Bring tools, e.g. a hammer. Pi is 3.14 and 안녕. Then stop";
+        assert_eq!(
+            sentences(text),
+            vec![("Bring tools, e.g. a hammer.", 0), ("Pi is 3.14 and 안녕.", 28), ("Then stop", 51)]
+        );
+    }
+
+    #[test]
+    fn test_sentence_stream_empty_input() {
+        assert!(sentences("").is_empty());
+        assert!(sentences("   \n ").is_empty());
+        assert_eq!(sentences("  no punctuation  "), vec![("no punctuation", 2)]);
+    }
+
+    #[test]
+    fn test_size_hint_tracks_position() {
+        let mut ints = IntStream { data: vec![1, 2, 3], position: 0 };
+        assert_eq!(ints.size_hint(), (3, Some(3)));
+        ints.next();
+        assert_eq!(ints.len(), 2);
+
+        let data = [1, 2, 3, 4, 5];
+        let mut slice = SliceStream::from_slice(&data);
+        slice.next();
+        slice.next();
+        assert_eq!(slice.size_hint(), (3, Some(3)));
+        let mut taken = slice.take(2);
+        assert_eq!(taken.size_hint(), (2, Some(2)));
+        taken.next();
+        taken.next();
+        assert_eq!(taken.size_hint(), (0, Some(0)));
+
+        let mut range = RangeStream::new(0, 10, 3).unwrap();
+        assert_eq!(range.size_hint(), (4, Some(4)));
+        range.next();
+        assert_eq!(range.size_hint(), (3, Some(3)));
+        assert!(RangeStream::new(5, 0, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_string_stream_keeps_default_hint() {
+        let mut words = string_stream("no cheap count here");
+        assert_eq!(words.size_hint(), (0, None));
+        words.next();
+        assert_eq!(words.size_hint(), (0, None));
+        assert_eq!(words.take(2).size_hint(), (0, Some(2)));
+    }
+
+    #[test]
+    fn test_collect_owned_preallocates_from_hint() {
+        // collect_owned needs a stream that owns or 'static-borrows its data (see above)
+        let data: &'static [u32] = (0..10_000).collect::<Vec<_>>().leak();
+        let collected: Vec<u32> = SliceStream::from_slice(data).collect_owned(|x: &u32| *x);
+        assert_eq!(collected.len(), 10_000);
+        assert!(collected.capacity() >= collected.len());
+        // Growing by doubling would have ended at 16_384
+        assert_eq!(collected.capacity(), 10_000);
+    }
+
+    #[test]
+    fn test_interleave_tail_from_longer_stream() {
+        let short = IntStream { data: vec![1, 2], position: 0 };
+        let long = IntStream { data: vec![10, 20, 30, 40], position: 0 };
+        let mut mixed = short.interleave(long);
+        assert_eq!(mixed.size_hint(), (6, Some(6)));
+        let mut seen = Vec::new();
+        while let Some((x, position)) = mixed.next_with_position() {
+            seen.push((*x, position));
+        }
+        assert_eq!(seen, vec![(1, 0), (10, 0), (2, 1), (20, 1), (30, 2), (40, 3)]);
+
+        mixed.reset_position();
+        assert_eq!(mixed.next(), Some(&1));
+    }
+
+    #[test]
+    fn test_round_robin_three_string_streams() {
+        let mut merged = RoundRobin::new([
+            string_stream("a1 a2 a3"),
+            string_stream("b1"),
+            string_stream("c1 c2"),
+        ]);
+        let mut seen = Vec::new();
+        while let Some(word) = merged.next() {
+            seen.push(word.to_string());
+        }
+        assert_eq!(seen, vec!["a1", "b1", "c1", "a2", "c2", "a3"]);
+        assert_eq!(merged.next(), None);
+    }
+
+    #[test]
+    fn test_round_robin_all_exhausted() {
+        let mut empty = RoundRobin::new([string_stream(""), string_stream("   ")]);
+        assert_eq!(empty.next(), None);
+
+        let mut none: RoundRobin<StringStream, 0> = RoundRobin::new([]);
+        assert_eq!(none.next(), None);
+        assert_eq!(none.size_hint(), (0, Some(0)));
+    }
+
+    // Yields "ok\n", then fails every read
+    struct FailingReader {
+        served: bool,
+    }
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.served {
+                return Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection lost"));
+            }
+            self.served = true;
+            buf[..3].copy_from_slice(b"ok\n");
+            Ok(3)
+        }
+    }
+
+    fn read_all_lines<T: TryStream>(stream: &mut T) -> Result<Vec<String>, T::Error>
+    where
+        for<'a> T::Ok<'a>: ToString,
+    {
+        let mut lines = Vec::new();
+        while let Some(line) = stream.try_next()? {
+            lines.push(line.to_string());
+        }
+        Ok(lines)
+    }
+
+    #[test]
+    fn test_fallible_line_stream_with_cursor() {
+        let mut lines = FallibleLineStream::new(std::io::Cursor::new("one\r\ntwo\n\nthree"));
+        assert_eq!(read_all_lines(&mut lines).unwrap(), vec!["one", "two", "", "three"]);
+        assert!(matches!(lines.try_next(), Ok(None)));
+    }
+
+    #[test]
+    fn test_fallible_line_stream_surfaces_io_error() {
+        let mut lines = FallibleLineStream::new(std::io::BufReader::new(FailingReader { served: false }));
+        assert_eq!(lines.try_next().unwrap(), Some("ok"));
+        let error = lines.try_next().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+
+        let mut again = FallibleLineStream::new(std::io::BufReader::new(FailingReader { served: false }));
+        assert_eq!(read_all_lines(&mut again).unwrap_err().kind(), std::io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn test_into_try_stream_never_fails() {
+        let mut words = string_stream("plain stream").into_try_stream();
+        assert_eq!(read_all_lines(&mut words), Ok(vec!["plain".to_string(), "stream".to_string()]));
+        assert_eq!(words.try_next(), Ok(None));
+    }
+
+    fn rle_runs(data: Vec<i32>) -> Vec<(i32, usize)> {
+        IntStream { data, position: 0 }.rle().collect_owned(|run| run)
+    }
+
+    #[test]
+    fn test_rle_collapses_runs() {
+        assert_eq!(rle_runs(vec![1, 1, 2, 3, 3, 3]), vec![(1, 2), (2, 1), (3, 3)]);
+
+        let mut words = string_stream("a a b a").rle();
+        assert_eq!(words.next_with_position(), Some((("a".to_string(), 2), 0)));
+        assert_eq!(words.next_with_position(), Some((("b".to_string(), 1), 4)));
+        assert_eq!(words.next_with_position(), Some((("a".to_string(), 1), 6)));
+        assert_eq!(words.next(), None);
+
+        words.reset_position();
+        assert_eq!(words.next(), Some(("a".to_string(), 2)));
+    }
+
+    #[test]
+    fn test_rle_single_and_empty() {
+        assert_eq!(rle_runs(vec![7]), vec![(7, 1)]);
+        assert!(rle_runs(vec![]).is_empty());
+
+        let mut empty = IntStream { data: vec![], position: 0 }.rle();
+        assert_eq!(empty.size_hint(), (0, Some(0)));
+        assert_eq!(empty.next(), None);
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn test_collect_map_indexes_words_by_offset() {
+        let index = string_stream("red green blue")
+            .with_metadata()
+            .collect_map(|token| (token.byte_offset, token.text.to_string()));
+        assert_eq!(index.len(), 3);
+        assert_eq!(index[&0], "red");
+        assert_eq!(index[&4], "green");
+        assert_eq!(index[&10], "blue");
+    }
+
+    #[test]
+    fn test_collect_map_last_duplicate_wins() {
+        let last_seen = string_stream("a bb a ccc").enumerate().collect_map(|(index, word)| (word.to_string(), index));
+        assert_eq!(last_seen.len(), 3);
+        assert_eq!(last_seen["a"], 2);
+        assert_eq!(last_seen["bb"], 1);
+        assert_eq!(last_seen["ccc"], 3);
+
+        let empty: HashMap<i32, i32> = seven_ints().take(0).collect_map(|x: &i32| (*x, *x));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_restore_rereads_identically() {
+        let mut stream = string_stream("let x = 1 ;");
+        stream.next();
+        let checkpoint = stream.checkpoint();
+        assert_eq!(checkpoint.position(), 4);
+        let mut first_pass = Vec::new();
+        while let Some((word, position)) = stream.next_with_position() {
+            first_pass.push((word.to_string(), position));
+        }
+        assert_eq!(stream.next(), None);
+
+        stream.restore(checkpoint.clone()).unwrap();
+        let mut second_pass = Vec::new();
+        while let Some((word, position)) = stream.next_with_position() {
+            second_pass.push((word.to_string(), position));
+        }
+        assert_eq!(first_pass, second_pass);
+        assert_eq!(first_pass.len(), 4);
+
+        // Restoring the end-of-input flag too: the trailing empty token is not repeated
+        let mut csv = StringStream::with_delimiter("a,", ',').keep_empty(true);
+        csv.next();
+        csv.next();
+        let done = csv.checkpoint();
+        csv.reset_position();
+        csv.restore(done).unwrap();
+        assert_eq!(csv.next(), None);
+    }
+
+    #[test]
+    fn test_checkpoint_index_streams() {
+        let mut ints = seven_ints();
+        ints.next();
+        ints.next();
+        let checkpoint = ints.checkpoint();
+        while ints.next().is_some() {}
+        ints.restore(checkpoint).unwrap();
+        assert_eq!(ints.next(), Some(&3));
+
+        let data = [10, 20, 30];
+        let mut slice = SliceStream::from_slice(&data);
+        slice.next();
+        let checkpoint = slice.checkpoint();
+        // Another cursor over the same slice shares the checkpoint's source
+        let mut other = SliceStream::from_slice(&data);
+        other.restore(checkpoint).unwrap();
+        assert_eq!(other.next(), Some(&20));
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_other_streams() {
+        let first = string_stream("same text");
+        let mut second = string_stream("same text");
+        assert_eq!(second.restore(first.checkpoint()), Err(CheckpointError::ForeignStream));
+
+        let mut clone = first.clone();
+        assert_eq!(clone.restore(first.checkpoint()), Err(CheckpointError::ForeignStream));
+
+        let mut ints = seven_ints();
+        let stale = ints.checkpoint();
+        ints.data.push(8);
+        ints.next();
+        assert_eq!(ints.restore(stale), Err(CheckpointError::ForeignStream));
+        assert_eq!(ints.position, 1);
+
+        let data = [1, 2, 3];
+        let mut full = SliceStream::from_slice(&data);
+        let prefix = SliceStream::from_slice(&data[..2]);
+        assert_eq!(full.restore(prefix.checkpoint()), Err(CheckpointError::ForeignStream));
+    }
+
+    #[test]
+    fn test_reset_position_chains_into_next() {
+        let mut stream = string_stream("first second");
+        while stream.next().is_some() {}
+        assert_eq!(stream.reset_position().next(), Some("first"));
+        assert_eq!(stream.reset_position().next_with_position(), Some(("first", 0)));
+
+        let mut ints = IntStream { data: vec![5, 6], position: 2 };
+        assert_eq!(ints.next(), None);
+        assert_eq!(ints.reset_position().next(), Some(&5));
+    }
+
+    #[test]
+    fn test_exact_boundary_positions() {
+        // The last token ends exactly at data.len(), with no trailing separator
+        let mut stream = string_stream("ab cd");
+        assert_eq!(stream.next_with_position(), Some(("ab", 0)));
+        assert_eq!(stream.next_with_position(), Some(("cd", 3)));
+        assert_eq!(stream.position, 5);
+        assert_eq!(stream.next_with_position(), None);
+        assert_eq!(stream.position, 5);
+
+        let mut ints = IntStream { data: vec![1, 2, 3], position: 2 };
+        assert_eq!(ints.next_with_position(), Some((&3, 2)));
+        assert_eq!(ints.next_with_position(), None);
+        assert_eq!(ints.position, 3);
+
+        let mut empty = IntStream { data: vec![], position: 0 };
+        assert_eq!(empty.next_with_position(), None);
+        assert_eq!(empty.reset_position().next(), None);
+    }
+
+    // Property-based checks over generated input, mostly arbitrary Unicode
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        // Separators and multi-byte characters are over-represented, so most inputs
+        // contain several tokens and char boundaries that are not byte boundaries
+        fn tokenish() -> impl Strategy<Value = String> {
+            prop_oneof![any::<String>(), "[a-zé안녕 ,\\t\\n\u{3000}]{0,40}"]
+        }
+
+        fn drain(stream: &mut StringStream) -> Vec<(String, usize)> {
+            let mut tokens = Vec::new();
+            while let Some((token, position)) = stream.next_with_position() {
+                tokens.push((token.to_string(), position));
+            }
+            tokens
+        }
+
+        proptest! {
+            #[test]
+            fn tokens_are_subslices_at_their_positions(data in tokenish()) {
+                let mut stream = StringStream::new(data.clone());
+                let mut previous_end = 0;
+                while let Some((token, position)) = stream.next_with_position() {
+                    prop_assert!(!token.is_empty());
+                    prop_assert!(position >= previous_end);
+                    prop_assert_eq!(&data[position..position + token.len()], token);
+                    previous_end = position + token.len();
+                }
+                prop_assert_eq!(stream.next(), None);
+            }
+
+            #[test]
+            fn whitespace_tokens_match_split_whitespace(data in tokenish()) {
+                let tokens: Vec<String> = drain(&mut StringStream::new(data.clone())).into_iter().map(|(t, _)| t).collect();
+                prop_assert_eq!(tokens, data.split_whitespace().collect::<Vec<_>>());
+            }
+
+            #[test]
+            fn keep_empty_tokens_match_str_split(data in tokenish()) {
+                let mut stream = StringStream::with_delimiter(data.clone(), ',').keep_empty(true);
+                let tokens: Vec<String> = drain(&mut stream).into_iter().map(|(t, _)| t).collect();
+                prop_assert_eq!(tokens, data.split(',').collect::<Vec<_>>());
+            }
+
+            #[test]
+            fn reset_reproduces_the_same_tokens(data in tokenish(), keep_empty in any::<bool>()) {
+                let mut stream = StringStream::with_delimiter(data, ' ').keep_empty(keep_empty);
+                let first = drain(&mut stream);
+                let second = drain(stream.reset_position());
+                prop_assert_eq!(first, second);
+            }
+
+            #[test]
+            fn str_stream_agrees_with_string_stream(data in tokenish()) {
+                let owned = StringStream::new(data.clone());
+                let mut borrowed = owned.as_str_stream();
+                let mut expected = StringStream::new(data);
+                while let Some((token, position)) = expected.next_with_position() {
+                    prop_assert_eq!(borrowed.next_with_position(), Some((token, position)));
+                }
+                prop_assert_eq!(borrowed.next(), None);
+            }
+
+            #[test]
+            fn arbitrary_positions_never_panic(data in tokenish(), position in 0usize..64) {
+                let mut stream = StringStream::new(data.clone());
+                stream.position = position;
+                while let Some(token) = stream.next() {
+                    prop_assert!(data.contains(token));
+                }
+                let mut stream = StringStream::new(data.clone());
+                let valid = position <= data.len() && data.is_char_boundary(position);
+                prop_assert_eq!(stream.seek(position).is_ok(), valid);
+            }
+
+            #[test]
+            fn int_stream_positions_and_reset(data in proptest::collection::vec(any::<i32>(), 0..32)) {
+                let mut stream = IntStream { data: data.clone(), position: 0 };
+                let mut seen = Vec::new();
+                while let Some((value, position)) = stream.next_with_position() {
+                    prop_assert_eq!(position, seen.len());
+                    seen.push(*value);
+                }
+                prop_assert_eq!(&seen, &data);
+                prop_assert_eq!(stream.reset_position().size_hint(), (data.len(), Some(data.len())));
+                prop_assert_eq!(stream.next().copied(), data.first().copied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_inspect_sees_every_item_once() {
+        use std::cell::RefCell;
+
+        // The for<'a> bounds make adapters 'static (see the note on terminal operations),
+        // so the closure records into shared state rather than borrowing a local
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&seen);
+        let total = seven_ints()
+            .inspect(move |x: &&i32| log.borrow_mut().push(**x))
+            .filter(|x: &&i32| **x % 2 == 0)
+            .fold(0, |acc, x: &i32| acc + x);
+        assert_eq!(total, 12);
+        assert_eq!(*seen.borrow(), vec![1, 2, 3, 4, 5, 6, 7]);
+
+        let words = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&words);
+        let mut stream = string_stream("a b").inspect(move |word: &&str| log.borrow_mut().push(word.to_string()));
+        assert_eq!(stream.next_with_position(), Some(("a", 0)));
+        assert_eq!(stream.next(), Some("b"));
+        assert_eq!(stream.next(), None);
+        assert_eq!(*words.borrow(), vec!["a", "b"]);
+    }
+}
@@ -5,6 +5,7 @@ pub mod with_lifetime;
 pub mod typesafe_builder;
 pub mod gat;
 pub mod functor_monad;
+pub mod sparse_vec;
 
 pub use const_generic::*;
 pub use state_machine::*;
@@ -13,3 +14,4 @@ pub use with_lifetime::*;
 pub use typesafe_builder::*;
 pub use gat::*;
 pub use functor_monad::*;
+pub use sparse_vec::*;
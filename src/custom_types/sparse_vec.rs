@@ -0,0 +1,120 @@
+//
+// Sparse Vectors and Index-Preserving Containers
+//
+// -- A mostly-empty indexed collection. Its "shape" is the set of occupied indices
+//    (plus the logical length), not the number of stored values, and mapping keeps that shape.
+use std::collections::BTreeMap;
+
+use crate::custom_types::container::Container;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseVec<T> {
+    len: usize,
+    entries: BTreeMap<usize, T>,
+}
+
+impl<T> SparseVec<T> {
+    /// Create an empty sparse vector with the given logical length
+    pub fn new(len: usize) -> Self {
+        SparseVec {
+            len,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Store a value at `index`, returning the value previously stored there
+    pub fn insert(&mut self, index: usize, value: T) -> Result<Option<T>, &'static str> {
+        if index < self.len {
+            Ok(self.entries.insert(index, value))
+        } else {
+            Err("Index out of bounds")
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.entries.get(&index)
+    }
+
+    /// Logical length, including empty slots
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of occupied slots
+    pub fn stored(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Fraction of slots that hold a value (0.0 for a zero-length vector)
+    pub fn density(&self) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            self.entries.len() as f64 / self.len as f64
+        }
+    }
+
+    /// Occupied indices in ascending order
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// Occupied (index, value) pairs in ascending index order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        self.entries.iter().map(|(&index, value)| (index, value))
+    }
+}
+
+// Mapping transforms the values while keeping every index and the logical length
+impl<T> Container for SparseVec<T> {
+    type Item = T;
+    type Mapped<U> = SparseVec<U>;
+
+    fn map<U, F: FnMut(&Self::Item) -> U>(self, mut f: F) -> Self::Mapped<U> {
+        SparseVec {
+            len: self.len,
+            entries: self.entries.iter().map(|(&index, value)| (index, f(value))).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut sparse = SparseVec::new(10);
+        assert_eq!(sparse.insert(3, "three"), Ok(None));
+        assert_eq!(sparse.insert(3, "THREE"), Ok(Some("three")));
+        assert_eq!(sparse.insert(10, "ten"), Err("Index out of bounds"));
+
+        assert_eq!(sparse.get(3), Some(&"THREE"));
+        assert_eq!(sparse.get(4), None);
+        assert_eq!(sparse.stored(), 1);
+        assert_eq!(sparse.density(), 0.1);
+    }
+
+    #[test]
+    fn test_map_preserves_indices_and_density() {
+        let mut sparse = SparseVec::new(200);
+        sparse.insert(2, 20).unwrap();
+        sparse.insert(100, 1000).unwrap();
+        sparse.insert(7, 70).unwrap();
+        let indices: Vec<usize> = sparse.indices().collect();
+        let density = sparse.density();
+
+        let mapped: SparseVec<String> = sparse.map(|x| format!("v{}", x));
+
+        assert_eq!(mapped.indices().collect::<Vec<_>>(), indices);
+        assert_eq!(mapped.indices().collect::<Vec<_>>(), vec![2, 7, 100]);
+        assert_eq!(mapped.density(), density);
+        assert_eq!(mapped.len(), 200);
+        assert_eq!(mapped.get(7).map(String::as_str), Some("v70"));
+        assert_eq!(mapped.get(100).map(String::as_str), Some("v1000"));
+    }
+}
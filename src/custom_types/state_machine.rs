@@ -4,7 +4,13 @@
 // -- Using PhantomData to encode state transitions at the type level
 // This ensures state machine invariants are checked at compile time
 
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::custom_types::gat::Stream;
 
 // State types - these exist only at the type level
 pub struct Uninitialized;
@@ -13,27 +19,342 @@ pub struct Running;
 pub struct Stopped;
 
 // Task representation
+// -- Ordered by priority descending, then id ascending, then name, so sorting a list of
+//    tasks puts them in a sensible run order. The scheduler's queue breaks priority ties
+//    by insertion order instead.
+// -- Eq compares exactly the same three keys as Ord, so `a == b` whenever
+//    `a.cmp(&b) == Equal`. The other fields say how a task runs, not which task it is.
+// -- may_fail and max_retries only matter to execute_next_with_retry; a task that
+//    may fail gets up to max_retries extra attempts before it is marked failed.
+// -- depends_on lists ids of tasks that must have executed before this one may run.
+// -- payload is whatever domain data the task carries (e.g. a command enum). It defaults
+//    to (), and it does not take part in the run order.
+// -- action is optional work for Scheduler::run_all; other ways of executing ignore it.
 #[derive(Debug, Clone)]
-pub struct Task {
+pub struct Task<P = ()> {
     pub id: u32,
     pub name: String,
     pub priority: u8,
+    pub may_fail: bool,
+    pub max_retries: u8,
+    pub depends_on: Vec<u32>,
+    pub payload: P,
+    pub action: Option<TaskAction>,
 }
 
 impl Task {
     pub fn new(id: u32, name: &str, priority: u8) -> Self {
+        Task::with_payload(id, name, priority, ())
+    }
+
+    /// Create a task whose work is `action`, run by Scheduler::run_all
+    pub fn with_action(id: u32, name: &str, priority: u8, action: ActionFn) -> Self {
+        let mut task = Task::new(id, name, priority);
+        task.action = Some(TaskAction::new(action));
+        task
+    }
+}
+
+// Only the payload-free task has a default, so Task::default() needs no annotation
+impl Default for Task {
+    fn default() -> Self {
+        Task::new(0, "", 0)
+    }
+}
+
+impl<P> Task<P> {
+    /// Create a task that carries `payload`
+    pub fn with_payload(id: u32, name: &str, priority: u8, payload: P) -> Self {
         Task {
             id,
             name: name.to_string(),
             priority,
+            may_fail: false,
+            max_retries: 0,
+            depends_on: Vec::new(),
+            payload,
+            action: None,
         }
     }
+
+    /// Mark the task as one that can fail, allowing `max_retries` extra attempts
+    pub fn with_retries(mut self, max_retries: u8) -> Self {
+        self.may_fail = true;
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Replace the id, keeping the other fields
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Replace the name, keeping the other fields
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Replace the priority, keeping the other fields
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl<P> PartialEq for Task<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.id == other.id && self.name == other.name
+    }
+}
+
+impl<P> Eq for Task<P> {}
+
+impl<P> Ord for Task<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority
+            .cmp(&self.priority)
+            .then_with(|| self.id.cmp(&other.id))
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl<P> PartialOrd for Task<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Closure run by Scheduler::run_all; Ok carries its output and Err its error message
+pub type ActionFn = Box<dyn FnOnce() -> Result<String, String>>;
+
+// A task's action, run at most once
+// -- Shared behind Rc so Task stays Clone: the copies kept in the history and completed
+//    list see the same cell, and once the closure has been taken every copy reports it spent.
+//    Two actions are equal only when they are the same cell; Task equality ignores the
+//    action altogether, like the payload.
+#[derive(Clone)]
+pub struct TaskAction(Rc<RefCell<Option<ActionFn>>>);
+
+impl TaskAction {
+    pub fn new(action: ActionFn) -> Self {
+        TaskAction(Rc::new(RefCell::new(Some(action))))
+    }
+
+    /// Whether the closure has already been run
+    pub fn is_spent(&self) -> bool {
+        self.0.borrow().is_none()
+    }
+
+    fn take(&self) -> Option<ActionFn> {
+        self.0.borrow_mut().take()
+    }
+}
+
+impl std::fmt::Debug for TaskAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.is_spent() { "TaskAction(spent)" } else { "TaskAction(pending)" })
+    }
+}
+
+impl PartialEq for TaskAction {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for TaskAction {}
+
+// Entry in the scheduler's max-heap
+// -- The greatest entry runs next: highest priority first, and among equal priorities
+//    the one queued earliest (lowest insertion number), so equal tasks run FIFO.
+// -- Equality follows the same two keys, so the payload needs no Eq or Ord of its own.
+#[derive(Debug, Clone)]
+struct QueuedTask<P> {
+    task: Task<P>,
+    insertion: u64,
+}
+
+impl<P> PartialEq for QueuedTask<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P> Eq for QueuedTask<P> {}
+
+impl<P> Ord for QueuedTask<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.task
+            .priority
+            .cmp(&other.task.priority)
+            .then_with(|| other.insertion.cmp(&self.insertion))
+    }
+}
+
+impl<P> PartialOrd for QueuedTask<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Queued task held back until its dependencies have executed
+// -- unmet counts the distinct dependencies still outstanding; the task moves to the heap
+//    when it drops to zero.
+#[derive(Debug, Clone)]
+struct WaitingTask<P> {
+    queued: QueuedTask<P>,
+    unmet: usize,
+}
+
+// Priority distribution of the queued tasks; the priority fields are None when empty
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchedulerStats {
+    pub count: usize,
+    pub max_priority: Option<u8>,
+    pub min_priority: Option<u8>,
+    pub avg_priority: Option<f64>,
+}
+
+// What happened to a task when the scheduler ran it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOutcome {
+    Completed,
+    // The attempt failed and the task was re-queued; attempts count from 1
+    Retrying { attempt: u8 },
+    // The attempt failed with no retries left
+    Failed,
+}
+
+// What a task's action produced under Scheduler::run_all
+// -- Unlike TaskOutcome this keeps the action's own output or error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOutcome {
+    Succeeded { task_id: u32, output: String },
+    Failed { task_id: u32, error: String },
+    // The task had no action (or it had already run); it still counts as completed
+    Skipped { task_id: u32 },
+    // The task never ran because `failed_dependency`, which it waits on directly or
+    // through other tasks, failed; it goes to the failed list
+    Blocked { task_id: u32, failed_dependency: u32 },
+}
+
+// Record of one execution attempt, returned by the *_result methods and kept in the
+// scheduler's history
+// -- sequence counts every attempt this scheduler has made, from 0, and is the record's
+//    index in history(); a retried task shows up once per attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutedTask<P = ()> {
+    pub task: Task<P>,
+    pub sequence: usize,
+    pub outcome: TaskOutcome,
+}
+
+// Why a scheduler operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulerError {
+    TaskNotFound(u32),
+}
+
+impl std::fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::TaskNotFound(id) => write!(f, "no queued task with id {}", id),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+// Why try_start refused a dependency graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyError {
+    // `task` depends on `missing`, which is neither queued nor already executed
+    UnknownTask { task: u32, missing: u32 },
+    // Each id depends on the next, and the last one on the first
+    Cycle(Vec<u32>),
+}
+
+impl std::fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependencyError::UnknownTask { task, missing } => {
+                write!(f, "task {} depends on unknown task {}", task, missing)
+            }
+            DependencyError::Cycle(ids) => write!(f, "dependency cycle between tasks {:?}", ids),
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+// Depth-first search for a cycle reachable from `id`
+// -- `path` holds the ids currently being visited, so meeting one of them again closes
+//    a cycle; `done` holds ids already known to lead to no cycle.
+fn find_cycle_from(
+    id: u32,
+    deps: &HashMap<u32, &[u32]>,
+    path: &mut Vec<u32>,
+    done: &mut HashSet<u32>,
+) -> Option<Vec<u32>> {
+    if let Some(index) = path.iter().position(|&visiting| visiting == id) {
+        return Some(path[index..].to_vec());
+    }
+    if done.contains(&id) {
+        return None;
+    }
+    path.push(id);
+    for &dep in deps.get(&id).copied().unwrap_or_default() {
+        if let Some(cycle) = find_cycle_from(dep, deps, path, done) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    done.insert(id);
+    None
+}
+
+// Deterministic stand-in for a flaky task: splitmix64 over (seed, id, attempt),
+// failing for roughly half of the inputs
+fn attempt_fails(seed: u64, task_id: u32, attempt: u8) -> bool {
+    let mut z = seed ^ ((task_id as u64) << 32) ^ attempt as u64;
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (z ^ (z >> 31)).is_multiple_of(2)
 }
 
+// Callback registered with Scheduler::on_execute
+type ExecuteHook<P> = Box<dyn FnMut(&Task<P>)>;
+
 // Scheduler with phantom type parameter for state
-pub struct Scheduler<State> {
-    tasks: Vec<Task>,
-    current_task: Option<Task>,
+// -- P is the task payload type; every state transition carries it through unchanged.
+pub struct Scheduler<State, P = ()> {
+    // Tasks whose dependencies have all executed; adding is O(log n) and the next task
+    // is always at the top
+    tasks: BinaryHeap<QueuedTask<P>>,
+    // Tasks still waiting on dependencies, by insertion number
+    waiting: HashMap<u64, WaitingTask<P>>,
+    // Insertion numbers of the waiting tasks that depend on each task id
+    // -- Entries for tasks removed from the queue are left behind and skipped on release.
+    dependents: HashMap<u32, Vec<u64>>,
+    // Ids of every task executed so far, for O(1) dependency checks
+    executed: HashSet<u32>,
+    // Insertion number for the next queued task
+    next_insertion: u64,
+    current_task: Option<Task<P>>,
+    // Every task executed so far, in execution order
+    completed: Vec<Task<P>>,
+    // Tasks that used up their retries or whose action failed, plus the tasks blocked
+    // behind them, in the order they gave up
+    failed: Vec<Task<P>>,
+    // Failed attempts so far, by task id
+    retries: HashMap<u32, u8>,
+    // Callbacks registered with on_execute, called in registration order
+    hooks: Vec<ExecuteHook<P>>,
+    // Every execution attempt, oldest first; kept by restart, cleared by reset
+    history: Vec<ExecutedTask<P>>,
     _state: PhantomData<State>,
 }
 
@@ -41,136 +362,667 @@ pub struct Scheduler<State> {
 impl Scheduler<Uninitialized> {
     /// Create a new scheduler in uninitialized state
     pub fn new() -> Self {
+        Scheduler::with_payloads()
+    }
+}
+
+impl<P> Scheduler<Uninitialized, P> {
+    /// Create a new scheduler in uninitialized state whose tasks carry a `P` payload
+    pub fn with_payloads() -> Self {
         println!("📋 Creating new scheduler...");
         Scheduler {
-            tasks: Vec::new(),
+            tasks: BinaryHeap::new(),
+            waiting: HashMap::new(),
+            dependents: HashMap::new(),
+            executed: HashSet::new(),
+            next_insertion: 0,
             current_task: None,
+            completed: Vec::new(),
+            failed: Vec::new(),
+            retries: HashMap::new(),
+            hooks: Vec::new(),
+            history: Vec::new(),
             _state: PhantomData,
         }
     }
 
     /// Initialize the scheduler - transitions to Initialized state
-    pub fn initialize(self) -> Scheduler<Initialized> {
+    pub fn initialize(self) -> Scheduler<Initialized, P> {
         println!("🔧 Initializing scheduler...");
         Scheduler {
             tasks: self.tasks,
+            waiting: self.waiting,
+            dependents: self.dependents,
+            executed: self.executed,
+            next_insertion: self.next_insertion,
             current_task: self.current_task,
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            hooks: self.hooks,
+            history: self.history,
             _state: PhantomData,
         }
     }
 }
 
+impl<P> Default for Scheduler<Uninitialized, P> {
+    fn default() -> Self {
+        Self::with_payloads()
+    }
+}
+
 // Implementation for Initialized state
-impl Scheduler<Initialized> {
+impl<P> Scheduler<Initialized, P> {
     /// Add a task to the scheduler
-    pub fn add_task(mut self, task: Task) -> Self {
+    pub fn add_task(mut self, task: Task<P>) -> Self {
         println!("   ➕ Adding task: {} (priority: {})", task.name, task.priority);
-        self.tasks.push(task);
+        self.enqueue(task);
+        self
+    }
+
+    /// Append the queued tasks of `other` after this scheduler's own.
+    ///
+    /// Only the queue and the ids of the tasks `other` has executed are taken from it, so
+    /// its tasks' dependencies stay satisfied; its history, failures and hooks are dropped.
+    /// Both queues keep their insertion order, so among equal priorities this scheduler's
+    /// tasks still run first.
+    pub fn merge(mut self, other: Scheduler<Initialized, P>) -> Scheduler<Initialized, P> {
+        println!("   🔗 Merging {} tasks into {} queued tasks", other.remaining_tasks(), self.remaining_tasks());
+        self.executed.extend(other.executed);
+        let mut incoming = other.tasks.into_vec();
+        incoming.extend(other.waiting.into_values().map(|waiting| waiting.queued));
+        incoming.sort_by_key(|queued| queued.insertion);
+        for queued in incoming {
+            self.enqueue(queued.task);
+        }
+        self
+    }
+
+    /// Add a task that may only run after every task in `depends_on` has executed
+    pub fn add_task_with_deps(self, mut task: Task<P>, depends_on: &[u32]) -> Self {
+        task.depends_on = depends_on.to_vec();
+        self.add_task(task)
+    }
+
+    /// Take the queued task with `id` out of the scheduler, if there is one
+    pub fn remove_task(mut self, id: u32) -> (Self, Option<Task<P>>) {
+        let mut queued = std::mem::take(&mut self.tasks).into_vec();
+        let removed = queued
+            .iter()
+            .position(|q| q.task.id == id)
+            .map(|index| queued.swap_remove(index).task);
+        self.tasks = BinaryHeap::from(queued);
+        let removed = removed.or_else(|| {
+            let insertion = self.waiting.iter().find(|(_, w)| w.queued.task.id == id).map(|(&i, _)| i)?;
+            self.waiting.remove(&insertion).map(|waiting| waiting.queued.task)
+        });
+        (self, removed)
+    }
+
+    /// Drop every queued task
+    pub fn clear_tasks(mut self) -> Self {
+        println!("   🧹 Clearing {} tasks", self.remaining_tasks());
+        self.tasks.clear();
+        self.waiting.clear();
+        self.dependents.clear();
         self
     }
 
+    /// Look up a queued task by id
+    pub fn find_task(&self, id: u32) -> Option<&Task<P>> {
+        self.queued().map(|q| &q.task).find(|task| task.id == id)
+    }
+
+    /// Change the priority of the queued task with `id`. It keeps its place among
+    /// tasks of equal priority according to when it was first added.
+    ///
+    /// The scheduler is consumed either way; use find_task first to keep it on failure.
+    pub fn set_priority(mut self, id: u32, new_priority: u8) -> Result<Self, SchedulerError> {
+        let mut queued = std::mem::take(&mut self.tasks).into_vec();
+        let entry = queued
+            .iter_mut()
+            .chain(self.waiting.values_mut().map(|waiting| &mut waiting.queued))
+            .find(|q| q.task.id == id)
+            .ok_or(SchedulerError::TaskNotFound(id))?;
+        entry.task.priority = new_priority;
+        self.tasks = BinaryHeap::from(queued);
+        Ok(self)
+    }
+
+    /// Start after checking the dependency graph: every dependency must be queued or
+    /// already executed, and there must be no cycles.
+    pub fn try_start(self) -> Result<Scheduler<Running, P>, DependencyError> {
+        self.check_dependencies()?;
+        Ok(self.start())
+    }
+
+    fn check_dependencies(&self) -> Result<(), DependencyError> {
+        let known: HashSet<u32> = self
+            .queued()
+            .map(|q| q.task.id)
+            .chain(self.executed.iter().copied())
+            .collect();
+        let mut queued: Vec<&Task<P>> = self.queued().map(|q| &q.task).collect();
+        queued.sort_by_key(|task| task.id);
+        for task in &queued {
+            if let Some(&missing) = task.depends_on.iter().find(|dep| !known.contains(dep)) {
+                return Err(DependencyError::UnknownTask { task: task.id, missing });
+            }
+        }
+
+        let deps: HashMap<u32, &[u32]> = queued.iter().map(|t| (t.id, t.depends_on.as_slice())).collect();
+        let mut done = HashSet::new();
+        for task in &queued {
+            if let Some(cycle) = find_cycle_from(task.id, &deps, &mut Vec::new(), &mut done) {
+                return Err(DependencyError::Cycle(cycle));
+            }
+        }
+        Ok(())
+    }
+
     /// Start the scheduler - transitions to Running state
-    pub fn start(mut self) -> Scheduler<Running> {
-        println!("    🚀 Starting scheduler with {} tasks...", self.tasks.len());
-        
-        // Sort tasks by priority (higher priority first)
-        self.tasks.sort_by(|a, b| b.priority.cmp(&a.priority));
+    ///
+    /// Dependencies are not validated; a task whose dependencies can never run just
+    /// stays queued. Use try_start when tasks have dependencies.
+    pub fn start(self) -> Scheduler<Running, P> {
+        println!("    🚀 Starting scheduler with {} tasks...", self.remaining_tasks());
         
+        // The heap already keeps the highest priority on top, so there is nothing to sort
         Scheduler {
             tasks: self.tasks,
+            waiting: self.waiting,
+            dependents: self.dependents,
+            executed: self.executed,
+            next_insertion: self.next_insertion,
             current_task: self.current_task,
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            hooks: self.hooks,
+            history: self.history,
             _state: PhantomData,
         }
     }
 
     /// Get the number of tasks
     pub fn task_count(&self) -> usize {
-        self.tasks.len()
+        self.remaining_tasks()
+    }
+
+    /// Summarize the priorities of the queued tasks
+    pub fn stats(&self) -> SchedulerStats {
+        self.queue_stats()
     }
 }
 
 // Implementation for Running state
-impl Scheduler<Running> {
+// -- Executed tasks are kept in the history and completed list as well as handed back,
+//    so running needs P: Clone.
+impl<P: Clone> Scheduler<Running, P> {
     /// Execute the next task
-    pub fn execute_next(mut self) -> Self {
-        if let Some(task) = self.tasks.pop() {
+    pub fn execute_next(self) -> Self {
+        self.execute_next_result().0
+    }
+
+    /// Execute the next task and report what ran; None when no task is ready to run
+    pub fn execute_next_result(mut self) -> (Self, Option<ExecutedTask<P>>) {
+        if self.run_next().is_none() {
+            self.report_idle();
+            return (self, None);
+        }
+        let executed = self.history.last().cloned();
+        (self, executed)
+    }
+
+    /// Register a callback that runs with every task as it executes, after any earlier
+    /// ones. Hooks stay registered across pause/stop/restart; reset drops them.
+    pub fn on_execute<F: FnMut(&Task<P>) + 'static>(mut self, hook: F) -> Scheduler<Running, P> {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    // Shared by execute_next and ExecutionStream: pop, record, and lend the task
+    fn run_next(&mut self) -> Option<&Task<P>> {
+        let task = self.tasks.pop()?.task;
+        println!("⚡ Executing task: {} (ID: {})", task.name, task.id);
+        self.complete(task);
+        self.current_task.as_ref()
+    }
+
+    // Call the hooks, record `task` as completed and current, and release the tasks that
+    // were only waiting on it
+    fn complete(&mut self, task: Task<P>) {
+        for hook in &mut self.hooks {
+            hook(&task);
+        }
+        self.record(task.clone(), TaskOutcome::Completed);
+        if self.executed.insert(task.id) {
+            self.release_dependents(task.id);
+        }
+        self.completed.push(task.clone());
+        self.current_task = Some(task);
+    }
+
+    // Count `id` as executed for every task waiting on it, moving those with nothing
+    // left to wait for onto the heap
+    fn release_dependents(&mut self, id: u32) {
+        for insertion in self.dependents.remove(&id).unwrap_or_default() {
+            let Some(waiting) = self.waiting.get_mut(&insertion) else { continue };
+            waiting.unmet -= 1;
+            if waiting.unmet == 0 {
+                let waiting = self.waiting.remove(&insertion).expect("entry was just found");
+                self.tasks.push(waiting.queued);
+            }
+        }
+    }
+
+    /// Run every remaining task's action in execution order, then stop.
+    ///
+    /// A task whose action fails goes to the failed list and the run carries on. The tasks
+    /// depending on it, directly or not, can no longer run: each is reported Blocked right
+    /// after the failure and goes to the failed list too. Tasks without an action are
+    /// Skipped but still complete. Retry settings are ignored.
+    pub fn run_all(self) -> (Scheduler<Stopped, P>, Vec<ActionOutcome>) {
+        self.run_actions(false)
+    }
+
+    /// run_all, but stop at the first failing action and leave the rest, including its
+    /// dependents, queued
+    pub fn run_all_fail_fast(self) -> (Scheduler<Stopped, P>, Vec<ActionOutcome>) {
+        self.run_actions(true)
+    }
+
+    fn run_actions(mut self, fail_fast: bool) -> (Scheduler<Stopped, P>, Vec<ActionOutcome>) {
+        let mut outcomes = Vec::new();
+        while let Some(queued) = self.tasks.pop() {
+            let task = queued.task;
             println!("⚡ Executing task: {} (ID: {})", task.name, task.id);
-            self.current_task = Some(task);
-        } else {
+            let task_id = task.id;
+            let outcome = match task.action.as_ref().and_then(TaskAction::take) {
+                None => ActionOutcome::Skipped { task_id },
+                Some(action) => match action() {
+                    Ok(output) => ActionOutcome::Succeeded { task_id, output },
+                    Err(error) => ActionOutcome::Failed { task_id, error },
+                },
+            };
+
+            let failed = matches!(outcome, ActionOutcome::Failed { .. });
+            if failed {
+                println!("❌ Task {} failed", task.name);
+                self.record(task.clone(), TaskOutcome::Failed);
+                self.failed.push(task);
+            } else {
+                self.complete(task);
+            }
+            outcomes.push(outcome);
+            if failed && fail_fast {
+                println!("🛑 Aborting run with {} tasks left", self.remaining_tasks());
+                break;
+            }
+            if failed {
+                outcomes.extend(self.block_dependents(task_id));
+            }
+        }
+        (self.stop(), outcomes)
+    }
+
+    // Move every task waiting on `failed_id`, directly or through other waiting tasks, to
+    // the failed list, nearest dependents first; none of them can run any more
+    fn block_dependents(&mut self, failed_id: u32) -> Vec<ActionOutcome> {
+        let mut blocked = Vec::new();
+        let mut pending = VecDeque::from([failed_id]);
+        while let Some(id) = pending.pop_front() {
+            for insertion in self.dependents.remove(&id).unwrap_or_default() {
+                let Some(waiting) = self.waiting.remove(&insertion) else { continue };
+                let task = waiting.queued.task;
+                println!("⛔ Task {} blocked by failed task {}", task.name, failed_id);
+                blocked.push(ActionOutcome::Blocked { task_id: task.id, failed_dependency: failed_id });
+                pending.push_back(task.id);
+                self.failed.push(task);
+            }
+        }
+        blocked
+    }
+
+    // Say why nothing ran: either the queue is empty or every task left is waiting on
+    // dependencies that have not executed
+    fn report_idle(&self) {
+        if self.waiting.is_empty() {
             println!("✅ No more tasks to execute");
+        } else {
+            println!("⏳ No runnable tasks: {} waiting on dependencies", self.waiting.len());
+        }
+    }
+
+    /// Execute the next task, simulating failures for tasks that may fail.
+    ///
+    /// Whether an attempt fails is derived from `seed`, the task id and the attempt
+    /// number, so a given seed always replays the same outcome. A failed task goes back
+    /// to the front of the queue until it has used `max_retries` retries; after that it
+    /// moves to the failed list, followed by every task depending on it.
+    pub fn execute_next_with_retry(self, seed: u64) -> Self {
+        self.execute_next_with_retry_result(seed).0
+    }
+
+    /// execute_next_with_retry, reporting the attempt and its outcome
+    pub fn execute_next_with_retry_result(mut self, seed: u64) -> (Self, Option<ExecutedTask<P>>) {
+        let Some(queued) = self.tasks.pop() else {
+            self.report_idle();
+            return (self, None);
+        };
+        let task = queued.task.clone();
+        let attempt = self.retries.get(&task.id).copied().unwrap_or(0);
+        if !(task.may_fail && attempt_fails(seed, task.id, attempt)) {
+            self.retries.remove(&task.id);
+            self.tasks.push(queued);
+            return self.execute_next_result();
+        }
+
+        let outcome = if attempt < task.max_retries {
+            println!("🔁 Task {} failed, retrying ({}/{})", task.name, attempt + 1, task.max_retries);
+            self.retries.insert(task.id, attempt + 1);
+            // Same insertion number, so it stays ahead of equal-priority tasks
+            self.tasks.push(queued);
+            TaskOutcome::Retrying { attempt: attempt + 1 }
+        } else {
+            println!("❌ Task {} failed after {} retries", task.name, attempt);
+            self.retries.remove(&task.id);
+            self.failed.push(task.clone());
+            self.block_dependents(task.id);
+            TaskOutcome::Failed
+        };
+        let executed = self.record(task, outcome);
+        (self, Some(executed))
+    }
+
+    // Append an attempt to the history, numbering it by its position there
+    fn record(&mut self, task: Task<P>, outcome: TaskOutcome) -> ExecutedTask<P> {
+        let executed = ExecutedTask { task, sequence: self.history.len(), outcome };
+        self.history.push(executed.clone());
+        executed
+    }
+
+    /// Every execution attempt so far, oldest first
+    pub fn history(&self) -> &[ExecutedTask<P>] {
+        &self.history
+    }
+
+    /// Failed attempts recorded so far for a queued task
+    pub fn retry_count(&self, task_id: u32) -> u8 {
+        self.retries.get(&task_id).copied().unwrap_or(0)
+    }
+
+    /// Queued tasks in priority order, without executing them. With dependencies the
+    /// actual run order can differ, since a blocked task waits for its dependencies.
+    pub fn task_stream(&self) -> TaskStream<'_, P> {
+        let mut queued: Vec<&QueuedTask<P>> = self.queued().collect();
+        queued.sort_by(|a, b| b.cmp(a));
+        TaskStream { tasks: queued.into_iter().map(|q| &q.task).collect(), position: 0 }
+    }
+
+    /// Turn the scheduler into a stream that executes one task per next()
+    pub fn into_execution_stream(self) -> ExecutionStream<P> {
+        ExecutionStream { scheduler: self, executed: 0 }
+    }
+
+    /// Look at the task execute_next would run, without removing it
+    pub fn peek_next(&self) -> Option<&Task<P>> {
+        self.tasks.peek().map(|queued| &queued.task)
+    }
+
+    /// Execute every remaining task in order, stopping early if the rest are blocked
+    /// on dependencies that can never run
+    pub fn run_to_completion(mut self) -> Self {
+        while self.peek_next().is_some() {
+            self = self.execute_next();
         }
         self
     }
 
     /// Get current running task
-    pub fn current_task(&self) -> Option<&Task> {
+    pub fn current_task(&self) -> Option<&Task<P>> {
         self.current_task.as_ref()
     }
 
-    /// Check if there are more tasks
+    /// Check if there is a task ready to run. Tasks still waiting on dependencies count
+    /// in remaining_tasks but not here.
     pub fn has_tasks(&self) -> bool {
         !self.tasks.is_empty()
     }
 
+    /// Summarize the priorities of the tasks still waiting to run
+    pub fn stats(&self) -> SchedulerStats {
+        self.queue_stats()
+    }
+
     /// Stop the scheduler - transitions to Stopped state
-    pub fn stop(self) -> Scheduler<Stopped> {
+    pub fn stop(self) -> Scheduler<Stopped, P> {
         println!("⏹️ Stopping scheduler...");
         Scheduler {
             tasks: self.tasks,
+            waiting: self.waiting,
+            dependents: self.dependents,
+            executed: self.executed,
+            next_insertion: self.next_insertion,
             current_task: self.current_task,
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            hooks: self.hooks,
+            history: self.history,
             _state: PhantomData,
         }
     }
 
     /// Pause and return to Initialized state for reconfiguration
-    pub fn pause(self) -> Scheduler<Initialized> {
+    pub fn pause(self) -> Scheduler<Initialized, P> {
         println!("⏸️ Pausing scheduler for reconfiguration...");
         Scheduler {
             tasks: self.tasks,
+            waiting: self.waiting,
+            dependents: self.dependents,
+            executed: self.executed,
+            next_insertion: self.next_insertion,
             current_task: None, // Clear current task when pausing
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            hooks: self.hooks,
+            history: self.history,
             _state: PhantomData,
         }
     }
 }
 
 // Implementation for Stopped state
-impl Scheduler<Stopped> {
+impl<P> Scheduler<Stopped, P> {
     /// Get execution summary
     pub fn get_summary(&self) -> String {
         let completed_task = self.current_task.as_ref()
             .map(|t| format!("Last executed: {}", t.name))
             .unwrap_or_else(|| "No tasks executed".to_string());
         
-        format!("📊 Scheduler Summary - Remaining tasks: {}, {}", 
-                self.tasks.len(), completed_task)
+        format!("📊 Scheduler Summary - Remaining tasks: {}, {}, Executions: {}", 
+                self.remaining_tasks(), completed_task, self.history.len())
+    }
+
+    /// Every execution attempt before the stop, oldest first
+    pub fn history(&self) -> &[ExecutedTask<P>] {
+        &self.history
     }
 
     /// Reset to initialized state for reuse
-    pub fn reset(self) -> Scheduler<Initialized> {
+    pub fn reset(self) -> Scheduler<Initialized, P> {
         println!("🔄 Resetting scheduler...");
         Scheduler {
-            tasks: Vec::new(),
+            tasks: BinaryHeap::new(),
+            waiting: HashMap::new(),
+            dependents: HashMap::new(),
+            executed: HashSet::new(),
+            next_insertion: 0,
             current_task: None,
+            completed: Vec::new(),
+            failed: Vec::new(),
+            retries: HashMap::new(),
+            hooks: Vec::new(),
+            history: Vec::new(),
             _state: PhantomData,
         }
     }
 
     /// Restart with current tasks
-    pub fn restart(self) -> Scheduler<Running> {
+    pub fn restart(self) -> Scheduler<Running, P> {
         println!("🔁 Restarting scheduler...");
         Scheduler {
             tasks: self.tasks,
+            waiting: self.waiting,
+            dependents: self.dependents,
+            executed: self.executed,
+            next_insertion: self.next_insertion,
             current_task: None,
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            hooks: self.hooks,
+            history: self.history,
             _state: PhantomData,
         }
     }
 }
 
 // Common implementations for all states
-impl<State> Scheduler<State> {
+impl<State, P> Scheduler<State, P> {
     /// Get remaining task count (available in all states)
     pub fn remaining_tasks(&self) -> usize {
-        self.tasks.len()
+        self.tasks.len() + self.waiting.len()
+    }
+
+    /// Tasks executed so far, oldest first (available in all states)
+    pub fn completed_tasks(&self) -> &[Task<P>] {
+        &self.completed
+    }
+
+    /// Tasks that failed or were blocked by a failed dependency, oldest first (available
+    /// in all states)
+    pub fn failed_tasks(&self) -> &[Task<P>] {
+        &self.failed
+    }
+
+    // Every queued task, ready or waiting, in no particular order
+    fn queued(&self) -> impl Iterator<Item = &QueuedTask<P>> + Clone {
+        self.tasks.iter().chain(self.waiting.values().map(|waiting| &waiting.queued))
+    }
+
+    // Queue a task, numbering it in arrival order for FIFO tie-breaking
+    // -- A task with dependencies that have not executed yet waits outside the heap until
+    //    complete() releases it.
+    fn enqueue(&mut self, task: Task<P>) {
+        let insertion = self.next_insertion;
+        self.next_insertion += 1;
+        let unmet: HashSet<u32> =
+            task.depends_on.iter().copied().filter(|dep| !self.executed.contains(dep)).collect();
+        let queued = QueuedTask { task, insertion };
+        if unmet.is_empty() {
+            self.tasks.push(queued);
+            return;
+        }
+        for &dep in &unmet {
+            self.dependents.entry(dep).or_default().push(insertion);
+        }
+        self.waiting.insert(insertion, WaitingTask { queued, unmet: unmet.len() });
+    }
+
+    // Exposed as stats() only in the states where the queue is meaningful to plan with
+    fn queue_stats(&self) -> SchedulerStats {
+        let priorities = self.queued().map(|q| q.task.priority);
+        let total: u32 = priorities.clone().map(u32::from).sum();
+        let count = self.remaining_tasks();
+        SchedulerStats {
+            count,
+            max_priority: priorities.clone().max(),
+            min_priority: priorities.min(),
+            avg_priority: (count > 0).then(|| total as f64 / count as f64),
+        }
+    }
+}
+
+// GAT streams over a running scheduler
+// -- TaskStream borrows the queue and lends each waiting task; ExecutionStream owns the
+//    scheduler and runs a task only when asked, lending the one it just executed.
+//    Positions are the task's rank in the queue (0 runs first) and the execution
+//    count respectively.
+pub struct TaskStream<'s, P = ()> {
+    // Queued tasks in run order; the heap itself is not ordered beyond its top
+    tasks: Vec<&'s Task<P>>,
+    pub position: usize,
+}
+
+impl<'s, P> Stream for TaskStream<'s, P> {
+    type Item<'a> = &'a Task<P>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(task, _)| task)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let task = *self.tasks.get(self.position)?;
+        let position = self.position;
+        self.position += 1;
+        Some((task, position))
+    }
+
+    fn reset_position(&mut self) -> &mut Self {
+        self.position = 0;
+        self
+    }
+}
+
+pub struct ExecutionStream<P = ()> {
+    scheduler: Scheduler<Running, P>,
+    executed: usize,
+}
+
+impl<P> ExecutionStream<P> {
+    /// Give the scheduler back, e.g. to stop it
+    pub fn into_scheduler(self) -> Scheduler<Running, P> {
+        self.scheduler
+    }
+
+    /// The underlying scheduler, e.g. to inspect the remaining tasks
+    pub fn get_ref(&self) -> &Scheduler<Running, P> {
+        &self.scheduler
+    }
+}
+
+impl<P: Clone> Stream for ExecutionStream<P> {
+    type Item<'a> = &'a Task<P>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        self.next_with_position().map(|(task, _)| task)
+    }
+
+    fn next_with_position<'a>(&'a mut self) -> Option<(Self::Item<'a>, usize)>
+    where
+        Self: Sized
+    {
+        let task = self.scheduler.run_next()?;
+        let position = self.executed;
+        self.executed += 1;
+        Some((task, position))
+    }
+
+    /// Executed tasks cannot be undone, so only the execution count restarts
+    fn reset_position(&mut self) -> &mut Self {
+        self.executed = 0;
+        self
     }
 }
 
@@ -188,6 +1040,7 @@ pub fn demonstrate_state_machine_safety() {
     
     let running_scheduler = initialized_scheduler.start();
     // scheduler.add_task(task); // ❌ This would not compile! Can't add tasks to running scheduler
+    // scheduler.set_priority(1, 9); // ❌ This would not compile! Can't reprioritize while running
     
     let running_scheduler = running_scheduler.execute_next();
     // scheduler.get_summary(); // ❌ This would not compile! Can't get summary on stopped scheduler
@@ -202,3 +1055,759 @@ pub fn demonstrate_state_machine_safety() {
     // let running_scheduler = restarted_scheduler.start();
     println!("✅ All state transitions are compile-time verified!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running_scheduler() -> Scheduler<Running> {
+        Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "Low", 1))
+            .add_task(Task::new(2, "High", 9))
+            .add_task(Task::new(3, "Mid", 5))
+            .start()
+    }
+
+    #[test]
+    fn test_run_to_completion_drains_all_tasks() {
+        let scheduler = running_scheduler().run_to_completion();
+        assert_eq!(scheduler.remaining_tasks(), 0);
+        assert!(!scheduler.has_tasks());
+        assert_eq!(scheduler.completed_tasks().len(), 3);
+        assert_eq!(scheduler.current_task().map(|t| t.id), scheduler.completed_tasks().last().map(|t| t.id));
+    }
+
+    #[test]
+    fn test_completed_history_survives_transitions() {
+        let scheduler = running_scheduler().execute_next().pause().start();
+        assert_eq!(scheduler.completed_tasks().len(), 1);
+
+        let stopped = scheduler.run_to_completion().stop();
+        assert_eq!(stopped.completed_tasks().len(), 3);
+        assert!(stopped.reset().completed_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_task_ordering_priority_then_id() {
+        let mut tasks = [
+            Task::new(3, "c", 5),
+            Task::new(1, "a", 5),
+            Task::new(2, "b", 9),
+        ];
+        tasks.sort();
+        let ids: Vec<u32> = tasks.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+
+        assert_eq!(Task::new(1, "a", 5), Task::new(1, "a", 5));
+        assert_ne!(Task::new(1, "a", 5), Task::new(1, "b", 5));
+        assert!(Task::new(1, "a", 5) < Task::new(1, "b", 5));
+    }
+
+    #[test]
+    fn test_task_eq_agrees_with_ord() {
+        let plain = Task::new(1, "a", 5);
+        let variants = [
+            plain.clone().with_retries(3),
+            Task { depends_on: vec![7, 8], ..plain.clone() },
+        ];
+        for variant in &variants {
+            assert_eq!(plain.cmp(variant), Ordering::Equal);
+            assert_eq!(&plain, variant);
+        }
+
+        for different in [plain.clone().with_id(2), plain.clone().with_name("b"), plain.clone().with_priority(6)] {
+            assert_ne!(plain.cmp(&different), Ordering::Equal);
+            assert_ne!(plain, different);
+        }
+    }
+
+    #[test]
+    fn test_execution_order_is_deterministic() {
+        let order = || {
+            Scheduler::new()
+                .initialize()
+                .add_task(Task::new(4, "d", 1))
+                .add_task(Task::new(2, "b", 7))
+                .add_task(Task::new(3, "c", 7))
+                .add_task(Task::new(1, "a", 7))
+                .start()
+                .run_to_completion()
+                .completed_tasks()
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>()
+        };
+        // Equal priorities run in insertion order, not id order
+        assert_eq!(order(), vec![2, 3, 1, 4]);
+        assert_eq!(order(), order());
+    }
+
+    #[test]
+    fn test_task_default_and_fluent_setters() {
+        let task = Task::default();
+        assert_eq!((task.id, task.name.as_str(), task.priority), (0, "", 0));
+
+        let base = Task::new(7, "base", 3);
+        assert_eq!(base.clone().with_id(8), Task::new(8, "base", 3));
+        assert_eq!(base.clone().with_name("renamed"), Task::new(7, "renamed", 3));
+        assert_eq!(base.with_priority(9), Task::new(7, "base", 9));
+
+        assert_eq!(Task::default().with_name("x").with_priority(5), Task::new(0, "x", 5));
+    }
+
+    #[test]
+    fn test_peek_next_matches_execute_next() {
+        let mut scheduler = running_scheduler();
+        while let Some(peeked) = scheduler.peek_next().cloned() {
+            scheduler = scheduler.execute_next();
+            assert_eq!(scheduler.current_task(), Some(&peeked));
+        }
+        assert_eq!(scheduler.completed_tasks().len(), 3);
+    }
+
+    #[test]
+    fn test_peek_next_on_empty_queue() {
+        let scheduler = Scheduler::new().initialize().start();
+        assert!(scheduler.peek_next().is_none());
+        let peeked = running_scheduler().peek_next().map(|t| t.id);
+        assert_eq!(peeked, Some(2));
+    }
+
+    #[test]
+    fn test_task_stream_previews_in_run_order() {
+        let scheduler = running_scheduler();
+        let mut preview = scheduler.task_stream();
+        let mut seen = Vec::new();
+        while let Some((task, rank)) = preview.next_with_position() {
+            seen.push((task.id, rank));
+        }
+        assert_eq!(seen, vec![(2, 0), (3, 1), (1, 2)]);
+        assert_eq!(scheduler.remaining_tasks(), 3);
+        assert!(scheduler.completed_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_execution_stream_runs_highest_priority_first() {
+        let mut executions = running_scheduler().into_execution_stream();
+        let mut priorities = Vec::new();
+        while let Some(task) = executions.next() {
+            priorities.push(task.priority);
+        }
+        assert_eq!(priorities, vec![9, 5, 1]);
+        assert!(executions.next().is_none());
+
+        let scheduler = executions.into_scheduler();
+        assert_eq!(scheduler.remaining_tasks(), 0);
+        assert_eq!(scheduler.completed_tasks().len(), 3);
+        assert_eq!(scheduler.current_task().map(|t| t.id), Some(1));
+    }
+
+    #[test]
+    fn test_stats_before_and_during_run() {
+        let initialized = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "a", 2))
+            .add_task(Task::new(2, "b", 9))
+            .add_task(Task::new(3, "c", 4))
+            .add_task(Task::new(4, "d", 5));
+        let stats = initialized.stats();
+        assert_eq!(stats.count, 4);
+        assert_eq!((stats.min_priority, stats.max_priority), (Some(2), Some(9)));
+        assert_eq!(stats.avg_priority, Some(5.0));
+
+        // The highest priority (9) runs first and leaves the queue
+        let running = initialized.start().execute_next();
+        let stats = running.stats();
+        assert_eq!((stats.count, stats.max_priority), (3, Some(5)));
+        assert!((stats.avg_priority.unwrap() - 11.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_empty_scheduler() {
+        let stats = Scheduler::new().initialize().stats();
+        assert_eq!(
+            stats,
+            SchedulerStats { count: 0, max_priority: None, min_priority: None, avg_priority: None }
+        );
+        assert_eq!(running_scheduler().run_to_completion().stats().count, 0);
+    }
+
+    // With seed 30, task 1 fails its first two attempts and task 2 fails every attempt
+    const FLAKY_SEED: u64 = 30;
+
+    #[test]
+    fn test_retry_until_success() {
+        let mut scheduler = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "Flaky", 5).with_retries(3))
+            .start();
+        scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        assert_eq!(scheduler.retry_count(1), 1);
+        scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        assert_eq!(scheduler.retry_count(1), 2);
+        assert!(scheduler.completed_tasks().is_empty());
+        scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        assert_eq!(scheduler.completed_tasks().len(), 1);
+        assert!(scheduler.failed_tasks().is_empty());
+        assert!(!scheduler.has_tasks());
+    }
+
+    #[test]
+    fn test_retry_limit_sends_task_to_failed_list() {
+        let mut scheduler = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(2, "Broken", 9).with_retries(2))
+            .add_task(Task::new(3, "Reliable", 1))
+            .start();
+        // 1 attempt + 2 retries for Broken, then Reliable
+        for _ in 0..3 {
+            scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        }
+        assert_eq!(scheduler.failed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(scheduler.peek_next().map(|t| t.id), Some(3));
+        scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        assert_eq!(scheduler.completed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(scheduler.stop().failed_tasks().len(), 1);
+    }
+
+    #[test]
+    fn test_retry_limit_blocks_dependents() {
+        let mut scheduler = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(2, "Broken", 9).with_retries(0))
+            .add_task_with_deps(Task::new(4, "Child", 5), &[2])
+            .add_task_with_deps(Task::new(5, "Grandchild", 5), &[4])
+            .add_task(Task::new(3, "Reliable", 1))
+            .try_start()
+            .unwrap();
+        // One attempt for Broken, one for Reliable; the dependents never become runnable
+        let mut attempts = 0;
+        while scheduler.has_tasks() {
+            scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+            attempts += 1;
+            assert!(attempts <= 2);
+        }
+        assert_eq!(scheduler.failed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![2, 4, 5]);
+        assert_eq!(scheduler.completed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(scheduler.remaining_tasks(), 0);
+        assert_eq!(scheduler.history().len(), 2);
+    }
+
+    #[test]
+    fn test_tasks_without_retries_never_fail() {
+        let mut scheduler = running_scheduler();
+        while scheduler.has_tasks() {
+            scheduler = scheduler.execute_next_with_retry(FLAKY_SEED);
+        }
+        assert_eq!(scheduler.completed_tasks().len(), 3);
+        assert!(scheduler.failed_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_on_execute_fires_once_per_task_in_order() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let executed = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&executed);
+        let mut calls = 0;
+        let scheduler = running_scheduler()
+            .on_execute(move |task| log.borrow_mut().push(task.name.clone()))
+            .on_execute(move |_| {
+                calls += 1;
+                assert!(calls <= 3);
+            })
+            .execute_next();
+        assert_eq!(*executed.borrow(), vec!["High"]);
+
+        let scheduler = scheduler.pause().start().run_to_completion().execute_next();
+        assert_eq!(*executed.borrow(), vec!["High", "Mid", "Low"]);
+
+        // Tasks re-queued by a failed attempt only trigger the hook once they succeed
+        let flaky = Rc::new(RefCell::new(0));
+        let count = Rc::clone(&flaky);
+        let mut retrying = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "Flaky", 5).with_retries(3))
+            .start()
+            .on_execute(move |_| *count.borrow_mut() += 1);
+        for _ in 0..3 {
+            retrying = retrying.execute_next_with_retry(FLAKY_SEED);
+        }
+        assert_eq!(*flaky.borrow(), 1);
+
+        // reset drops the hooks along with the tasks
+        scheduler.stop().reset().add_task(Task::new(4, "Fresh", 1)).start().execute_next();
+        assert_eq!(executed.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_execute_next_result_reports_priority_order() {
+        let mut scheduler = running_scheduler();
+        let mut executed = Vec::new();
+        loop {
+            let (next, result) = scheduler.execute_next_result();
+            scheduler = next;
+            match result {
+                Some(result) => executed.push(result),
+                None => break,
+            }
+        }
+        let ids: Vec<u32> = executed.iter().map(|e| e.task.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+        let sequences: Vec<usize> = executed.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2]);
+        assert!(executed.iter().all(|e| e.outcome == TaskOutcome::Completed));
+    }
+
+    #[test]
+    fn test_retry_results_report_each_attempt() {
+        let mut scheduler = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(2, "Broken", 9).with_retries(1))
+            .add_task(Task::new(1, "Flaky", 5).with_retries(3))
+            .start();
+        let mut outcomes = Vec::new();
+        while scheduler.has_tasks() {
+            let (next, result) = scheduler.execute_next_with_retry_result(FLAKY_SEED);
+            scheduler = next;
+            let result = result.unwrap();
+            outcomes.push((result.task.id, result.sequence, result.outcome));
+        }
+        assert_eq!(
+            outcomes,
+            vec![
+                (2, 0, TaskOutcome::Retrying { attempt: 1 }),
+                (2, 1, TaskOutcome::Failed),
+                (1, 2, TaskOutcome::Retrying { attempt: 1 }),
+                (1, 3, TaskOutcome::Retrying { attempt: 2 }),
+                (1, 4, TaskOutcome::Completed),
+            ]
+        );
+        assert_eq!(scheduler.execute_next_result().1, None);
+    }
+
+    #[test]
+    fn test_history_survives_restart_and_clears_on_reset() {
+        let stopped = running_scheduler().run_to_completion().stop();
+        let ids: Vec<(usize, u32)> = stopped.history().iter().map(|e| (e.sequence, e.task.id)).collect();
+        assert_eq!(ids, vec![(0, 2), (1, 3), (2, 1)]);
+        assert!(stopped.get_summary().contains("Executions: 3"));
+
+        let stopped = stopped
+            .restart()
+            .pause()
+            .add_task(Task::new(4, "Late", 2))
+            .start()
+            .run_to_completion()
+            .stop();
+        assert_eq!(stopped.history().len(), 4);
+        assert_eq!(stopped.history()[3].task.id, 4);
+        assert_eq!(stopped.history()[3].sequence, 3);
+
+        let fresh = stopped.reset().start();
+        assert!(fresh.history().is_empty());
+        assert_eq!(fresh.stop().get_summary(), "📊 Scheduler Summary - Remaining tasks: 0, No tasks executed, Executions: 0");
+    }
+
+    fn run_order(scheduler: Scheduler<Running>) -> Vec<u32> {
+        scheduler.run_to_completion().completed_tasks().iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_heap_runs_highest_priority_first() {
+        let scheduler = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "low", 1))
+            .add_task(Task::new(2, "top", 255))
+            .add_task(Task::new(3, "mid", 100))
+            .add_task(Task::new(4, "zero", 0))
+            .start();
+        let mut stream = scheduler.task_stream();
+        let mut preview = Vec::new();
+        while let Some(task) = stream.next() {
+            preview.push(task.id);
+        }
+        assert_eq!(preview, vec![2, 3, 1, 4]);
+        assert_eq!(run_order(scheduler), vec![2, 3, 1, 4]);
+    }
+
+    #[test]
+    fn test_equal_priorities_keep_insertion_order() {
+        let mut scheduler = Scheduler::new().initialize();
+        for id in [5, 3, 9, 1, 7] {
+            scheduler = scheduler.add_task(Task::new(id, "same", 4));
+        }
+        assert_eq!(run_order(scheduler.start()), vec![5, 3, 9, 1, 7]);
+    }
+
+    #[test]
+    fn test_task_added_after_pause_runs_next() {
+        let scheduler = running_scheduler().execute_next();
+        assert_eq!(scheduler.peek_next().map(|t| t.id), Some(3));
+
+        let scheduler = scheduler
+            .pause()
+            .add_task(Task::new(10, "Urgent", 8))
+            .add_task(Task::new(11, "Also mid", 5))
+            .start();
+        assert_eq!(scheduler.peek_next().map(|t| t.id), Some(10));
+        // Task 3 was queued before task 11, so it wins the tie at priority 5
+        assert_eq!(run_order(scheduler), vec![2, 10, 3, 11, 1]);
+    }
+
+    #[test]
+    fn test_merge_keeps_every_task_once() {
+        let first = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "a", 3))
+            .add_task(Task::new(2, "b", 8));
+        let second = Scheduler::new()
+            .initialize()
+            .add_task(Task::new(3, "c", 3))
+            .add_task(Task::new(4, "d", 9))
+            .add_task(Task::new(5, "e", 1));
+        let merged = first.merge(second);
+        assert_eq!(merged.task_count(), 5);
+
+        let mut ids = run_order(merged.start());
+        // Priority order, with the first scheduler's task winning the tie at 3
+        assert_eq!(ids, vec![4, 2, 1, 3, 5]);
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_merge_with_empty_schedulers() {
+        let empty = || Scheduler::new().initialize();
+        assert_eq!(empty().merge(empty()).task_count(), 0);
+        let one = empty().add_task(Task::new(1, "only", 2));
+        assert_eq!(empty().merge(one).task_count(), 1);
+    }
+
+    fn initialized_three() -> Scheduler<Initialized> {
+        Scheduler::new()
+            .initialize()
+            .add_task(Task::new(1, "Low", 1))
+            .add_task(Task::new(2, "High", 9))
+            .add_task(Task::new(3, "Mid", 5))
+    }
+
+    #[test]
+    fn test_remove_and_find_tasks() {
+        let (scheduler, removed) = initialized_three().remove_task(2);
+        assert_eq!(removed.map(|t| t.name), Some("High".to_string()));
+        assert!(scheduler.find_task(2).is_none());
+        assert_eq!(scheduler.find_task(3).map(|t| t.priority), Some(5));
+
+        let (scheduler, missing) = scheduler.remove_task(42);
+        assert_eq!(missing, None);
+        assert_eq!(scheduler.task_count(), 2);
+        assert_eq!(run_order(scheduler.start()), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_clear_tasks_then_count() {
+        let scheduler = initialized_three().clear_tasks();
+        assert_eq!(scheduler.task_count(), 0);
+        assert!(scheduler.find_task(1).is_none());
+        let scheduler = scheduler.add_task(Task::new(4, "After clear", 2));
+        assert_eq!(run_order(scheduler.start()), vec![4]);
+    }
+
+    #[test]
+    fn test_set_priority_changes_execution_order() {
+        let scheduler = initialized_three().set_priority(1, 10).unwrap();
+        assert_eq!(scheduler.find_task(1).map(|t| t.priority), Some(10));
+        assert_eq!(run_order(scheduler.start()), vec![1, 2, 3]);
+
+        // Lowered to tie with task 1, which was added earlier and so wins the tie
+        let scheduler = initialized_three().set_priority(2, 1).unwrap();
+        assert_eq!(run_order(scheduler.start()), vec![3, 1, 2]);
+
+        let error = initialized_three().set_priority(42, 3).err();
+        assert_eq!(error, Some(SchedulerError::TaskNotFound(42)));
+        assert_eq!(error.unwrap().to_string(), "no queued task with id 42");
+    }
+
+    fn with_deps(tasks: &[(u32, u8, &[u32])]) -> Scheduler<Initialized> {
+        tasks.iter().fold(Scheduler::new().initialize(), |scheduler, &(id, priority, deps)| {
+            scheduler.add_task_with_deps(Task::new(id, "dep", priority), deps)
+        })
+    }
+
+    #[test]
+    fn test_diamond_dependencies_run_in_topological_order() {
+        // 1 <- {2, 3} <- 4, with priorities that would otherwise run 4 first
+        let scheduler = with_deps(&[(4, 9, &[2, 3]), (3, 5, &[1]), (2, 7, &[1]), (1, 1, &[])]);
+        let running = scheduler.try_start().unwrap();
+        assert_eq!(running.peek_next().map(|t| t.id), Some(1));
+        let order = run_order(running);
+        assert_eq!(order, vec![1, 2, 3, 4]);
+
+        // Ready tasks compete by priority alongside blocked ones
+        let scheduler = with_deps(&[(1, 1, &[]), (2, 9, &[1]), (5, 4, &[])]);
+        assert_eq!(run_order(scheduler.try_start().unwrap()), vec![5, 1, 2]);
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_rejected() {
+        let scheduler = with_deps(&[(1, 5, &[3]), (2, 5, &[1]), (3, 5, &[2]), (4, 5, &[])]);
+        let error = scheduler.try_start().err().unwrap();
+        assert_eq!(error, DependencyError::Cycle(vec![1, 3, 2]));
+        assert_eq!(error.to_string(), "dependency cycle between tasks [1, 3, 2]");
+
+        let own = with_deps(&[(7, 1, &[7])]);
+        assert_eq!(own.try_start().err(), Some(DependencyError::Cycle(vec![7])));
+    }
+
+    #[test]
+    fn test_dependency_on_missing_task_errors() {
+        let scheduler = with_deps(&[(1, 5, &[]), (2, 5, &[1, 99])]);
+        assert_eq!(
+            scheduler.try_start().err(),
+            Some(DependencyError::UnknownTask { task: 2, missing: 99 })
+        );
+
+        // start() skips validation; the blocked task simply never runs
+        let running = with_deps(&[(1, 5, &[]), (2, 5, &[99])]).start().run_to_completion();
+        assert_eq!(running.completed_tasks().len(), 1);
+        assert_eq!(running.remaining_tasks(), 1);
+        assert!(running.peek_next().is_none());
+    }
+
+    #[test]
+    fn test_dependencies_satisfied_before_pause_count_as_known() {
+        let running = with_deps(&[(1, 5, &[])]).start().execute_next();
+        let resumed = running.pause().add_task_with_deps(Task::new(2, "after", 1), &[1]).try_start();
+        assert_eq!(run_order(resumed.unwrap()), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_waiting_tasks_stay_editable() {
+        // 2 lists its dependency twice; it still runs once 1 has
+        let scheduler = with_deps(&[(1, 1, &[]), (2, 5, &[1, 1]), (3, 5, &[1]), (4, 5, &[1])]);
+        assert_eq!(scheduler.task_count(), 4);
+        assert_eq!(scheduler.stats().max_priority, Some(5));
+        assert_eq!(scheduler.find_task(3).map(|t| t.priority), Some(5));
+
+        let scheduler = scheduler.set_priority(3, 9).unwrap();
+        let (scheduler, removed) = scheduler.remove_task(4);
+        assert_eq!(removed.map(|t| t.id), Some(4));
+        let running = scheduler.try_start().unwrap();
+        assert_eq!(running.peek_next().map(|t| t.id), Some(1));
+        assert_eq!(run_order(running), vec![1, 3, 2]);
+
+        // A waiting task keeps waiting after a merge, now on the other scheduler's task
+        let merged = with_deps(&[(1, 1, &[])]).merge(with_deps(&[(2, 9, &[1])]));
+        assert_eq!(merged.task_count(), 2);
+        assert_eq!(run_order(merged.try_start().unwrap()), vec![1, 2]);
+
+        // Dependencies the other scheduler already executed stay satisfied
+        let other = with_deps(&[(1, 5, &[])]).start().execute_next().pause();
+        let other = other.add_task_with_deps(Task::new(2, "after", 1), &[1]);
+        let merged = with_deps(&[(7, 1, &[])]).merge(other);
+        assert_eq!(run_order(merged.try_start().unwrap()), vec![7, 2]);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Method {
+        Get,
+        Post,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct HttpRequestStub {
+        method: Method,
+        url: String,
+    }
+
+    fn request(id: u32, priority: u8, method: Method, url: &str) -> Task<HttpRequestStub> {
+        Task::with_payload(id, "request", priority, HttpRequestStub { method, url: url.to_string() })
+    }
+
+    #[test]
+    fn test_scheduler_carries_task_payloads() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&seen);
+        let running = Scheduler::with_payloads()
+            .initialize()
+            .add_task(request(1, 1, Method::Get, "/health"))
+            .add_task(request(2, 9, Method::Post, "/orders"))
+            .start()
+            .on_execute(move |task: &Task<HttpRequestStub>| log.borrow_mut().push(task.payload.url.clone()));
+
+        let (running, executed) = running.execute_next_result();
+        let executed = executed.unwrap();
+        assert_eq!(executed.outcome, TaskOutcome::Completed);
+        assert_eq!(executed.task.payload, HttpRequestStub { method: Method::Post, url: "/orders".to_string() });
+
+        // Payloads survive every transition
+        let stopped = running.pause().start().run_to_completion().stop();
+        let methods: Vec<&Method> = stopped.history().iter().map(|e| &e.task.payload.method).collect();
+        assert_eq!(methods, vec![&Method::Post, &Method::Get]);
+        assert_eq!(*seen.borrow(), vec!["/orders", "/health"]);
+    }
+
+    #[test]
+    fn test_unit_payload_scheduler_matches_default() {
+        // Task and Scheduler without a payload parameter are the () instantiation
+        let explicit: Scheduler<Running, ()> = Scheduler::<Uninitialized, ()>::with_payloads()
+            .initialize()
+            .add_task(Task::with_payload(1, "Low", 1, ()))
+            .add_task(Task::with_payload(2, "High", 9, ()))
+            .add_task(Task::with_payload(3, "Mid", 5, ()))
+            .start();
+        let defaulted: Scheduler<Running> = running_scheduler();
+
+        assert_eq!(run_order(explicit), run_order(defaulted));
+        assert_eq!(Task::new(1, "a", 5), Task::<()>::with_payload(1, "a", 5, ()));
+    }
+
+    #[test]
+    fn test_payload_is_not_part_of_identity() {
+        // f64 is not Eq, yet tasks carrying it can still be compared and sorted
+        let mut tasks = [
+            Task::with_payload(1, "a", 1, 0.5),
+            Task::with_payload(2, "b", 9, f64::NAN),
+            Task::with_payload(1, "a", 1, 2.5),
+        ];
+        tasks.sort();
+        assert_eq!(tasks.iter().map(|t| t.id).collect::<Vec<_>>(), vec![2, 1, 1]);
+
+        // Payload-only differences are neither unequal nor ordered
+        assert_eq!(tasks[1].cmp(&tasks[2]), Ordering::Equal);
+        assert_eq!(tasks[1], tasks[2]);
+        let get = |method| request(7, 3, method, "/");
+        assert_eq!(get(Method::Get), get(Method::Post));
+    }
+
+    // Action that logs `label` into `log` and succeeds with it
+    fn logging(log: &Rc<RefCell<Vec<&'static str>>>, label: &'static str) -> ActionFn {
+        let log = Rc::clone(log);
+        Box::new(move || {
+            log.borrow_mut().push(label);
+            Ok(label.to_uppercase())
+        })
+    }
+
+    #[test]
+    fn test_run_all_invokes_actions_in_priority_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (stopped, outcomes) = Scheduler::new()
+            .initialize()
+            .add_task(Task::with_action(1, "low", 1, logging(&log, "low")))
+            .add_task(Task::with_action(2, "high", 9, logging(&log, "high")))
+            .add_task(Task::with_action(3, "mid", 5, logging(&log, "mid")))
+            .start()
+            .run_all();
+
+        assert_eq!(*log.borrow(), vec!["high", "mid", "low"]);
+        assert_eq!(
+            outcomes,
+            vec![
+                ActionOutcome::Succeeded { task_id: 2, output: "HIGH".to_string() },
+                ActionOutcome::Succeeded { task_id: 3, output: "MID".to_string() },
+                ActionOutcome::Succeeded { task_id: 1, output: "LOW".to_string() },
+            ]
+        );
+        assert_eq!(stopped.completed_tasks().len(), 3);
+        assert!(stopped.completed_tasks().iter().all(|t| t.action.as_ref().is_some_and(TaskAction::is_spent)));
+    }
+
+    #[test]
+    fn test_run_all_records_failure_and_continues() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (stopped, outcomes) = Scheduler::new()
+            .initialize()
+            .add_task(Task::with_action(1, "first", 9, logging(&log, "first")))
+            .add_task(Task::with_action(2, "broken", 5, Box::new(|| Err("disk full".to_string()))))
+            .add_task(Task::new(3, "plain", 3))
+            .add_task(Task::with_action(4, "last", 1, logging(&log, "last")))
+            .start()
+            .run_all();
+
+        assert_eq!(outcomes[1], ActionOutcome::Failed { task_id: 2, error: "disk full".to_string() });
+        assert_eq!(outcomes[2], ActionOutcome::Skipped { task_id: 3 });
+        assert_eq!(*log.borrow(), vec!["first", "last"]);
+        assert_eq!(stopped.failed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(stopped.completed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert_eq!(stopped.history()[1].outcome, TaskOutcome::Failed);
+        assert_eq!(stopped.remaining_tasks(), 0);
+    }
+
+    #[test]
+    fn test_run_all_fail_fast_aborts_remaining_tasks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (stopped, outcomes) = Scheduler::new()
+            .initialize()
+            .add_task(Task::with_action(1, "first", 9, logging(&log, "first")))
+            .add_task(Task::with_action(2, "broken", 5, Box::new(|| Err("disk full".to_string()))))
+            .add_task(Task::with_action(3, "never", 1, logging(&log, "never")))
+            .start()
+            .run_all_fail_fast();
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes.last(), Some(ActionOutcome::Failed { task_id: 2, .. })));
+        assert_eq!(*log.borrow(), vec!["first"]);
+        assert_eq!(stopped.remaining_tasks(), 1);
+
+        // The aborted task keeps its action and runs after a restart
+        let (_, rest) = stopped.restart().run_all();
+        assert_eq!(rest, vec![ActionOutcome::Succeeded { task_id: 3, output: "NEVER".to_string() }]);
+        assert_eq!(*log.borrow(), vec!["first", "never"]);
+    }
+
+    #[test]
+    fn test_run_all_blocks_dependents_of_failed_task() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let scheduler = || {
+            Scheduler::new()
+                .initialize()
+                .add_task(Task::with_action(1, "broken", 5, Box::new(|| Err("disk full".to_string()))))
+                .add_task_with_deps(Task::with_action(2, "child", 9, logging(&log, "child")), &[1])
+                .add_task_with_deps(Task::with_action(3, "grandchild", 9, logging(&log, "grandchild")), &[2])
+                .add_task(Task::with_action(4, "other", 1, logging(&log, "other")))
+                .try_start()
+                .unwrap()
+        };
+
+        let (stopped, outcomes) = scheduler().run_all();
+        assert_eq!(
+            outcomes,
+            vec![
+                ActionOutcome::Failed { task_id: 1, error: "disk full".to_string() },
+                ActionOutcome::Blocked { task_id: 2, failed_dependency: 1 },
+                ActionOutcome::Blocked { task_id: 3, failed_dependency: 1 },
+                ActionOutcome::Succeeded { task_id: 4, output: "OTHER".to_string() },
+            ]
+        );
+        assert_eq!(*log.borrow(), vec!["other"]);
+        assert_eq!(stopped.remaining_tasks(), 0);
+        assert_eq!(stopped.failed_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+        // Blocked tasks never ran, so only the failure and task 4 are in the history
+        assert_eq!(stopped.history().len(), 2);
+
+        // Failing fast leaves the dependents queued instead
+        let (stopped, outcomes) = scheduler().run_all_fail_fast();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(stopped.remaining_tasks(), 3);
+    }
+
+    #[test]
+    fn test_action_is_not_part_of_identity() {
+        let plain = Task::new(1, "a", 5);
+        let first = Task::with_action(1, "a", 5, Box::new(|| Ok(String::new())));
+        let second = Task::with_action(1, "a", 5, Box::new(|| Err(String::new())));
+        for task in [&first, &second] {
+            assert_eq!(plain.cmp(task), Ordering::Equal);
+            assert_eq!(&plain, task);
+        }
+        assert_ne!(first.action, second.action);
+        assert_eq!(first, second);
+    }
+}
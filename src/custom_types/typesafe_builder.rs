@@ -13,15 +13,48 @@ pub struct Complete;
 pub trait HasName {}
 pub trait HasAge {}
 pub trait HasEmail {}
+pub trait HasPhone {}
 
 // Marker structs for tracking what fields have been set
 pub struct WithName;
 pub struct WithAge;
 pub struct WithEmail;
+pub struct WithPhone;
 
 impl HasName for WithName {}
 impl HasAge for WithAge {}
 impl HasEmail for WithEmail {}
+impl HasPhone for WithPhone {}
+
+// Type-level OR over field states
+// -- () | () = (), and any set marker on either side wins. Used by merge() so the
+//    merged builder's type reflects the union of set fields.
+pub trait IsSet {}
+
+impl IsSet for WithName {}
+impl IsSet for WithAge {}
+impl IsSet for WithEmail {}
+impl IsSet for WithPhone {}
+
+pub trait Or<Rhs> {
+    type Output;
+}
+
+impl Or<()> for () {
+    type Output = ();
+}
+
+impl<M: IsSet> Or<M> for () {
+    type Output = M;
+}
+
+impl<M: IsSet> Or<()> for M {
+    type Output = M;
+}
+
+impl<M: IsSet> Or<M> for M {
+    type Output = M;
+}
 
 // The final Person struct
 #[derive(Debug, Clone)]
@@ -29,6 +62,7 @@ pub struct Person {
     name: String,
     age: u32,
     email: String,
+    phone: Option<String>,
 }
 
 impl Person {
@@ -43,80 +77,312 @@ impl Person {
     pub fn email(&self) -> &str {
         &self.email
     }
+
+    pub fn phone(&self) -> Option<&str> {
+        self.phone.as_deref()
+    }
 }
 
+// Runtime validation failures - the type system guarantees presence, not validity
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    AgeOutOfRange(u32),
+    InvalidEmail(String),
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::AgeOutOfRange(age) => write!(f, "age {} is out of range (0..=150)", age),
+            ValidationError::InvalidEmail(email) => write!(f, "email '{}' is missing '@'", email),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 // Builder with type-level state tracking
-pub struct PersonBuilder<Name, Age, Email> {
+// -- Phone is optional: its parameter records whether it was supplied, but build() ignores it
+pub struct PersonBuilder<Name, Age, Email, Phone = ()> {
     name: Option<String>,
     age: Option<u32>,
     email: Option<String>,
-    _phantom: PhantomData<(Name, Age, Email)>,
+    phone: Option<String>,
+    _phantom: PhantomData<(Name, Age, Email, Phone)>,
 }
 
 // Starting state - nothing set
-impl PersonBuilder<(), (), ()> {
+impl PersonBuilder<(), (), (), ()> {
     pub fn new() -> Self {
         Self {
             name: None,
             age: None,
             email: None,
+            phone: None,
             _phantom: PhantomData,
         }
     }
 }
 
+impl Default for PersonBuilder<(), (), (), ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Setting name
-impl<Age, Email> PersonBuilder<(), Age, Email> {
-    pub fn name(mut self, name: String) -> PersonBuilder<WithName, Age, Email> {
+impl<Age, Email, Phone> PersonBuilder<(), Age, Email, Phone> {
+    pub fn name(mut self, name: String) -> PersonBuilder<WithName, Age, Email, Phone> {
         self.name = Some(name);
         PersonBuilder {
             name: self.name,
             age: self.age,
             email: self.email,
+            phone: self.phone,
             _phantom: PhantomData,
         }
     }
 }
 
 // Setting age
-impl<Name, Email> PersonBuilder<Name, (), Email> {
-    pub fn age(mut self, age: u32) -> PersonBuilder<Name, WithAge, Email> {
+impl<Name, Email, Phone> PersonBuilder<Name, (), Email, Phone> {
+    pub fn age(mut self, age: u32) -> PersonBuilder<Name, WithAge, Email, Phone> {
         self.age = Some(age);
         PersonBuilder {
             name: self.name,
             age: self.age,
             email: self.email,
+            phone: self.phone,
             _phantom: PhantomData,
         }
     }
 }
 
 // Setting email
-impl<Name, Age> PersonBuilder<Name, Age, ()> {
-    pub fn email(mut self, email: String) -> PersonBuilder<Name, Age, WithEmail> {
+impl<Name, Age, Phone> PersonBuilder<Name, Age, (), Phone> {
+    pub fn email(mut self, email: String) -> PersonBuilder<Name, Age, WithEmail, Phone> {
         self.email = Some(email);
         PersonBuilder {
             name: self.name,
             age: self.age,
             email: self.email,
+            phone: self.phone,
             _phantom: PhantomData,
         }
     }
 }
 
-// Only allow build when all fields are set
-impl PersonBuilder<WithName, WithAge, WithEmail> {
+// Resetting fields - clears the value and flips the marker back to (), so the setter
+// becomes available (and required) again
+impl<Age, Email, Phone> PersonBuilder<WithName, Age, Email, Phone> {
+    /// Clear the name so a corrected one can be supplied.
+    ///
+    /// `build` is unavailable again until `name` is called:
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::typesafe_builder::PersonBuilder;
+    ///
+    /// let person = PersonBuilder::new()
+    ///     .name("Typo".to_string())
+    ///     .age(30)
+    ///     .email("typo@example.com".to_string())
+    ///     .reset_name()
+    ///     .build(); // ❌ name was reset and not re-supplied
+    /// ```
+    ///
+    /// ```
+    /// use rust_higher_kined_types::typesafe_builder::PersonBuilder;
+    ///
+    /// let person = PersonBuilder::new()
+    ///     .name("Typo".to_string())
+    ///     .age(30)
+    ///     .email("typo@example.com".to_string())
+    ///     .reset_name()
+    ///     .name("Fixed".to_string())
+    ///     .build();
+    /// assert_eq!(person.name(), "Fixed");
+    /// ```
+    pub fn reset_name(self) -> PersonBuilder<(), Age, Email, Phone> {
+        PersonBuilder {
+            name: None,
+            age: self.age,
+            email: self.email,
+            phone: self.phone,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Name, Email, Phone> PersonBuilder<Name, WithAge, Email, Phone> {
+    /// Clear the age so a corrected one can be supplied.
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::typesafe_builder::PersonBuilder;
+    ///
+    /// let person = PersonBuilder::new()
+    ///     .name("Alice".to_string())
+    ///     .age(300)
+    ///     .email("alice@example.com".to_string())
+    ///     .reset_age()
+    ///     .build(); // ❌ age was reset and not re-supplied
+    /// ```
+    pub fn reset_age(self) -> PersonBuilder<Name, (), Email, Phone> {
+        PersonBuilder {
+            name: self.name,
+            age: None,
+            email: self.email,
+            phone: self.phone,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Name, Age, Phone> PersonBuilder<Name, Age, WithEmail, Phone> {
+    /// Clear the email so a corrected one can be supplied.
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::typesafe_builder::PersonBuilder;
+    ///
+    /// let person = PersonBuilder::new()
+    ///     .name("Alice".to_string())
+    ///     .age(30)
+    ///     .email("alice-at-example.com".to_string())
+    ///     .reset_email()
+    ///     .build(); // ❌ email was reset and not re-supplied
+    /// ```
+    pub fn reset_email(self) -> PersonBuilder<Name, Age, (), Phone> {
+        PersonBuilder {
+            name: self.name,
+            age: self.age,
+            email: None,
+            phone: self.phone,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Setting the optional phone - allowed in any state, and it never gates build()
+impl<Name, Age, Email, Phone> PersonBuilder<Name, Age, Email, Phone> {
+    pub fn optional_phone(self, phone: String) -> PersonBuilder<Name, Age, Email, WithPhone> {
+        PersonBuilder {
+            name: self.name,
+            age: self.age,
+            email: self.email,
+            phone: Some(phone),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Merging partial builders - a field is set if either side set it, and self wins conflicts
+impl<Name, Age, Email, Phone> PersonBuilder<Name, Age, Email, Phone> {
+    /// Combine two partial builders into one.
+    ///
+    /// `build` is only available when the union of both sides covers every required field:
+    ///
+    /// ```compile_fail
+    /// use rust_higher_kined_types::typesafe_builder::PersonBuilder;
+    ///
+    /// let person = PersonBuilder::new()
+    ///     .name("Half".to_string())
+    ///     .merge(PersonBuilder::new().age(5))
+    ///     .build(); // ❌ neither side set an email
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn merge<Name2, Age2, Email2, Phone2>(
+        self,
+        other: PersonBuilder<Name2, Age2, Email2, Phone2>,
+    ) -> PersonBuilder<
+        <Name as Or<Name2>>::Output,
+        <Age as Or<Age2>>::Output,
+        <Email as Or<Email2>>::Output,
+        <Phone as Or<Phone2>>::Output,
+    >
+    where
+        Name: Or<Name2>,
+        Age: Or<Age2>,
+        Email: Or<Email2>,
+        Phone: Or<Phone2>,
+    {
+        PersonBuilder {
+            name: self.name.or(other.name),
+            age: self.age.or(other.age),
+            email: self.email.or(other.email),
+            phone: self.phone.or(other.phone),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Only allow build when all required fields are set (with or without a phone)
+impl<Phone> PersonBuilder<WithName, WithAge, WithEmail, Phone> {
     pub fn build(self) -> Person {
         Person {
             name: self.name.unwrap(),
             age: self.age.unwrap(),
             email: self.email.unwrap(),
+            phone: self.phone,
+        }
+    }
+
+    /// Every rule the values break, in rule order (age, then email); empty when valid
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        let age = self.age.unwrap();
+        if age > 150 {
+            errors.push(ValidationError::AgeOutOfRange(age));
+        }
+
+        let email = self.email.as_deref().unwrap();
+        if !email.contains('@') {
+            errors.push(ValidationError::InvalidEmail(email.to_string()));
+        }
+
+        errors
+    }
+
+    /// Fallible build that also checks the values, not just their presence.
+    /// Reports the first broken rule; use validate to see all of them.
+    pub fn try_build(self) -> Result<Person, ValidationError> {
+        match self.validate().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.build()),
+        }
+    }
+}
+
+// Batch construction for complete builders
+pub fn build_all<Phone>(builders: Vec<PersonBuilder<WithName, WithAge, WithEmail, Phone>>) -> Vec<Person> {
+    builders.into_iter().map(PersonBuilder::build).collect()
+}
+
+// Validating batch construction - every broken rule of every record is reported,
+// tagged with the index of the builder it came from
+pub fn try_build_all<Phone>(
+    builders: Vec<PersonBuilder<WithName, WithAge, WithEmail, Phone>>,
+) -> Result<Vec<Person>, Vec<(usize, ValidationError)>> {
+    let mut people = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, builder) in builders.into_iter().enumerate() {
+        let violations = builder.validate();
+        if violations.is_empty() {
+            people.push(builder.build());
+        } else {
+            errors.extend(violations.into_iter().map(|error| (index, error)));
         }
     }
+
+    if errors.is_empty() {
+        Ok(people)
+    } else {
+        Err(errors)
+    }
 }
 
 // Optional: Provide convenience methods for validation
-impl<Name, Age, Email> PersonBuilder<Name, Age, Email> {
+impl<Name, Age, Email, Phone> PersonBuilder<Name, Age, Email, Phone> {
     pub fn has_name(&self) -> bool {
         self.name.is_some()
     }
@@ -128,6 +394,281 @@ impl<Name, Age, Email> PersonBuilder<Name, Age, Email> {
     pub fn has_email(&self) -> bool {
         self.email.is_some()
     }
+
+    pub fn has_phone(&self) -> bool {
+        self.phone.is_some()
+    }
+}
+
+// ----------------------------------------------------------
+// A second builder using the same phantom-state technique
+// -- Nothing above is specific to Person: any struct with required fields
+//    gets one marker type per field and one impl block per setter.
+// ----------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Http,
+    Https,
+    Tcp,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    host: String,
+    port: u16,
+    protocol: Protocol,
+}
+
+impl ServerConfig {
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+}
+
+// Marker structs for tracking which server fields have been set
+pub struct WithHost;
+pub struct WithPort;
+pub struct WithProtocol;
+
+pub struct ServerConfigBuilder<Host, Port, Proto> {
+    host: Option<String>,
+    port: Option<u16>,
+    protocol: Option<Protocol>,
+    _phantom: PhantomData<(Host, Port, Proto)>,
+}
+
+// Starting state - nothing set
+impl ServerConfigBuilder<(), (), ()> {
+    pub fn new() -> Self {
+        Self {
+            host: None,
+            port: None,
+            protocol: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl Default for ServerConfigBuilder<(), (), ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Setting host
+impl<Port, Proto> ServerConfigBuilder<(), Port, Proto> {
+    pub fn host(self, host: String) -> ServerConfigBuilder<WithHost, Port, Proto> {
+        ServerConfigBuilder {
+            host: Some(host),
+            port: self.port,
+            protocol: self.protocol,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Setting port
+impl<Host, Proto> ServerConfigBuilder<Host, (), Proto> {
+    pub fn port(self, port: u16) -> ServerConfigBuilder<Host, WithPort, Proto> {
+        ServerConfigBuilder {
+            host: self.host,
+            port: Some(port),
+            protocol: self.protocol,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Setting protocol
+impl<Host, Port> ServerConfigBuilder<Host, Port, ()> {
+    pub fn protocol(self, protocol: Protocol) -> ServerConfigBuilder<Host, Port, WithProtocol> {
+        ServerConfigBuilder {
+            host: self.host,
+            port: self.port,
+            protocol: Some(protocol),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Only allow build when all fields are set
+impl ServerConfigBuilder<WithHost, WithPort, WithProtocol> {
+    pub fn build(self) -> ServerConfig {
+        ServerConfig {
+            host: self.host.unwrap(),
+            port: self.port.unwrap(),
+            protocol: self.protocol.unwrap(),
+        }
+    }
+}
+
+impl<Host, Port, Proto> ServerConfigBuilder<Host, Port, Proto> {
+    pub fn has_host(&self) -> bool {
+        self.host.is_some()
+    }
+
+    pub fn has_port(&self) -> bool {
+        self.port.is_some()
+    }
+
+    pub fn has_protocol(&self) -> bool {
+        self.protocol.is_some()
+    }
+}
+
+// ----------------------------------------------------------
+// Generating type-safe builders with a macro
+// -- The hand-written impls above follow a fixed recipe: one phantom parameter per
+//    required field, one setter impl per field that flips its parameter from () to a
+//    "set" marker, and a build impl only for the all-set state. typesafe_builder!
+//    writes that recipe out for any list of fields.
+// ----------------------------------------------------------
+
+// Generic "field has been set" marker used by macro-generated builders
+pub struct Set;
+
+/// Generate a type-safe builder for a struct whose fields are all required.
+///
+/// macro_rules cannot build new identifiers, so each field names its own
+/// introspection method in brackets.
+///
+/// ```
+/// use rust_higher_kined_types::typesafe_builder;
+///
+/// pub struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// typesafe_builder! {
+///     pub PointBuilder => Point {
+///         x: i32 [has_x],
+///         y: i32 [has_y],
+///     }
+/// }
+///
+/// let point = PointBuilder::new().y(2).x(1).build();
+/// assert_eq!((point.x, point.y), (1, 2));
+/// ```
+///
+/// Missing fields are rejected at compile time, exactly like `PersonBuilder`:
+///
+/// ```compile_fail
+/// use rust_higher_kined_types::typesafe_builder;
+///
+/// pub struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// typesafe_builder! {
+///     pub PointBuilder => Point {
+///         x: i32 [has_x],
+///         y: i32 [has_y],
+///     }
+/// }
+///
+/// let point = PointBuilder::new().x(1).build(); // ❌ y is missing
+/// ```
+///
+/// Each setter can only be called once:
+///
+/// ```compile_fail
+/// use rust_higher_kined_types::typesafe_builder;
+///
+/// pub struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// typesafe_builder! {
+///     pub PointBuilder => Point {
+///         x: i32 [has_x],
+///         y: i32 [has_y],
+///     }
+/// }
+///
+/// let point = PointBuilder::new().x(1).x(2).y(3).build(); // ❌ x is already set
+/// ```
+#[macro_export]
+macro_rules! typesafe_builder {
+    // Expands to the "set" marker once per field (used to spell the all-set state)
+    (@set $field:ident) => {
+        $crate::typesafe_builder::Set
+    };
+
+    // One setter impl per field: fields before and after stay generic, this one must be ()
+    (@setters $builder:ident [$($before:ident)*] []) => {};
+    (@setters $builder:ident [$($before:ident)*]
+        [$field:ident : $ty:ty [$has:ident] $(, $after:ident : $aty:ty [$ahas:ident])*]) => {
+        #[allow(non_camel_case_types)]
+        impl<$($before,)* $($after),*> $builder<$($before,)* (), $($after),*> {
+            pub fn $field(self, value: $ty) -> $builder<$($before,)* $crate::typesafe_builder::Set, $($after),*> {
+                $builder {
+                    $($before: self.$before,)*
+                    $field: ::std::option::Option::Some(value),
+                    $($after: self.$after,)*
+                    _phantom: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        $crate::typesafe_builder!(@setters $builder [$($before)* $field] [$($after : $aty [$ahas]),*]);
+    };
+
+    ($vis:vis $builder:ident => $target:ident { $($field:ident : $ty:ty [$has:ident]),+ $(,)? }) => {
+        // Each field's name doubles as its phantom type parameter
+        #[allow(non_camel_case_types)]
+        $vis struct $builder<$($field = ()),+> {
+            $($field: ::std::option::Option<$ty>,)+
+            _phantom: ::std::marker::PhantomData<($($field,)+)>,
+        }
+
+        // Starting state - nothing set
+        impl $builder {
+            pub fn new() -> Self {
+                $builder {
+                    $($field: ::std::option::Option::None,)+
+                    _phantom: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl ::std::default::Default for $builder {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        $crate::typesafe_builder!(@setters $builder [] [$($field : $ty [$has]),+]);
+
+        // Only allow build when all fields are set
+        impl $builder<$($crate::typesafe_builder!(@set $field)),+> {
+            pub fn build(self) -> $target {
+                $target {
+                    $($field: self.$field.unwrap(),)+
+                }
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl<$($field),+> $builder<$($field),+> {
+            $(
+                pub fn $has(&self) -> bool {
+                    self.$field.is_some()
+                }
+            )+
+        }
+    };
 }
 
 /// 타입 안전성 검증 예제
@@ -145,4 +686,265 @@ pub fn demonstrate_builder_safety() {
     println!("✅ Type system prevents incomplete object construction!");
     println!("✅ Only builders with ALL required fields can call build()!");
 }
-    
\ No newline at end of file
+    
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_without_phone() {
+        let person = PersonBuilder::new()
+            .name("Alice".to_string())
+            .age(30)
+            .email("alice@example.com".to_string())
+            .build();
+
+        assert_eq!(person.name(), "Alice");
+        assert_eq!(person.phone(), None);
+    }
+
+    #[test]
+    fn test_build_with_phone_in_any_state() {
+        let early = PersonBuilder::new()
+            .optional_phone("010-1234-5678".to_string())
+            .name("Bob".to_string())
+            .age(25)
+            .email("bob@example.com".to_string())
+            .build();
+        assert_eq!(early.phone(), Some("010-1234-5678"));
+
+        let late = PersonBuilder::new()
+            .name("Carol".to_string())
+            .age(41)
+            .email("carol@example.com".to_string())
+            .optional_phone("555-0100".to_string())
+            .build();
+        assert_eq!(late.phone(), Some("555-0100"));
+    }
+
+    #[test]
+    fn test_try_build_valid_person() {
+        let person = PersonBuilder::new()
+            .name("Dana".to_string())
+            .age(150)
+            .email("dana@example.com".to_string())
+            .try_build()
+            .unwrap();
+        assert_eq!(person.age(), 150);
+        assert_eq!(person.email(), "dana@example.com");
+    }
+
+    #[test]
+    fn test_try_build_rejects_age_over_150() {
+        let result = PersonBuilder::new()
+            .name("Old".to_string())
+            .age(151)
+            .email("old@example.com".to_string())
+            .try_build();
+        assert_eq!(result.unwrap_err(), ValidationError::AgeOutOfRange(151));
+    }
+
+    #[test]
+    fn test_try_build_rejects_email_without_at() {
+        let result = PersonBuilder::new()
+            .name("Eve".to_string())
+            .age(20)
+            .email("eve.example.com".to_string())
+            .optional_phone("555-0101".to_string())
+            .try_build();
+        assert_eq!(
+            result.unwrap_err(),
+            ValidationError::InvalidEmail("eve.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reset_fields_then_resupply() {
+        let builder = PersonBuilder::new()
+            .name("Wrong".to_string())
+            .age(999)
+            .email("wrong".to_string());
+
+        let reset = builder.reset_name().reset_age().reset_email();
+        assert!(!reset.has_name());
+        assert!(!reset.has_age());
+        assert!(!reset.has_email());
+
+        let person = reset
+            .name("Right".to_string())
+            .age(40)
+            .email("right@example.com".to_string())
+            .build();
+        assert_eq!(person.name(), "Right");
+        assert_eq!(person.age(), 40);
+        assert_eq!(person.email(), "right@example.com");
+    }
+
+    #[test]
+    fn test_server_config_builder_happy_path() {
+        let config = ServerConfigBuilder::new()
+            .protocol(Protocol::Https)
+            .host("example.com".to_string())
+            .port(443)
+            .build();
+
+        assert_eq!(config.host(), "example.com");
+        assert_eq!(config.port(), 443);
+        assert_eq!(config.protocol(), Protocol::Https);
+    }
+
+    #[test]
+    fn test_server_config_builder_has_checks() {
+        let builder = ServerConfigBuilder::new().host("localhost".to_string());
+        assert!(builder.has_host());
+        assert!(!builder.has_port());
+        assert!(!builder.has_protocol());
+
+        let builder = builder.port(8080).protocol(Protocol::Tcp);
+        assert!(builder.has_host() && builder.has_port() && builder.has_protocol());
+    }
+
+    // Same fields as Person, regenerated through the macro
+    #[derive(Debug)]
+    struct MacroPerson {
+        name: String,
+        age: u32,
+        email: String,
+    }
+
+    crate::typesafe_builder! {
+        MacroPersonBuilder => MacroPerson {
+            name: String [has_name],
+            age: u32 [has_age],
+            email: String [has_email],
+        }
+    }
+
+    #[test]
+    fn test_macro_builder_builds_in_any_order() {
+        let person = MacroPersonBuilder::new()
+            .email("ada@example.com".to_string())
+            .age(36)
+            .name("Ada".to_string())
+            .build();
+
+        assert_eq!(person.name, "Ada");
+        assert_eq!(person.age, 36);
+        assert_eq!(person.email, "ada@example.com");
+    }
+
+    #[test]
+    fn test_macro_builder_has_checks_and_state_types() {
+        let builder = MacroPersonBuilder::new().age(1);
+        assert!(!builder.has_name());
+        assert!(builder.has_age());
+        assert!(!builder.has_email());
+
+        // The state is tracked in the type exactly like the hand-written builder
+        let _: MacroPersonBuilder<(), Set, ()> = builder;
+        let _: MacroPersonBuilder = MacroPersonBuilder::default();
+    }
+
+    #[test]
+    fn test_merge_name_with_age_and_email() {
+        let name_only = PersonBuilder::new().name("Merged".to_string());
+        let contact = PersonBuilder::new()
+            .age(33)
+            .email("merged@example.com".to_string());
+
+        let person = name_only.merge(contact).build();
+        assert_eq!(person.name(), "Merged");
+        assert_eq!(person.age(), 33);
+        assert_eq!(person.email(), "merged@example.com");
+    }
+
+    #[test]
+    fn test_merge_self_wins_on_conflict() {
+        let primary = PersonBuilder::new().name("Primary".to_string()).age(1);
+        let secondary = PersonBuilder::new()
+            .name("Secondary".to_string())
+            .email("secondary@example.com".to_string())
+            .optional_phone("555-0000".to_string());
+
+        let merged = primary.merge(secondary);
+        // Both sides set a name: the result is still WithName, not something else
+        let merged: PersonBuilder<WithName, WithAge, WithEmail, WithPhone> = merged;
+        let person = merged.build();
+        assert_eq!(person.name(), "Primary");
+        assert_eq!(person.age(), 1);
+        assert_eq!(person.phone(), Some("555-0000"));
+    }
+
+    #[test]
+    fn test_merge_partial_union_tracks_missing_fields() {
+        let merged = PersonBuilder::new()
+            .name("Half".to_string())
+            .merge(PersonBuilder::new().age(5));
+        // The union lacks an email, so the email setter is still available (and required)
+        let merged: PersonBuilder<WithName, WithAge, (), ()> = merged;
+        assert!(!merged.has_email());
+
+        let person = merged.email("half@example.com".to_string()).build();
+        assert_eq!(person.email(), "half@example.com");
+    }
+
+    fn complete(name: &str, age: u32, email: &str) -> PersonBuilder<WithName, WithAge, WithEmail> {
+        PersonBuilder::new()
+            .name(name.to_string())
+            .age(age)
+            .email(email.to_string())
+    }
+
+    #[test]
+    fn test_build_all() {
+        let people = build_all(vec![complete("A", 1, "a@x"), complete("B", 2, "b@x")]);
+        assert_eq!(people.len(), 2);
+        assert_eq!(people[1].name(), "B");
+    }
+
+    #[test]
+    fn test_try_build_all_valid_batch() {
+        let people = try_build_all(vec![
+            complete("A", 10, "a@example.com"),
+            complete("B", 20, "b@example.com"),
+            complete("C", 30, "c@example.com"),
+        ])
+        .unwrap();
+        let names: Vec<&str> = people.iter().map(Person::name).collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_try_build_all_reports_every_error() {
+        let errors = try_build_all(vec![
+            complete("Ok", 10, "ok@example.com"),
+            complete("Old", 200, "old@example.com"),
+            complete("NoAt", 20, "no-at"),
+            complete("Both", 151, "also-no-at"),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                (1, ValidationError::AgeOutOfRange(200)),
+                (2, ValidationError::InvalidEmail("no-at".to_string())),
+                (3, ValidationError::AgeOutOfRange(151)),
+                (3, ValidationError::InvalidEmail("also-no-at".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_collects_every_rule() {
+        assert!(complete("Ok", 150, "ok@example.com").validate().is_empty());
+        let both = complete("Both", 151, "nope");
+        assert_eq!(
+            both.validate(),
+            vec![ValidationError::AgeOutOfRange(151), ValidationError::InvalidEmail("nope".to_string())]
+        );
+        // try_build still stops at the first broken rule
+        assert_eq!(both.try_build().unwrap_err(), ValidationError::AgeOutOfRange(151));
+    }
+}
@@ -3,6 +3,7 @@
 // -- Higher-ranked trait bounds allow us to work with traits that have lifetime parameters 
 //    in sophisticated ways:
 //----------------------------------------------------------
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 // Define a trait with a lifetime parameter
@@ -20,7 +21,16 @@ where
     // The Output type must be Debug for any lifetime
     for<'a> <T as WithLifetime<'a>>::Output: Debug,
 {
-    let inputs = vec!["first", "second", "third"];
+    process_inputs(processor, &["first", "second", "third"])
+}
+
+// Same as process_any_lifetime, but over a caller-supplied set of inputs
+// -- The results are formatted in input order.
+pub fn process_inputs<'inp, T>(processor: T, inputs: &[&'inp str]) -> Vec<String>
+where
+    for<'a> T: WithLifetime<'a>,
+    for<'a> <T as WithLifetime<'a>>::Output: Debug,
+{
     inputs.iter()
         .map(|&s| {
             let result = processor.process(s);
@@ -39,3 +49,338 @@ impl<'a> WithLifetime<'a> for WordCounter {
         input.split_whitespace().count()
     }
 }
+
+// A processor whose Output borrows from its input
+// -- Output = &'a str ties the result to the input lifetime, so it cannot outlive the input.
+pub struct FirstWord;
+
+impl<'a> WithLifetime<'a> for FirstWord {
+    type Output = &'a str;
+
+    fn process(&self, input: &'a str) -> Self::Output {
+        input.split_whitespace().next().unwrap_or("")
+    }
+}
+
+// Variant of process_any_lifetime for processors returning borrowed slices
+// -- The inputs are owned locally, so each borrowed result is copied into a String
+//    before the input it points into is dropped.
+pub fn process_borrowed_outputs<T>(processor: T) -> Vec<String>
+where
+    for<'a> T: WithLifetime<'a, Output = &'a str>,
+{
+    let inputs = [
+        String::from("first input line"),
+        String::from("  second  input"),
+        String::new(),
+    ];
+    inputs.iter()
+        .map(|s| processor.process(s).to_string())
+        .collect()
+}
+
+// A config parser whose key/value pairs borrow from the input text
+// -- Blank lines and lines without '=' are skipped; keys and values are trimmed.
+pub struct KeyValueParser;
+
+impl<'a> WithLifetime<'a> for KeyValueParser {
+    type Output = Vec<(&'a str, &'a str)>;
+
+    fn process(&self, input: &'a str) -> Self::Output {
+        input.lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect()
+    }
+}
+
+// Processors over two inputs whose lifetimes are independent of each other
+pub trait WithTwoLifetimes<'a, 'b> {
+    type Output;
+
+    fn process(&self, a: &'a str, b: &'b str) -> Self::Output;
+}
+
+// Drive a two-input processor over a list of pairs
+// -- for<'a, 'b> lets the processor accept each pair no matter how long either side lives.
+pub fn process_pairs<P>(processor: P, pairs: &[(&str, &str)]) -> Vec<String>
+where
+    for<'a, 'b> P: WithTwoLifetimes<'a, 'b>,
+    for<'a, 'b> <P as WithTwoLifetimes<'a, 'b>>::Output: Debug,
+{
+    pairs.iter()
+        .map(|&(a, b)| {
+            let result = processor.process(a, b);
+            format!("Processed: {:?}", result)
+        })
+        .collect()
+}
+
+// Concatenates both inputs into an owned String
+pub struct Concat;
+
+impl<'a, 'b> WithTwoLifetimes<'a, 'b> for Concat {
+    type Output = String;
+
+    fn process(&self, a: &'a str, b: &'b str) -> Self::Output {
+        format!("{}{}", a, b)
+    }
+}
+
+// Uppercase conversion produces an owned String, so it can feed another processor
+pub struct UppercaseConverter;
+
+impl<'a> WithLifetime<'a> for UppercaseConverter {
+    type Output = String;
+
+    fn process(&self, input: &'a str) -> Self::Output {
+        input.to_uppercase()
+    }
+}
+
+// Names a processor's Output without mentioning any particular lifetime
+// -- Projecting through 'static gives the compiler a concrete type to compare against;
+//    projecting through B itself inside B's own bound overflows the trait solver.
+pub trait FixedOutput {
+    type Fixed;
+}
+
+impl<T> FixedOutput for T
+where
+    T: WithLifetime<'static>,
+{
+    type Fixed = <T as WithLifetime<'static>>::Output;
+}
+
+// Composition of two processors: the output of A becomes the input of B
+// -- A's output generally isn't a &str, so it is bridged through Into<String>.
+//    The intermediate String only lives inside process(), which is why B must work
+//    for every lifetime and produce the same Output type regardless of it.
+pub struct Compose<A, B>(pub A, pub B);
+
+impl<'a, A, B> WithLifetime<'a> for Compose<A, B>
+where
+    A: WithLifetime<'a>,
+    <A as WithLifetime<'a>>::Output: Into<String>,
+    B: FixedOutput + for<'b> WithLifetime<'b, Output = <B as FixedOutput>::Fixed>,
+{
+    type Output = <B as FixedOutput>::Fixed;
+
+    fn process(&self, input: &'a str) -> Self::Output {
+        let intermediate: String = self.0.process(input).into();
+        self.1.process(&intermediate)
+    }
+}
+
+// Convenience constructor reading left to right: compose(first, then)
+pub fn compose<A, B>(first: A, then: B) -> Compose<A, B> {
+    Compose(first, then)
+}
+
+// Adapter giving any processor a String output, via its Output's Display impl
+// -- Processors with different Output types (usize, String, ...) cannot share a trait
+//    object type; stringifying them first lets them live in one ProcessorRegistry.
+pub struct Stringify<P>(pub P);
+
+impl<'a, P> WithLifetime<'a> for Stringify<P>
+where
+    P: WithLifetime<'a>,
+    <P as WithLifetime<'a>>::Output: ToString,
+{
+    type Output = String;
+
+    fn process(&self, input: &'a str) -> Self::Output {
+        self.0.process(input).to_string()
+    }
+}
+
+// A processor usable through dynamic dispatch
+// -- WithLifetime is object safe, and the for<'a> in the object type keeps the
+//    higher-ranked guarantee: the boxed processor accepts input of any lifetime.
+pub type DynProcessor = dyn for<'a> WithLifetime<'a, Output = String>;
+
+// Processors selected by name at runtime
+#[derive(Default)]
+pub struct ProcessorRegistry {
+    processors: HashMap<String, Box<DynProcessor>>,
+}
+
+impl ProcessorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a processor under `name`, replacing any processor registered there before
+    pub fn register<P>(&mut self, name: &str, processor: P) -> &mut Self
+    where
+        P: for<'a> WithLifetime<'a, Output = String> + 'static,
+    {
+        self.processors.insert(name.to_string(), Box::new(processor));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&DynProcessor> {
+        self.processors.get(name).map(|processor| processor.as_ref())
+    }
+
+    /// Run the processor registered under `name`; None if there is none
+    pub fn run(&self, name: &str, input: &str) -> Option<String> {
+        self.get(name).map(|processor| processor.process(input))
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.processors.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_uppercase_then_word_counter() {
+        let composed = compose(UppercaseConverter, WordCounter);
+
+        assert_eq!(composed.process("hello world"), 2);
+        assert_eq!(composed.process("  spaced   out   words  "), 3);
+        assert_eq!(composed.process(""), 0);
+
+        let owned = String::from("one two three four");
+        assert_eq!(composed.process(&owned), 4);
+    }
+
+    #[test]
+    fn test_compose_nested() {
+        // Compose is itself a processor, so compositions nest
+        let composed = Compose(UppercaseConverter, Compose(UppercaseConverter, UppercaseConverter));
+        assert_eq!(composed.process("straße"), "STRASSE");
+    }
+
+    #[test]
+    fn test_compose_with_process_any_lifetime() {
+        let results = process_any_lifetime(compose(UppercaseConverter, WordCounter));
+        assert_eq!(results, vec!["Processed: 1", "Processed: 1", "Processed: 1"]);
+    }
+
+    #[test]
+    fn test_first_word_borrows_from_input() {
+        let input = "hello world";
+        let first = FirstWord.process(input);
+        assert_eq!(first, "hello");
+        assert!(std::ptr::eq(first.as_ptr(), input.as_ptr()));
+
+        assert_eq!(FirstWord.process(""), "");
+        assert_eq!(FirstWord.process("   "), "");
+        assert_eq!(FirstWord.process("  padded word"), "padded");
+    }
+
+    #[test]
+    fn test_process_borrowed_outputs() {
+        assert_eq!(process_borrowed_outputs(FirstWord), vec!["first", "second", ""]);
+    }
+
+    #[test]
+    fn test_process_inputs_empty_slice() {
+        assert!(process_inputs(WordCounter, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_process_inputs_preserves_order() {
+        let owned = String::from("three little words");
+        let inputs = ["one", "two words", owned.as_str(), ""];
+        assert_eq!(
+            process_inputs(WordCounter, &inputs),
+            vec!["Processed: 1", "Processed: 2", "Processed: 3", "Processed: 0"]
+        );
+    }
+
+    #[test]
+    fn test_key_value_parser_multi_line() {
+        let config = "host=localhost\nport=8080\n\nssl=true";
+        assert_eq!(
+            KeyValueParser.process(config),
+            vec![("host", "localhost"), ("port", "8080"), ("ssl", "true")]
+        );
+    }
+
+    #[test]
+    fn test_key_value_parser_skips_lines_without_equals() {
+        let config = "name=demo\n# just a comment\nmode=fast";
+        assert_eq!(KeyValueParser.process(config), vec![("name", "demo"), ("mode", "fast")]);
+    }
+
+    #[test]
+    fn test_key_value_parser_trims_whitespace() {
+        let config = String::from("  key  =  spaced value  \n\tother\t=\tx ");
+        assert_eq!(
+            KeyValueParser.process(&config),
+            vec![("key", "spaced value"), ("other", "x")]
+        );
+    }
+
+    #[test]
+    fn test_concat_combines_inputs() {
+        assert_eq!(Concat.process("outer ", "inner"), "outer inner");
+        assert_eq!(
+            process_pairs(Concat, &[("a", "b"), ("", "only b"), ("only a", "")]),
+            vec!["Processed: \"ab\"", "Processed: \"only b\"", "Processed: \"only a\""]
+        );
+    }
+
+    #[test]
+    fn test_two_lifetimes_can_differ() {
+        // Output borrows only from `a`, so it may outlive `b`
+        struct KeepFirst;
+
+        impl<'a, 'b> WithTwoLifetimes<'a, 'b> for KeepFirst {
+            type Output = &'a str;
+
+            fn process(&self, a: &'a str, _b: &'b str) -> Self::Output {
+                a
+            }
+        }
+
+        let outer = String::from("outer lives longer");
+        let kept = {
+            let inner = String::from("inner is dropped first");
+            KeepFirst.process(&outer, &inner)
+        };
+        assert_eq!(kept, "outer lives longer");
+
+        let combined = {
+            let inner = String::from(" + short-lived");
+            Concat.process(&outer, &inner)
+        };
+        assert_eq!(combined, "outer lives longer + short-lived");
+    }
+
+    #[test]
+    fn test_registry_runs_processor_by_name() {
+        let mut registry = ProcessorRegistry::new();
+        registry
+            .register("upper", UppercaseConverter)
+            .register("words", Stringify(WordCounter));
+        assert_eq!(registry.names(), vec!["upper", "words"]);
+
+        assert_eq!(registry.run("upper", "hello"), Some("HELLO".to_string()));
+        let owned = String::from("three short words");
+        assert_eq!(registry.run("words", &owned), Some("3".to_string()));
+        assert_eq!(registry.get("words").map(|p| p.process("")), Some("0".to_string()));
+
+        // Registering again under the same name replaces the processor
+        registry.register("words", compose(UppercaseConverter, UppercaseConverter));
+        assert_eq!(registry.run("words", "abc"), Some("ABC".to_string()));
+    }
+
+    #[test]
+    fn test_registry_unknown_name_is_none() {
+        let mut registry = ProcessorRegistry::new();
+        assert!(registry.get("missing").is_none());
+        registry.register("first", Stringify(FirstWord));
+        assert_eq!(registry.run("missing", "input"), None);
+        assert_eq!(registry.run("first", "  padded word"), Some("padded".to_string()));
+    }
+}
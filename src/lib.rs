@@ -1,3 +1,5 @@
+#![forbid(unsafe_code)]
+
 pub mod custom_types;
 
 pub use custom_types::const_generic;
@@ -6,4 +8,5 @@ pub use custom_types::state_machine;
 pub use custom_types::container;
 pub use custom_types::with_lifetime;
 pub use custom_types::typesafe_builder;
-pub use custom_types::functor_monad;
\ No newline at end of file
+pub use custom_types::functor_monad;
+pub use custom_types::sparse_vec;
\ No newline at end of file